@@ -0,0 +1,143 @@
+//!
+//! The ABI value type.
+//!
+
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The ABI value type.
+///
+/// Only the static value types are supported, which occupy a single field in the encoding.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    /// The unsigned integer of the specified bitlength.
+    Uint(usize),
+    /// The signed integer of the specified bitlength.
+    Int(usize),
+    /// The account address.
+    Address,
+    /// The boolean.
+    Bool,
+    /// The fixed-size byte array of the specified length.
+    FixedBytes(usize),
+}
+
+impl AbiType {
+    ///
+    /// Checks that the type parameters are valid.
+    ///
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Uint(bitlength) | Self::Int(bitlength)
+                if *bitlength == 0
+                    || *bitlength > compiler_common::BITLENGTH_FIELD
+                    || *bitlength % compiler_common::BITLENGTH_BYTE != 0 =>
+            {
+                anyhow::bail!("Invalid ABI type `{}`", self)
+            }
+            Self::FixedBytes(length) if *length == 0 || *length > compiler_common::SIZE_FIELD => {
+                anyhow::bail!("Invalid ABI type `{}`", self)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Cleans up the `value` according to the type, so it becomes ABI-encoded.
+    ///
+    /// The higher bits of unsigned integers and addresses are cleared, signed integers are
+    /// sign-extended, booleans are normalized, and the lower bytes of byte arrays are cleared.
+    ///
+    pub fn cleanup<'ctx, D>(
+        &self,
+        context: &Context<'ctx, D>,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        match self {
+            Self::Uint(bitlength) if *bitlength == compiler_common::BITLENGTH_FIELD => value,
+            Self::Int(bitlength) if *bitlength == compiler_common::BITLENGTH_FIELD => value,
+            Self::FixedBytes(length) if *length == compiler_common::SIZE_FIELD => value,
+            Self::Uint(bitlength) => Self::cleanup_unsigned(context, value, *bitlength),
+            Self::Address => {
+                Self::cleanup_unsigned(context, value, Context::<D>::BITLENGTH_ADDRESS)
+            }
+            Self::Int(bitlength) => {
+                let truncated = context.builder().build_int_truncate(
+                    value,
+                    context.integer_type(*bitlength),
+                    "abi_cleanup_signed_truncated",
+                );
+                context.builder().build_int_s_extend(
+                    truncated,
+                    context.field_type(),
+                    "abi_cleanup_signed_extended",
+                )
+            }
+            Self::Bool => {
+                let is_true = context.builder().build_int_compare(
+                    inkwell::IntPredicate::NE,
+                    value,
+                    context.field_const(0),
+                    "abi_cleanup_bool_is_true",
+                );
+                context.builder().build_int_z_extend_or_bit_cast(
+                    is_true,
+                    context.field_type(),
+                    "abi_cleanup_bool",
+                )
+            }
+            Self::FixedBytes(length) => {
+                let mask = context.builder().build_left_shift(
+                    context.field_type().const_all_ones(),
+                    context.field_const(
+                        (compiler_common::BITLENGTH_BYTE * (compiler_common::SIZE_FIELD - length))
+                            as u64,
+                    ),
+                    "abi_cleanup_bytes_mask",
+                );
+                context
+                    .builder()
+                    .build_and(value, mask, "abi_cleanup_bytes")
+            }
+        }
+    }
+
+    ///
+    /// Clears the bits of `value` higher than `bitlength`.
+    ///
+    fn cleanup_unsigned<'ctx, D>(
+        context: &Context<'ctx, D>,
+        value: inkwell::values::IntValue<'ctx>,
+        bitlength: usize,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let mask = context.builder().build_right_shift(
+            context.field_type().const_all_ones(),
+            context.field_const((compiler_common::BITLENGTH_FIELD - bitlength) as u64),
+            false,
+            "abi_cleanup_unsigned_mask",
+        );
+        context
+            .builder()
+            .build_and(value, mask, "abi_cleanup_unsigned")
+    }
+}
+
+impl std::fmt::Display for AbiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uint(bitlength) => write!(f, "uint{}", bitlength),
+            Self::Int(bitlength) => write!(f, "int{}", bitlength),
+            Self::Address => write!(f, "address"),
+            Self::Bool => write!(f, "bool"),
+            Self::FixedBytes(length) => write!(f, "bytes{}", length),
+        }
+    }
+}
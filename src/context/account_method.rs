@@ -0,0 +1,92 @@
+//!
+//! The account abstraction lifecycle method.
+//!
+
+///
+/// The account abstraction lifecycle method.
+///
+/// The methods are called by the bootloader while processing a transaction on behalf of the
+/// account contract.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountMethod {
+    /// Validates the transaction signature and nonce.
+    ValidateTransaction,
+    /// Executes the validated transaction.
+    ExecuteTransaction,
+    /// Executes the transaction signed by the account, but submitted by another one.
+    ExecuteTransactionFromOutside,
+    /// Pays the transaction fee to the bootloader.
+    PayForTransaction,
+    /// Prepares the paymaster flow before the fee is paid.
+    PrepareForPaymaster,
+}
+
+impl AccountMethod {
+    /// The bootloader address, which is the only allowed caller of the system methods.
+    pub const BOOTLOADER_ADDRESS: &'static str = "8001";
+
+    /// The transaction structure ABI signature.
+    pub const TRANSACTION_SIGNATURE: &'static str =
+        "(uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256[4],bytes,bytes,bytes32[],bytes,bytes)";
+
+    /// The expected signature length, which is the `r`, `s`, and `v` of an ECDSA signature.
+    pub const SIGNATURE_LENGTH: usize = 65;
+
+    /// The error thrown if a system method is called not by the bootloader.
+    pub const ERROR_NOT_BOOTLOADER: &'static str = "Only the bootloader can call this method";
+
+    /// The error thrown if the signature is malformed or out of the calldata bounds.
+    pub const ERROR_INVALID_SIGNATURE: &'static str = "Invalid signature";
+
+    ///
+    /// Returns the method ABI signature.
+    ///
+    pub fn signature(&self) -> String {
+        match self {
+            Self::ValidateTransaction => format!(
+                "validateTransaction(bytes32,bytes32,{})",
+                Self::TRANSACTION_SIGNATURE
+            ),
+            Self::ExecuteTransaction => format!(
+                "executeTransaction(bytes32,bytes32,{})",
+                Self::TRANSACTION_SIGNATURE
+            ),
+            Self::ExecuteTransactionFromOutside => format!(
+                "executeTransactionFromOutside({})",
+                Self::TRANSACTION_SIGNATURE
+            ),
+            Self::PayForTransaction => format!(
+                "payForTransaction(bytes32,bytes32,{})",
+                Self::TRANSACTION_SIGNATURE
+            ),
+            Self::PrepareForPaymaster => format!(
+                "prepareForPaymaster(bytes32,bytes32,{})",
+                Self::TRANSACTION_SIGNATURE
+            ),
+        }
+    }
+
+    ///
+    /// Whether the method can only be called by the bootloader.
+    ///
+    pub fn is_bootloader_only(&self) -> bool {
+        !matches!(self, Self::ExecuteTransactionFromOutside)
+    }
+
+    ///
+    /// Whether the method returns the magic value on success.
+    ///
+    /// The magic value is the method selector, so that a contract which is not an account cannot
+    /// be validated by accident.
+    ///
+    pub fn has_magic(&self) -> bool {
+        matches!(self, Self::ValidateTransaction)
+    }
+}
+
+impl std::fmt::Display for AccountMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.signature())
+    }
+}
@@ -17,6 +17,22 @@ pub enum AddressSpace {
     Child,
 }
 
+impl AddressSpace {
+    ///
+    /// Returns the alignment of the memory accesses in `address_space`.
+    ///
+    /// The stack is accessed with 256-bit alignment, whereas the other address spaces are
+    /// byte-addressable.
+    ///
+    pub fn alignment_of(address_space: inkwell::AddressSpace) -> u32 {
+        if address_space == inkwell::AddressSpace::from(Self::Stack) {
+            compiler_common::SIZE_FIELD as u32
+        } else {
+            1
+        }
+    }
+}
+
 impl From<AddressSpace> for inkwell::AddressSpace {
     fn from(value: AddressSpace) -> Self {
         match value {
@@ -27,3 +43,14 @@ impl From<AddressSpace> for inkwell::AddressSpace {
         }
     }
 }
+
+impl TryFrom<inkwell::AddressSpace> for AddressSpace {
+    type Error = anyhow::Error;
+
+    fn try_from(value: inkwell::AddressSpace) -> Result<Self, Self::Error> {
+        [Self::Stack, Self::Heap, Self::Parent, Self::Child]
+            .into_iter()
+            .find(|address_space| inkwell::AddressSpace::from(*address_space) == value)
+            .ok_or_else(|| anyhow::anyhow!("Unknown address space {:?}", value))
+    }
+}
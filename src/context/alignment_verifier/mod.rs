@@ -0,0 +1,243 @@
+//!
+//! The alignment verifier.
+//!
+
+pub mod violation;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::gas_report::GasReport;
+
+use self::violation::Violation;
+
+///
+/// The alignment verifier.
+///
+/// Checks the alignment policy which LLVM does not verify, but the backend relies on: the stack
+/// accesses are aligned to 256 bits, and the heap, parent, and child accesses to 1 byte.
+///
+#[derive(Debug, Default)]
+pub struct AlignmentVerifier {
+    /// The policy violations.
+    pub violations: Vec<Violation>,
+}
+
+impl AlignmentVerifier {
+    /// The prefix of the LLVM intrinsics, whose pointer parameters are not decorated.
+    const LLVM_INTRINSIC_PREFIX: &'static str = "llvm.";
+
+    ///
+    /// Walks the loads, stores, allocations, and call sites of the `module`.
+    ///
+    pub fn new(module: &inkwell::module::Module) -> Self {
+        let mut verifier = Self::default();
+
+        for function in module.get_functions() {
+            let function_name = function.get_name().to_string_lossy().to_string();
+            for block in function.get_basic_blocks() {
+                let mut instruction = block.get_first_instruction();
+                while let Some(current) = instruction {
+                    instruction = current.get_next_instruction();
+
+                    match current.get_opcode() {
+                        inkwell::values::InstructionOpcode::Load => {
+                            verifier.check_access(function_name.as_str(), current, 0)
+                        }
+                        inkwell::values::InstructionOpcode::Store => {
+                            verifier.check_access(function_name.as_str(), current, 1)
+                        }
+                        inkwell::values::InstructionOpcode::Alloca => verifier.check_alignment(
+                            function_name.as_str(),
+                            current,
+                            AddressSpace::alignment_of(AddressSpace::Stack.into()),
+                        ),
+                        inkwell::values::InstructionOpcode::Call
+                        | inkwell::values::InstructionOpcode::Invoke => {
+                            verifier.check_call_site(function_name.as_str(), current)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        verifier
+    }
+
+    ///
+    /// Returns an error listing the violations, if there are any.
+    ///
+    pub fn check(&self, module_name: &str) -> anyhow::Result<()> {
+        if self.violations.is_empty() {
+            return Ok(());
+        }
+
+        let violations = self
+            .violations
+            .iter()
+            .map(Violation::to_string)
+            .collect::<Vec<String>>()
+            .join("\n");
+        anyhow::bail!(
+            "Module `{}` violates the alignment policy:\n{}",
+            module_name,
+            violations
+        )
+    }
+
+    ///
+    /// Checks the load or store `instruction`, whose pointer is the operand at `pointer_index`.
+    ///
+    fn check_access(
+        &mut self,
+        function_name: &str,
+        instruction: inkwell::values::InstructionValue,
+        pointer_index: u32,
+    ) {
+        let address_space = match instruction
+            .get_operand(pointer_index)
+            .and_then(|operand| operand.left())
+        {
+            Some(inkwell::values::BasicValueEnum::PointerValue(pointer)) => {
+                pointer.get_type().get_address_space()
+            }
+            _ => return,
+        };
+
+        self.check_alignment(
+            function_name,
+            instruction,
+            AddressSpace::alignment_of(address_space),
+        );
+    }
+
+    ///
+    /// Checks that the `instruction` alignment is equal to `expected`.
+    ///
+    fn check_alignment(
+        &mut self,
+        function_name: &str,
+        instruction: inkwell::values::InstructionValue,
+        expected: u32,
+    ) {
+        let found = instruction.get_alignment().ok();
+        if found != Some(expected) {
+            self.violations.push(Violation::new(
+                function_name.to_owned(),
+                Self::instruction_text(instruction),
+                expected,
+                found,
+            ));
+        }
+    }
+
+    ///
+    /// Checks the call site `instruction`.
+    ///
+    /// The alignment attributes are only exposed in the textual representation, so they must
+    /// be either 256-bit or 1-byte, and a 256-bit one must be present if a non-null stack
+    /// pointer is passed to a callee other than an intrinsic or the exception throwing function.
+    ///
+    fn check_call_site(
+        &mut self,
+        function_name: &str,
+        instruction: inkwell::values::InstructionValue,
+    ) {
+        let text = Self::instruction_text(instruction);
+        let stack_alignment = AddressSpace::alignment_of(AddressSpace::Stack.into());
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        for window in words.windows(2) {
+            if window[0] != "align" {
+                continue;
+            }
+            let found = match window[1]
+                .trim_end_matches(|character: char| !character.is_ascii_digit())
+                .parse::<u32>()
+            {
+                Ok(found) => found,
+                Err(_) => continue,
+            };
+            if found != 1 && found != stack_alignment {
+                self.violations.push(Violation::new(
+                    function_name.to_owned(),
+                    text.clone(),
+                    stack_alignment,
+                    Some(found),
+                ));
+            }
+        }
+
+        let is_exempt = GasReport::callee_name(instruction)
+            .map(|name| {
+                name.starts_with(Self::LLVM_INTRINSIC_PREFIX)
+                    || name == compiler_common::LLVM_FUNCTION_CXA_THROW
+            })
+            .unwrap_or_default();
+        if is_exempt {
+            return;
+        }
+
+        // The last operand is the callee, which is also a pointer.
+        let arguments_count = instruction.get_num_operands().saturating_sub(1);
+        let has_stack_pointer = (0..arguments_count).any(|index| {
+            matches!(
+                instruction.get_operand(index).and_then(|operand| operand.left()),
+                Some(inkwell::values::BasicValueEnum::PointerValue(pointer))
+                    if pointer.get_type().get_address_space() == AddressSpace::Stack.into()
+                        && !pointer.is_null()
+            )
+        });
+        let expected_attribute = format!("align {}", stack_alignment);
+        if has_stack_pointer && !text.contains(expected_attribute.as_str()) {
+            self.violations.push(Violation::new(
+                function_name.to_owned(),
+                text,
+                stack_alignment,
+                None,
+            ));
+        }
+    }
+
+    ///
+    /// Returns the trimmed textual representation of the `instruction`.
+    ///
+    fn instruction_text(instruction: inkwell::values::InstructionValue) -> String {
+        instruction.print_to_string().to_string().trim().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use inkwell::types::BasicType;
+    use inkwell::values::BasicValue;
+
+    use crate::context::address_space::AddressSpace;
+
+    use super::AlignmentVerifier;
+
+    #[test]
+    fn throw_block_passes() {
+        let llvm = inkwell::context::Context::create();
+        let module = llvm.create_module("test");
+        let builder = llvm.create_builder();
+
+        let pointer_type = llvm.i8_type().ptr_type(AddressSpace::Stack.into());
+        let cxa_throw = module.add_function(
+            compiler_common::LLVM_FUNCTION_CXA_THROW,
+            llvm.void_type()
+                .fn_type(vec![pointer_type.as_basic_type_enum(); 3].as_slice(), false),
+            Some(inkwell::module::Linkage::External),
+        );
+        let function = module.add_function("test", llvm.void_type().fn_type(&[], false), None);
+        let throw_block = llvm.append_basic_block(function, "throw");
+        builder.position_at_end(throw_block);
+        builder.build_call(
+            cxa_throw,
+            vec![pointer_type.const_null().as_basic_value_enum(); 3].as_slice(),
+            compiler_common::LLVM_FUNCTION_CXA_THROW,
+        );
+        builder.build_unreachable();
+
+        assert!(AlignmentVerifier::new(&module).check("test").is_ok());
+    }
+}
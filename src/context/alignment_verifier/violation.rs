@@ -0,0 +1,49 @@
+//!
+//! The alignment verifier violation.
+//!
+
+///
+/// The alignment verifier violation.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The function containing the instruction.
+    pub function: String,
+    /// The instruction IR.
+    pub instruction: String,
+    /// The alignment required by the policy.
+    pub expected: u32,
+    /// The actual alignment, if it is set.
+    pub found: Option<u32>,
+}
+
+impl Violation {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(function: String, instruction: String, expected: u32, found: Option<u32>) -> Self {
+        Self {
+            function,
+            instruction,
+            expected,
+            found,
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.found {
+            Some(found) => write!(
+                f,
+                "function `{}`: expected alignment {}, found {}: {}",
+                self.function, self.expected, found, self.instruction
+            ),
+            None => write!(
+                f,
+                "function `{}`: expected alignment {}, found none: {}",
+                self.function, self.expected, self.instruction
+            ),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //! The LLVM argument with metadata.
 //!
 
+use num::Zero;
+
 ///
 /// The LLVM argument with metadata.
 ///
@@ -11,6 +13,8 @@ pub struct Argument<'ctx> {
     pub value: inkwell::values::BasicValueEnum<'ctx>,
     /// The original AST value. Used mostly for string literals.
     pub original: Option<String>,
+    /// The compile-time constant value in the two's complement form, if it is known.
+    pub constant: Option<num::BigUint>,
 }
 
 impl<'ctx> Argument<'ctx> {
@@ -21,6 +25,7 @@ impl<'ctx> Argument<'ctx> {
         Self {
             value,
             original: None,
+            constant: Self::constant_of(value),
         }
     }
 
@@ -34,15 +39,97 @@ impl<'ctx> Argument<'ctx> {
         Self {
             value,
             original: Some(original),
+            constant: Self::constant_of(value),
         }
     }
 
+    ///
+    /// Sets the compile-time constant value known to the frontend.
+    ///
+    pub fn with_constant(mut self, constant: num::BigUint) -> Self {
+        self.constant = Some(constant);
+        self
+    }
+
     ///
     /// Returns the inner LLVM value.
     ///
     pub fn to_llvm(&self) -> inkwell::values::BasicValueEnum<'ctx> {
         self.value
     }
+
+    ///
+    /// Whether the argument is a compile-time constant.
+    ///
+    pub fn is_constant(&self) -> bool {
+        self.constant.is_some()
+    }
+
+    ///
+    /// Returns the compile-time constant value, if it is known and fits into 64 bits.
+    ///
+    pub fn constant_u64(&self) -> Option<u64> {
+        self.constant.as_ref().and_then(|constant| {
+            if constant.bits() > u64::BITS as u64 {
+                return None;
+            }
+            Some(constant.iter_u64_digits().next().unwrap_or_default())
+        })
+    }
+
+    ///
+    /// Whether the compile-time constant value is negative, if it is known.
+    ///
+    pub fn is_negative(&self) -> Option<bool> {
+        let bit_width = match self.value {
+            inkwell::values::BasicValueEnum::IntValue(value) => value.get_type().get_bit_width(),
+            _ => return None,
+        };
+        self.constant
+            .as_ref()
+            .map(|constant| constant.bit((bit_width - 1) as u64))
+    }
+
+    ///
+    /// Extracts the compile-time constant value of an integer `value`.
+    ///
+    /// LLVM only exposes the constants which fit into 64 bits, so the wider ones are folded into
+    /// 64-bit words, which must rebuild the original constant.
+    ///
+    pub fn constant_of(value: inkwell::values::BasicValueEnum<'ctx>) -> Option<num::BigUint> {
+        let value = match value {
+            inkwell::values::BasicValueEnum::IntValue(value) if value.is_const() => value,
+            _ => return None,
+        };
+
+        let r#type = value.get_type();
+        let bit_width = r#type.get_bit_width();
+        if bit_width <= u64::BITS {
+            return value.get_zero_extended_constant().map(num::BigUint::from);
+        }
+
+        let word_type = r#type.get_context().i64_type();
+        let mut words = Vec::new();
+        for offset in (0..bit_width).step_by(u64::BITS as usize) {
+            let word = value
+                .const_rshr(r#type.const_int(offset as u64, false))
+                .const_truncate(word_type)
+                .get_zero_extended_constant()?;
+            words.push(word);
+        }
+        if r#type.const_int_arbitrary_precision(words.as_slice()) != value {
+            return None;
+        }
+
+        Some(
+            words
+                .into_iter()
+                .rev()
+                .fold(num::BigUint::zero(), |constant, word| {
+                    (constant << u64::BITS) + num::BigUint::from(word)
+                }),
+        )
+    }
 }
 
 impl<'ctx> From<inkwell::values::BasicValueEnum<'ctx>> for Argument<'ctx> {
@@ -50,3 +137,43 @@ impl<'ctx> From<inkwell::values::BasicValueEnum<'ctx>> for Argument<'ctx> {
         Self::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use num::One;
+    use num::Zero;
+
+    use super::Argument;
+
+    ///
+    /// Extracts the constant of the 256-bit `words`, which are ordered from the least significant.
+    ///
+    fn constant_of(words: &[u64]) -> Option<num::BigUint> {
+        let llvm = inkwell::context::Context::create();
+        let value = llvm
+            .custom_width_int_type(compiler_common::BITLENGTH_FIELD as u32)
+            .const_int_arbitrary_precision(words);
+        Argument::constant_of(value.into())
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(constant_of(&[0; 4]), Some(num::BigUint::zero()));
+    }
+
+    #[test]
+    fn minus_one() {
+        assert_eq!(
+            constant_of(&[u64::MAX; 4]),
+            Some((num::BigUint::one() << compiler_common::BITLENGTH_FIELD) - num::BigUint::one())
+        );
+    }
+
+    #[test]
+    fn highest_bit() {
+        assert_eq!(
+            constant_of(&[0, 0, 0, 1 << 63]),
+            Some(num::BigUint::one() << (compiler_common::BITLENGTH_FIELD - 1))
+        );
+    }
+}
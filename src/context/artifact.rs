@@ -0,0 +1,103 @@
+//!
+//! The LLVM generator artifact.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::context::dump::Dump;
+use crate::context::eliminated_write::EliminatedWrite;
+use crate::context::size_report::SizeReport;
+use crate::context::statistics::Statistics;
+use crate::context::warning::Warning;
+
+///
+/// The LLVM generator artifact.
+///
+/// Contains the auxiliary data collected from the translated module.
+///
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Artifact {
+    /// The code size report.
+    pub size_report: SizeReport,
+    /// The warnings emitted during the translation.
+    pub warnings: Vec<Warning>,
+    /// The optimization statistics, if collected.
+    pub statistics: Option<Statistics>,
+    /// The submodules linked into the module.
+    pub submodules: Vec<String>,
+    /// The storage writes eliminated by the storage write combining.
+    pub eliminated_writes: Vec<EliminatedWrite>,
+    /// The module-level named metadata, e.g. the toolchain versions.
+    pub metadata: BTreeMap<String, Vec<String>>,
+    /// The dispatched function names by the hexadecimal 4-byte selector.
+    pub selectors: BTreeMap<String, String>,
+    /// The dumps requested by the dump flags.
+    pub dumps: Vec<Dump>,
+}
+
+impl Artifact {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        size_report: SizeReport,
+        warnings: Vec<Warning>,
+        statistics: Option<Statistics>,
+    ) -> Self {
+        Self {
+            size_report,
+            warnings,
+            statistics,
+            submodules: Vec::new(),
+            eliminated_writes: Vec::new(),
+            metadata: BTreeMap::new(),
+            selectors: BTreeMap::new(),
+            dumps: Vec::new(),
+        }
+    }
+
+    ///
+    /// Sets the submodules linked into the module.
+    ///
+    pub fn with_submodules(mut self, submodules: Vec<String>) -> Self {
+        self.submodules = submodules;
+        self
+    }
+
+    ///
+    /// Sets the storage writes eliminated by the storage write combining.
+    ///
+    pub fn with_eliminated_writes(mut self, eliminated_writes: Vec<EliminatedWrite>) -> Self {
+        self.eliminated_writes = eliminated_writes;
+        self
+    }
+
+    ///
+    /// Sets the module-level named metadata.
+    ///
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, Vec<String>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    ///
+    /// Sets the dispatched function names by the 4-byte selector.
+    ///
+    pub fn with_selectors(mut self, selectors: &BTreeMap<u32, String>) -> Self {
+        self.selectors = selectors
+            .iter()
+            .map(|(selector, name)| (format!("0x{:08x}", selector), name.to_owned()))
+            .collect();
+        self
+    }
+
+    ///
+    /// Sets the dumps requested by the dump flags.
+    ///
+    pub fn with_dumps(mut self, dumps: Vec<Dump>) -> Self {
+        self.dumps = dumps;
+        self
+    }
+}
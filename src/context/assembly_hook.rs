@@ -0,0 +1,50 @@
+//!
+//! The assembly text post-processing hook.
+//!
+
+///
+/// The assembly text post-processing hook.
+///
+/// Is applied to the emitted assembly before assembling, e.g. for the target-specific peepholes
+/// or annotations.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct AssemblyHook {
+    /// The post-processing function.
+    hook: fn(String) -> String,
+}
+
+impl AssemblyHook {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(hook: fn(String) -> String) -> Self {
+        Self { hook }
+    }
+
+    ///
+    /// Applies the hook to the `assembly` text.
+    ///
+    pub fn apply(&self, assembly: String) -> String {
+        (self.hook)(assembly)
+    }
+
+    ///
+    /// Counts the instructions in the `assembly` text.
+    ///
+    /// The empty lines, comments, labels, and directives are not counted.
+    ///
+    pub fn count_instructions(assembly: &str) -> usize {
+        assembly
+            .lines()
+            .map(str::trim)
+            .filter(|line| {
+                !line.is_empty()
+                    && !line.starts_with(';')
+                    && !line.starts_with('#')
+                    && !line.starts_with('.')
+                    && !line.ends_with(':')
+            })
+            .count()
+    }
+}
@@ -0,0 +1,59 @@
+//!
+//! The incremental compilation cache.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The incremental compilation cache.
+///
+/// Contains the optimized module bitcode, keyed by the hash of the function inputs and the
+/// context settings it was built with. If neither has changed, the module is restored instead
+/// of being translated and optimized again.
+///
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Cache {
+    /// The hash of the function inputs and the context settings.
+    pub key: String,
+    /// The optimized module bitcode.
+    pub bitcode: Vec<u8>,
+}
+
+impl Cache {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(key: String, bitcode: Vec<u8>) -> Self {
+        Self { key, bitcode }
+    }
+
+    ///
+    /// Hashes the function `input`, e.g. the source code snippet or the pre-IR representation.
+    ///
+    pub fn hash(input: &[u8]) -> String {
+        format!("{:x}", md5::compute(input))
+    }
+
+    ///
+    /// Computes the cache key of the function input `hashes` keyed by name, and the context
+    /// `settings` description.
+    ///
+    pub fn key(hashes: &BTreeMap<String, String>, settings: &str) -> String {
+        let mut preimage = String::new();
+        for (name, hash) in hashes.iter() {
+            preimage.push_str(format!("{}={}\n", name, hash).as_str());
+        }
+        preimage.push_str(settings);
+        Self::hash(preimage.as_bytes())
+    }
+
+    ///
+    /// Checks whether the cache has been built with the same `key`.
+    ///
+    pub fn is_valid(&self, key: &str) -> bool {
+        !self.bitcode.is_empty() && self.key == key
+    }
+}
@@ -0,0 +1,212 @@
+//!
+//! The external call definition word.
+//!
+
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The external call definition word.
+///
+/// The word passed to the call intrinsics is laid out as follows, starting from the lowest bit:
+/// 32 bits of flags, 160 bits of the callee address, and 32 bits of the gas limit.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct CallDefinition<'ctx> {
+    /// The callee address.
+    pub address: inkwell::values::IntValue<'ctx>,
+    /// The call flags.
+    pub flags: Option<inkwell::values::IntValue<'ctx>>,
+    /// The gas limit.
+    pub gas: Option<inkwell::values::IntValue<'ctx>>,
+    /// The transferred value. Non-zero values are currently forbidden by the target.
+    pub value: Option<inkwell::values::IntValue<'ctx>>,
+}
+
+impl<'ctx> CallDefinition<'ctx> {
+    /// The flags bit offset.
+    pub const OFFSET_FLAGS: usize = 0;
+    /// The flags bitlength.
+    pub const BITLENGTH_FLAGS: usize = compiler_common::BITLENGTH_X32;
+    /// The address bit offset.
+    pub const OFFSET_ADDRESS: usize = Self::OFFSET_FLAGS + Self::BITLENGTH_FLAGS;
+    /// The gas limit bit offset.
+    pub const OFFSET_GAS: usize = Self::OFFSET_ADDRESS + 160;
+    /// The gas limit bitlength.
+    pub const BITLENGTH_GAS: usize = compiler_common::BITLENGTH_X32;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(address: inkwell::values::IntValue<'ctx>) -> Self {
+        Self {
+            address,
+            flags: None,
+            gas: None,
+            value: None,
+        }
+    }
+
+    ///
+    /// Sets the call flags.
+    ///
+    pub fn with_flags(mut self, flags: inkwell::values::IntValue<'ctx>) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    ///
+    /// Sets the gas limit.
+    ///
+    pub fn with_gas(mut self, gas: inkwell::values::IntValue<'ctx>) -> Self {
+        self.gas = Some(gas);
+        self
+    }
+
+    ///
+    /// Sets the transferred value.
+    ///
+    pub fn with_value(mut self, value: inkwell::values::IntValue<'ctx>) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    ///
+    /// Emits the check throwing on non-zero transferred values, if the value is set.
+    ///
+    pub fn check_value<D>(&self, context: &mut Context<'ctx, D>)
+    where
+        D: Dependency,
+    {
+        if let Some(value) = self.value {
+            crate::evm::check_value_zero(context, value);
+        }
+    }
+
+    ///
+    /// Encodes the call definition word.
+    ///
+    /// The value is not a part of the word and must be checked with `check_value`.
+    ///
+    pub fn encode<D>(&self, context: &Context<'ctx, D>) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let address = context.build_address_mask(self.address);
+        let mut word = context.builder().build_left_shift(
+            address,
+            context.field_const(Self::OFFSET_ADDRESS as u64),
+            "call_definition_address",
+        );
+
+        if let Some(flags) = self.flags {
+            let flags =
+                Self::build_field(context, flags, Self::OFFSET_FLAGS, Self::BITLENGTH_FLAGS);
+            word = context
+                .builder()
+                .build_or(word, flags, "call_definition_with_flags");
+        }
+        if let Some(gas) = self.gas {
+            let gas = Self::build_field(context, gas, Self::OFFSET_GAS, Self::BITLENGTH_GAS);
+            word = context
+                .builder()
+                .build_or(word, gas, "call_definition_with_gas");
+        }
+
+        word
+    }
+
+    ///
+    /// Decodes the callee address from the call definition `word`.
+    ///
+    pub fn decode_address<D>(
+        context: &Context<'ctx, D>,
+        word: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let address = context.builder().build_right_shift(
+            word,
+            context.field_const(Self::OFFSET_ADDRESS as u64),
+            false,
+            "call_definition_address_decoded",
+        );
+        context.build_address_mask(address)
+    }
+
+    ///
+    /// Decodes the call flags from the call definition `word`.
+    ///
+    pub fn decode_flags<D>(
+        context: &Context<'ctx, D>,
+        word: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        Self::decode_field(context, word, Self::OFFSET_FLAGS, Self::BITLENGTH_FLAGS)
+    }
+
+    ///
+    /// Decodes the gas limit from the call definition `word`.
+    ///
+    pub fn decode_gas<D>(
+        context: &Context<'ctx, D>,
+        word: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        Self::decode_field(context, word, Self::OFFSET_GAS, Self::BITLENGTH_GAS)
+    }
+
+    ///
+    /// Masks `value` to `bitlength` bits and shifts it to `offset`.
+    ///
+    fn build_field<D>(
+        context: &Context<'ctx, D>,
+        value: inkwell::values::IntValue<'ctx>,
+        offset: usize,
+        bitlength: usize,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let value = context.builder().build_and(
+            value,
+            context.field_const(u64::MAX >> (u64::BITS as usize - bitlength)),
+            "call_definition_field_masked",
+        );
+        context.builder().build_left_shift(
+            value,
+            context.field_const(offset as u64),
+            "call_definition_field_shifted",
+        )
+    }
+
+    ///
+    /// Extracts `bitlength` bits at `offset` from `word`.
+    ///
+    fn decode_field<D>(
+        context: &Context<'ctx, D>,
+        word: inkwell::values::IntValue<'ctx>,
+        offset: usize,
+        bitlength: usize,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let value = context.builder().build_right_shift(
+            word,
+            context.field_const(offset as u64),
+            false,
+            "call_definition_field_decoded",
+        );
+        context.builder().build_and(
+            value,
+            context.field_const(u64::MAX >> (u64::BITS as usize - bitlength)),
+            "call_definition_field_decoded_masked",
+        )
+    }
+}
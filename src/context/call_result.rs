@@ -0,0 +1,33 @@
+//!
+//! The contract call result.
+//!
+
+///
+/// The contract call result.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct CallResult<'ctx> {
+    /// The success flag, which is 1 on success and 0 otherwise.
+    pub status: inkwell::values::IntValue<'ctx>,
+    /// The return data size, saved right after the call.
+    pub return_data_size: inkwell::values::IntValue<'ctx>,
+    /// The heap pointer to the output region.
+    pub output_pointer: inkwell::values::PointerValue<'ctx>,
+}
+
+impl<'ctx> CallResult<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        status: inkwell::values::IntValue<'ctx>,
+        return_data_size: inkwell::values::IntValue<'ctx>,
+        output_pointer: inkwell::values::PointerValue<'ctx>,
+    ) -> Self {
+        Self {
+            status,
+            return_data_size,
+            output_pointer,
+        }
+    }
+}
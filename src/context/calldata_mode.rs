@@ -0,0 +1,23 @@
+//!
+//! The calldata access mode.
+//!
+
+///
+/// The calldata access mode.
+///
+/// Applied to the calldata loads, copies, and argument decoding.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalldataMode {
+    /// The calldata is copied to the heap on the first access, and the accesses read the copy.
+    Staged,
+    /// The calldata is read directly from the parent memory, and only the requested ranges are
+    /// copied, which avoids the per-call overhead for large calldata.
+    Lazy,
+}
+
+impl Default for CalldataMode {
+    fn default() -> Self {
+        Self::Lazy
+    }
+}
@@ -0,0 +1,36 @@
+//!
+//! The blob context values backend.
+//!
+
+///
+/// The blob context values backend.
+///
+/// Selects how `blobhash` and `blobbasefee` are lowered, since most targets have no blobs.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blobs {
+    /// The configured constants, each parsed as a hexadecimal with the `0x` prefix or a decimal.
+    Constant {
+        /// The value returned by `blobhash` for every index.
+        hash: String,
+        /// The value returned by `blobbasefee`.
+        base_fee: String,
+    },
+    /// The target context values.
+    Context {
+        /// The context value identifier of the first blob hash. The hash at the index `i` is
+        /// read from the identifier `hash + i`.
+        hash: u64,
+        /// The context value identifier of the blob base fee.
+        base_fee: u64,
+    },
+}
+
+impl Default for Blobs {
+    fn default() -> Self {
+        Self::Constant {
+            hash: "0".to_owned(),
+            base_fee: "1".to_owned(),
+        }
+    }
+}
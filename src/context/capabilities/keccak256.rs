@@ -0,0 +1,22 @@
+//!
+//! The `keccak256` hashing backend.
+//!
+
+///
+/// The `keccak256` hashing backend.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Keccak256 {
+    /// The static call to the precompile at the specified address.
+    Precompile(String),
+    /// The native target intrinsic.
+    ///
+    /// The translation fails if the target does not provide the intrinsic.
+    Intrinsic,
+}
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Self::Precompile(compiler_common::ABI_ADDRESS_KECCAK256.to_owned())
+    }
+}
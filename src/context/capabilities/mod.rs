@@ -0,0 +1,82 @@
+//!
+//! The target capabilities.
+//!
+
+pub mod blobs;
+pub mod keccak256;
+pub mod program_counter;
+pub mod system_intrinsics;
+pub mod value_simulation;
+
+use self::blobs::Blobs;
+use self::keccak256::Keccak256;
+use self::program_counter::ProgramCounter;
+use self::system_intrinsics::SystemIntrinsics;
+use self::value_simulation::ValueSimulation;
+
+///
+/// The target capabilities.
+///
+/// Describes the target features the lowerings may rely on, so that the frontends do not have to
+/// know about them.
+///
+#[derive(Debug, Default, Clone)]
+pub struct Capabilities {
+    /// The `keccak256` hashing backend.
+    pub keccak256: Keccak256,
+    /// The call value simulation.
+    pub value_simulation: ValueSimulation,
+    /// The blob context values backend.
+    pub blobs: Blobs,
+    /// The `pc` instruction backend.
+    pub program_counter: ProgramCounter,
+    /// The system intrinsics support.
+    pub system_intrinsics: SystemIntrinsics,
+}
+
+impl Capabilities {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(keccak256: Keccak256) -> Self {
+        Self {
+            keccak256,
+            value_simulation: ValueSimulation::default(),
+            blobs: Blobs::default(),
+            program_counter: ProgramCounter::default(),
+            system_intrinsics: SystemIntrinsics::default(),
+        }
+    }
+
+    ///
+    /// Sets the call value simulation.
+    ///
+    pub fn with_value_simulation(mut self, value_simulation: ValueSimulation) -> Self {
+        self.value_simulation = value_simulation;
+        self
+    }
+
+    ///
+    /// Sets the blob context values backend.
+    ///
+    pub fn with_blobs(mut self, blobs: Blobs) -> Self {
+        self.blobs = blobs;
+        self
+    }
+
+    ///
+    /// Sets the `pc` instruction backend.
+    ///
+    pub fn with_program_counter(mut self, program_counter: ProgramCounter) -> Self {
+        self.program_counter = program_counter;
+        self
+    }
+
+    ///
+    /// Sets the system intrinsics support.
+    ///
+    pub fn with_system_intrinsics(mut self, system_intrinsics: SystemIntrinsics) -> Self {
+        self.system_intrinsics = system_intrinsics;
+        self
+    }
+}
@@ -0,0 +1,23 @@
+//!
+//! The `pc` instruction backend.
+//!
+
+///
+/// The `pc` instruction backend.
+///
+/// The target has no program counter observable by the contract, so legacy assembly using `pc`
+/// is either rejected or given a deterministic substitute.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramCounter {
+    /// The instruction is rejected with an error pointing at its source location.
+    Unsupported,
+    /// The instruction returns the index of the current basic block in the current function.
+    BlockIndex,
+}
+
+impl Default for ProgramCounter {
+    fn default() -> Self {
+        Self::Unsupported
+    }
+}
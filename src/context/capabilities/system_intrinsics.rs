@@ -0,0 +1,24 @@
+//!
+//! The system intrinsics support.
+//!
+
+///
+/// The system intrinsics support.
+///
+/// The mimic call and the context setter intrinsics are only provided by the targets built with
+/// the system contract extensions, so their lowerings are rejected unless the target declares
+/// them. The system mode must be enabled as well to emit them.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemIntrinsics {
+    /// The intrinsics are not provided, and their lowerings fail with an error.
+    Unsupported,
+    /// The intrinsics are provided by the target.
+    Supported,
+}
+
+impl Default for SystemIntrinsics {
+    fn default() -> Self {
+        Self::Unsupported
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! The call value simulation.
+//!
+
+///
+/// The call value simulation.
+///
+/// Selects how `callvalue`, `balance`, and the value-bearing calls are lowered on targets
+/// without the native value.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSimulation {
+    /// The value is not simulated. The call value is always zero, `balance` is rejected, and
+    /// the non-zero value calls throw.
+    Disabled,
+    /// The value is a token managed by the system contract at the specified address.
+    ///
+    /// The call value is read with a static call to the token contract, which is a new frame, so
+    /// the contract must track the value passed to the current frame of its caller and return it
+    /// from `msgValue()`. Otherwise `callvalue` yields a wrong value.
+    Token(String),
+}
+
+impl ValueSimulation {
+    /// The token contract call value getter signature, returning the value of the caller frame.
+    pub const SIGNATURE_MSG_VALUE: &'static str = "msgValue()";
+
+    /// The token contract balance getter signature.
+    pub const SIGNATURE_BALANCE_OF: &'static str = "balanceOf(address)";
+
+    /// The token contract transfer signature.
+    pub const SIGNATURE_TRANSFER: &'static str = "transfer(address,uint256)";
+}
+
+impl Default for ValueSimulation {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
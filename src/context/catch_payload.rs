@@ -0,0 +1,40 @@
+//!
+//! The catch block payload.
+//!
+
+///
+/// The catch block payload.
+///
+/// Binds the landing pad result and the thrown data to the values available to the catch
+/// handlers, e.g. for decoding `Error(string)` and `Panic(uint256)`.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct CatchPayload<'ctx> {
+    /// The exception pointer from the landing pad.
+    pub exception: inkwell::values::PointerValue<'ctx>,
+    /// The exception type selector from the landing pad.
+    pub selector: inkwell::values::IntValue<'ctx>,
+    /// The pointer to the thrown data in the parent memory.
+    pub data_pointer: inkwell::values::PointerValue<'ctx>,
+    /// The thrown data size from the parent memory header.
+    pub data_size: inkwell::values::IntValue<'ctx>,
+}
+
+impl<'ctx> CatchPayload<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        exception: inkwell::values::PointerValue<'ctx>,
+        selector: inkwell::values::IntValue<'ctx>,
+        data_pointer: inkwell::values::PointerValue<'ctx>,
+        data_size: inkwell::values::IntValue<'ctx>,
+    ) -> Self {
+        Self {
+            exception,
+            selector,
+            data_pointer,
+            data_size,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//!
+//! The LLVM constant pool.
+//!
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+///
+/// The LLVM constant pool.
+///
+/// Interns the field constants parsed from strings, e.g. the precompile addresses and
+/// selectors, so the same literal is only parsed once per context.
+///
+#[derive(Debug, Default)]
+pub struct ConstantPool<'ctx> {
+    /// The interned constants, keyed by the source string.
+    values: RefCell<HashMap<String, inkwell::values::IntValue<'ctx>>>,
+}
+
+impl<'ctx> ConstantPool<'ctx> {
+    ///
+    /// Returns the interned constant for `key`, creating it with `create` if it is missing.
+    ///
+    /// The failed creations are not interned.
+    ///
+    pub fn get_or_try_insert<F>(
+        &self,
+        key: &str,
+        create: F,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        F: FnOnce() -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+    {
+        if let Some(value) = self.values.borrow().get(key) {
+            return Ok(*value);
+        }
+
+        let value = create()?;
+        self.values.borrow_mut().insert(key.to_owned(), value);
+        Ok(value)
+    }
+
+    ///
+    /// Returns the number of the interned constants.
+    ///
+    pub fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    ///
+    /// Whether the pool is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.values.borrow().is_empty()
+    }
+}
@@ -0,0 +1,16 @@
+//!
+//! The debug tracer.
+//!
+
+///
+/// The debug tracer.
+///
+/// Receives the values passed to `Context::build_debug_trace` in debug builds.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugTracer {
+    /// The values are emitted as events with the tag hash as the only topic.
+    Event,
+    /// The values are sent to the tracer system contract at the specified address.
+    Contract(String),
+}
@@ -0,0 +1,30 @@
+//!
+//! The intermediate representation dump.
+//!
+
+use serde::Serialize;
+
+use crate::dump_flag::DumpFlag;
+
+///
+/// The intermediate representation dump.
+///
+/// Is collected if the corresponding dump flag is set and surfaced in the artifact, so the
+/// frontends decide where to write it.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Dump {
+    /// The dump flag which has requested the dump.
+    pub flag: DumpFlag,
+    /// The dump text.
+    pub text: String,
+}
+
+impl Dump {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(flag: DumpFlag, text: String) -> Self {
+        Self { flag, text }
+    }
+}
@@ -0,0 +1,43 @@
+//!
+//! The eliminated storage write.
+//!
+
+use serde::Serialize;
+
+///
+/// The eliminated storage write.
+///
+/// Describes a storage write overwritten later in the same basic block without being observed.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EliminatedWrite {
+    /// The function name.
+    pub function: String,
+    /// The basic block name.
+    pub block: String,
+    /// The constant slot, as printed in the LLVM IR.
+    pub slot: String,
+}
+
+impl EliminatedWrite {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(function: String, block: String, slot: String) -> Self {
+        Self {
+            function,
+            block,
+            slot,
+        }
+    }
+}
+
+impl std::fmt::Display for EliminatedWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: the write to the slot `{}` is overwritten",
+            self.function, self.block, self.slot
+        )
+    }
+}
@@ -0,0 +1,82 @@
+//!
+//! The LLVM generator context error.
+//!
+
+use crate::context::code_type::CodeType;
+use crate::context::evm_version::EvmVersion;
+use crate::context::privileged_operation::PrivilegedOperation;
+use crate::context::restricted_instruction::RestrictedInstruction;
+use crate::context::versioned_instruction::VersionedInstruction;
+use crate::context::warning::span::Span as WarningSpan;
+use crate::context::warning::Warning;
+
+///
+/// The LLVM generator context error.
+///
+/// Is wrapped into `anyhow::Error`, so the frontends may downcast it to get the details.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A privileged operation has been requested outside of the system mode.
+    PrivilegedOperation(PrivilegedOperation),
+    /// A warning has been promoted to an error.
+    PromotedWarning(Warning),
+    /// An instruction has been requested outside of the code type it is allowed in.
+    RestrictedInstruction {
+        /// The requested instruction.
+        instruction: RestrictedInstruction,
+        /// The code type being translated.
+        code_type: CodeType,
+    },
+    /// An instruction has been requested before the EVM version it is introduced in.
+    UnsupportedInstruction {
+        /// The requested instruction.
+        instruction: VersionedInstruction,
+        /// The selected EVM version.
+        evm_version: EvmVersion,
+    },
+    /// The `pc` instruction has been requested on a target without its emulation.
+    UnsupportedProgramCounter(Option<WarningSpan>),
+    /// A privileged operation has been requested on a target without the system intrinsics.
+    UnsupportedSystemIntrinsic(PrivilegedOperation),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PrivilegedOperation(operation) => {
+                write!(f, "The {} is only allowed in the system mode", operation)
+            }
+            Self::PromotedWarning(warning) => write!(f, "{}", warning),
+            Self::RestrictedInstruction {
+                instruction,
+                code_type,
+            } => write!(
+                f,
+                "The instruction `{}` is not allowed in {} code",
+                instruction, code_type
+            ),
+            Self::UnsupportedInstruction {
+                instruction,
+                evm_version,
+            } => write!(
+                f,
+                "The instruction `{}` is not available in the EVM version `{}`, but only since `{}`",
+                instruction,
+                evm_version,
+                instruction.introduced_in()
+            ),
+            Self::UnsupportedProgramCounter(Some(span)) => {
+                write!(f, "{}: The instruction `pc` is not supported by the target", span)
+            }
+            Self::UnsupportedProgramCounter(None) => {
+                write!(f, "The instruction `pc` is not supported by the target")
+            }
+            Self::UnsupportedSystemIntrinsic(operation) => {
+                write!(f, "The {} is not supported by the target", operation)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
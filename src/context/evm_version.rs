@@ -0,0 +1,52 @@
+//!
+//! The EVM version.
+//!
+
+///
+/// The EVM version.
+///
+/// Gates the instructions introduced by the hard forks. The versions are ordered, so an
+/// instruction is available in its introducing version and every later one.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EvmVersion {
+    /// The Paris hard fork, with `prevrandao` replacing `difficulty`.
+    Paris,
+    /// The Shanghai hard fork, with `push0`.
+    Shanghai,
+    /// The Cancun hard fork, with `mcopy`, transient storage, and blobs.
+    Cancun,
+    /// The Prague hard fork.
+    Prague,
+}
+
+impl Default for EvmVersion {
+    fn default() -> Self {
+        Self::Cancun
+    }
+}
+
+impl TryFrom<&str> for EvmVersion {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "paris" => Self::Paris,
+            "shanghai" => Self::Shanghai,
+            "cancun" => Self::Cancun,
+            "prague" => Self::Prague,
+            _ => anyhow::bail!("Invalid EVM version: {}", value),
+        })
+    }
+}
+
+impl std::fmt::Display for EvmVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Paris => write!(f, "paris"),
+            Self::Shanghai => write!(f, "shanghai"),
+            Self::Cancun => write!(f, "cancun"),
+            Self::Prague => write!(f, "prague"),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//!
+//! The contract exit kind.
+//!
+
+///
+/// The contract exit kind.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitKind {
+    /// The successful exit with the return data.
+    Return,
+    /// The failing exit with the revert data.
+    Revert,
+    /// The successful exit without data.
+    Stop,
+    /// The failing exit without data.
+    Invalid,
+}
+
+impl ExitKind {
+    ///
+    /// Whether the exit is successful, so the state changes are kept.
+    ///
+    pub fn is_successful(&self) -> bool {
+        matches!(self, Self::Return | Self::Stop)
+    }
+
+    ///
+    /// Whether the exit passes the heap data to the parent.
+    ///
+    pub fn has_data(&self) -> bool {
+        matches!(self, Self::Return | Self::Revert)
+    }
+}
+
+impl std::fmt::Display for ExitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Return => write!(f, "return"),
+            Self::Revert => write!(f, "revert"),
+            Self::Stop => write!(f, "stop"),
+            Self::Invalid => write!(f, "invalid"),
+        }
+    }
+}
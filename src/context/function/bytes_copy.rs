@@ -0,0 +1,230 @@
+//!
+//! The LLVM bytes copying runtime function.
+//!
+
+use crate::context::address_space::AddressSpace;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM bytes copying runtime function.
+///
+/// Copies a length-prefixed `bytes` or `string` value from the `source` to the `destination`
+/// address space. The function takes the source and destination offsets, copies the length word
+/// and data, zero-pads the data to the field boundary, and returns the size of the written
+/// region. The parent and child offsets are relative to their data regions. One function is
+/// emitted per pair of address spaces on the first request.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BytesCopy {
+    /// The source address space.
+    pub source: AddressSpace,
+    /// The destination address space.
+    pub destination: AddressSpace,
+}
+
+impl BytesCopy {
+    /// The runtime function name prefix.
+    pub const NAME_PREFIX: &'static str = "__bytes_copy";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    /// # Errors
+    /// If there is no memory copy intrinsic between the address spaces.
+    ///
+    pub fn new(source: AddressSpace, destination: AddressSpace) -> anyhow::Result<Self> {
+        let object = Self {
+            source,
+            destination,
+        };
+        object.intrinsic()?;
+        Ok(object)
+    }
+
+    ///
+    /// Returns the runtime function name.
+    ///
+    pub fn name(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            Self::NAME_PREFIX,
+            Self::address_space_name(self.source),
+            Self::address_space_name(self.destination)
+        )
+    }
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(&self, context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let name = self.name();
+        let intrinsic = self.intrinsic()?;
+
+        context.build_runtime_function(name.as_str(), 2, 1, |context, parameters| {
+            let source_offset = parameters[0];
+            let destination_offset = parameters[1];
+
+            let source_length_pointer = self.pointer(
+                context,
+                self.source,
+                source_offset,
+                "bytes_copy_source_length_pointer",
+            );
+            let length = context
+                .build_load(source_length_pointer, "bytes_copy_length")
+                .into_int_value();
+            let destination_length_pointer = self.pointer(
+                context,
+                self.destination,
+                destination_offset,
+                "bytes_copy_destination_length_pointer",
+            );
+            context.build_store(destination_length_pointer, length);
+
+            let padded_length = context.builder().build_int_add(
+                length,
+                context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
+                context.value_name("bytes_copy_length_rounded_up").as_str(),
+            );
+            let padded_length = context.builder().build_and(
+                padded_length,
+                context
+                    .field_const((compiler_common::SIZE_FIELD - 1) as u64)
+                    .const_not(),
+                context.value_name("bytes_copy_padded_length").as_str(),
+            );
+            let size = context.builder().build_int_add(
+                padded_length,
+                context.field_const(compiler_common::SIZE_FIELD as u64),
+                context.value_name("bytes_copy_size").as_str(),
+            );
+
+            let is_padding_required = context.builder().build_int_compare(
+                inkwell::IntPredicate::NE,
+                padded_length,
+                length,
+                context
+                    .value_name("bytes_copy_is_padding_required")
+                    .as_str(),
+            );
+            context.build_if(is_padding_required, "bytes_copy_padding", |context| {
+                let last_word_offset = context.builder().build_int_add(
+                    destination_offset,
+                    padded_length,
+                    context.value_name("bytes_copy_last_word_offset").as_str(),
+                );
+                let last_word_pointer = self.pointer(
+                    context,
+                    self.destination,
+                    last_word_offset,
+                    "bytes_copy_last_word_pointer",
+                );
+                context.build_store(last_word_pointer, context.field_const(0));
+                Ok(())
+            })?;
+
+            let source_data_offset = context.builder().build_int_add(
+                source_offset,
+                context.field_const(compiler_common::SIZE_FIELD as u64),
+                context.value_name("bytes_copy_source_data_offset").as_str(),
+            );
+            let source_data_pointer = self.pointer(
+                context,
+                self.source,
+                source_data_offset,
+                "bytes_copy_source_data_pointer",
+            );
+            let destination_data_offset = context.builder().build_int_add(
+                destination_offset,
+                context.field_const(compiler_common::SIZE_FIELD as u64),
+                context
+                    .value_name("bytes_copy_destination_data_offset")
+                    .as_str(),
+            );
+            let destination_data_pointer = self.pointer(
+                context,
+                self.destination,
+                destination_data_offset,
+                "bytes_copy_destination_data_pointer",
+            );
+            context.build_memcpy_non_empty(
+                intrinsic,
+                destination_data_pointer,
+                source_data_pointer,
+                length,
+                "bytes_copy_data",
+            );
+            Ok(Some(size))
+        })
+    }
+
+    ///
+    /// Returns the memory copy intrinsic between the address spaces.
+    ///
+    fn intrinsic(&self) -> anyhow::Result<IntrinsicFunction> {
+        Ok(match (self.source, self.destination) {
+            (AddressSpace::Heap, AddressSpace::Heap) => IntrinsicFunction::MemoryCopy,
+            (AddressSpace::Parent, AddressSpace::Heap) => IntrinsicFunction::MemoryCopyFromParent,
+            (AddressSpace::Heap, AddressSpace::Parent) => IntrinsicFunction::MemoryCopyToParent,
+            (AddressSpace::Child, AddressSpace::Heap) => IntrinsicFunction::MemoryCopyFromChild,
+            (AddressSpace::Heap, AddressSpace::Child) => IntrinsicFunction::MemoryCopyToChild,
+            (AddressSpace::Child, AddressSpace::Parent) => {
+                IntrinsicFunction::MemoryCopyFromChildToParent
+            }
+            (source, destination) => anyhow::bail!(
+                "Copying bytes from the {} to the {} memory is not supported",
+                Self::address_space_name(source),
+                Self::address_space_name(destination)
+            ),
+        })
+    }
+
+    ///
+    /// Returns the pointer to `offset` in `address_space`.
+    ///
+    /// The parent and child offsets are shifted to their data regions.
+    ///
+    fn pointer<'ctx, D>(
+        &self,
+        context: &mut Context<'ctx, D>,
+        address_space: AddressSpace,
+        offset: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> inkwell::values::PointerValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let offset = match address_space {
+            AddressSpace::Parent | AddressSpace::Child => context.builder().build_int_add(
+                offset,
+                context.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+                ),
+                context
+                    .value_name(format!("{}_offset", name).as_str())
+                    .as_str(),
+            ),
+            AddressSpace::Stack | AddressSpace::Heap => offset,
+        };
+        context.access_memory(offset, address_space, name)
+    }
+
+    ///
+    /// Returns the `address_space` name used in the function names and errors.
+    ///
+    fn address_space_name(address_space: AddressSpace) -> &'static str {
+        match address_space {
+            AddressSpace::Stack => "stack",
+            AddressSpace::Heap => "heap",
+            AddressSpace::Parent => "parent",
+            AddressSpace::Child => "child",
+        }
+    }
+}
@@ -4,10 +4,8 @@
 
 use std::marker::PhantomData;
 
-use inkwell::values::BasicValue;
-
 use crate::context::code_type::CodeType;
-use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::long_return_policy::LongReturnPolicy;
 use crate::context::Context;
 use crate::Dependency;
 use crate::WriteLLVM;
@@ -45,22 +43,20 @@ where
     ///
     /// Writes the contract constructor executed flag.
     ///
-    fn write_is_executed_flag(context: &mut Context<D>) {
+    fn write_is_executed_flag(context: &mut Context<D>) -> anyhow::Result<()> {
         let storage_key_string = compiler_common::keccak256(
             compiler_common::ABI_STORAGE_IS_CONSTRUCTOR_EXECUTED.as_bytes(),
         );
-        let storage_key_value = context.field_const_str(storage_key_string.as_str());
+        let storage_key_value = context
+            .field_const_hex(storage_key_string.as_str())
+            .expect("Always valid");
 
-        let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageStore);
-        context.build_call(
-            intrinsic,
-            &[
-                context.field_const(1).as_basic_value_enum(),
-                storage_key_value.as_basic_value_enum(),
-                context.field_const(0).as_basic_value_enum(),
-            ],
+        context.build_storage_store(
+            context.field_const(1),
+            storage_key_value,
+            context.field_const(0),
             "is_executed_flag_store",
-        );
+        )
     }
 }
 
@@ -75,15 +71,14 @@ where
             compiler_common::LLVM_FUNCTION_CONSTRUCTOR,
             function_type,
             Some(inkwell::module::Linkage::Private),
-        );
+        )?;
 
         self.inner.declare(context)
     }
 
     fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
         let function = context
-            .functions
-            .get(compiler_common::LLVM_FUNCTION_CONSTRUCTOR)
+            .get_function(compiler_common::LLVM_FUNCTION_CONSTRUCTOR)
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("Contract constructor not found"))?;
         context.set_function(function);
@@ -101,11 +96,12 @@ where
             _ => context.build_unconditional_branch(context.function().return_block),
         }
 
-        context.build_throw_block(true);
-        context.build_catch_block(true);
+        context.build_throw_block(LongReturnPolicy::Return);
+        context.build_catch_block(LongReturnPolicy::Return);
 
         context.set_basic_block(context.function().return_block);
-        Self::write_is_executed_flag(context);
+        context.build_immutables_flush()?;
+        Self::write_is_executed_flag(context)?;
         context.build_return(None);
 
         Ok(())
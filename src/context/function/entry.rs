@@ -6,6 +6,7 @@ use inkwell::values::BasicValue;
 
 use crate::context::address_space::AddressSpace;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::long_return_policy::LongReturnPolicy;
 use crate::context::Context;
 use crate::Dependency;
 use crate::WriteLLVM;
@@ -28,13 +29,9 @@ impl Entry {
     where
         D: Dependency,
     {
-        let header = context.read_header(AddressSpace::Parent);
-        context.builder().build_right_shift(
-            header,
-            context.field_const((8 * compiler_common::BITLENGTH_BYTE) as u64),
-            false,
-            "header_constructor_bit",
-        )
+        context
+            .read_header(AddressSpace::Parent)
+            .constructor_flag(context)
     }
 
     ///
@@ -49,7 +46,9 @@ impl Entry {
         let storage_key_string = compiler_common::keccak256(
             compiler_common::ABI_STORAGE_IS_CONSTRUCTOR_EXECUTED.as_bytes(),
         );
-        let storage_key_value = context.field_const_str(storage_key_string.as_str());
+        let storage_key_value = context
+            .field_const_hex(storage_key_string.as_str())
+            .expect("Always valid");
 
         let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageLoad);
         context
@@ -76,27 +75,24 @@ where
             compiler_common::LLVM_FUNCTION_ENTRY,
             function_type,
             Some(inkwell::module::Linkage::External),
-        );
+        )?;
 
         Ok(())
     }
 
     fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
         let function = context
-            .functions
-            .get(compiler_common::LLVM_FUNCTION_ENTRY)
+            .get_function(compiler_common::LLVM_FUNCTION_ENTRY)
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("Contract entry not found"))?;
         context.set_function(function);
 
         let constructor = context
-            .functions
-            .get(compiler_common::LLVM_FUNCTION_CONSTRUCTOR)
+            .get_function(compiler_common::LLVM_FUNCTION_CONSTRUCTOR)
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("Contract constructor not found"))?;
         let selector = context
-            .functions
-            .get(compiler_common::LLVM_FUNCTION_SELECTOR)
+            .get_function(compiler_common::LLVM_FUNCTION_SELECTOR)
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("Contract selector not found"))?;
 
@@ -106,41 +102,45 @@ where
             inkwell::IntPredicate::EQ,
             is_executed_flag,
             context.field_const(0),
-            "is_executed_flag_zero",
+            context.value_name("is_executed_flag_zero").as_str(),
         );
         let is_executed_flag_one = context.builder().build_int_compare(
             inkwell::IntPredicate::EQ,
             is_executed_flag,
             context.field_const(1),
-            "is_executed_flag_one",
+            context.value_name("is_executed_flag_one").as_str(),
         );
         let is_constructor_call = Self::is_constructor_call(context);
         let is_constructor_call_zero = context.builder().build_int_compare(
             inkwell::IntPredicate::EQ,
             is_constructor_call,
             context.field_const(0),
-            "is_constructor_call_zero",
+            context.value_name("is_constructor_call_zero").as_str(),
         );
         let is_constructor_call_one = context.builder().build_int_compare(
             inkwell::IntPredicate::EQ,
             is_constructor_call,
             context.field_const(1),
-            "is_constructor_call_one",
+            context.value_name("is_constructor_call_one").as_str(),
         );
         let is_error_double_constructor_call = context.builder().build_and(
             is_constructor_call_one,
             is_executed_flag_one,
-            "is_error_double_constructor_call",
+            context
+                .value_name("is_error_double_constructor_call")
+                .as_str(),
         );
         let is_error_expected_constructor_call = context.builder().build_and(
             is_constructor_call_zero,
             is_executed_flag_zero,
-            "is_error_expected_constructor_call",
+            context
+                .value_name("is_error_expected_constructor_call")
+                .as_str(),
         );
         let is_constructor_call = context.builder().build_and(
             is_constructor_call_one,
             is_executed_flag_zero,
-            "is_constructor_call",
+            context.value_name("is_constructor_call").as_str(),
         );
 
         let double_constructor_call_block =
@@ -190,8 +190,8 @@ where
         context.build_invoke(selector.value, &[], "selector_call");
         context.build_unconditional_branch(context.function().return_block);
 
-        context.build_throw_block(false);
-        context.build_catch_block(false);
+        context.build_throw_block(LongReturnPolicy::Propagate);
+        context.build_catch_block(LongReturnPolicy::Propagate);
 
         context.set_basic_block(context.function().return_block);
         context.build_return(None);
@@ -0,0 +1,145 @@
+//!
+//! The LLVM error encoder runtime function.
+//!
+
+use crate::context::address_space::AddressSpace;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM error encoder runtime function.
+///
+/// Writes the ABI-encoded error with a dynamic message, e.g. `Error(string)`, to the parent
+/// memory. The function is emitted once per module on the first request.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorEncoder {}
+
+impl ErrorEncoder {
+    /// The runtime function name.
+    pub const NAME: &'static str = "__write_error";
+
+    /// The `Error(string)` selector.
+    pub const SELECTOR_ERROR_STRING: u64 = 0x08c379a0;
+
+    /// The number of the field arguments: the selector, message offset, and message length.
+    pub const ARGUMENTS_COUNT: usize = 3;
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        context.build_runtime_function(
+            Self::NAME,
+            Self::ARGUMENTS_COUNT,
+            0,
+            |context, parameters| {
+                Self::build_body(context, parameters[0], parameters[1], parameters[2]);
+                Ok(None)
+            },
+        )
+    }
+
+    ///
+    /// Builds the function body.
+    ///
+    /// The layout is the 4-byte selector, followed by the message offset, length, and the
+    /// message itself, padded to the field size.
+    ///
+    fn build_body<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+        selector: inkwell::values::IntValue<'ctx>,
+        message_offset: inkwell::values::IntValue<'ctx>,
+        message_length: inkwell::values::IntValue<'ctx>,
+    ) where
+        D: Dependency,
+    {
+        let data_offset =
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64;
+        let selector_pointer = context.access_memory(
+            context.field_const(data_offset),
+            AddressSpace::Parent,
+            "error_selector_pointer",
+        );
+        let selector = context.build_left_align_bytes(selector, compiler_common::SIZE_X32);
+        context.build_store(selector_pointer, selector);
+
+        let offset_pointer = context.access_memory(
+            context.field_const(data_offset + compiler_common::SIZE_X32 as u64),
+            AddressSpace::Parent,
+            "error_message_offset_pointer",
+        );
+        context.build_store(
+            offset_pointer,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+        );
+
+        let length_pointer = context.access_memory(
+            context.field_const(
+                data_offset + (compiler_common::SIZE_X32 + compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Parent,
+            "error_message_length_pointer",
+        );
+        context.build_store(length_pointer, message_length);
+
+        let message_destination_offset =
+            data_offset + (compiler_common::SIZE_X32 + compiler_common::SIZE_FIELD * 2) as u64;
+        let padding_offset = context.builder().build_int_add(
+            context.field_const(message_destination_offset),
+            message_length,
+            context.value_name("error_message_padding_offset").as_str(),
+        );
+        let padding_pointer = context.access_memory(
+            padding_offset,
+            AddressSpace::Parent,
+            "error_message_padding_pointer",
+        );
+        context.build_store(padding_pointer, context.field_const(0));
+
+        let message_destination = context.access_memory(
+            context.field_const(message_destination_offset),
+            AddressSpace::Parent,
+            "error_message_destination_pointer",
+        );
+        let message_source = context.access_memory(
+            message_offset,
+            AddressSpace::Heap,
+            "error_message_source_pointer",
+        );
+        context.build_memcpy(
+            IntrinsicFunction::MemoryCopyToParent,
+            message_destination,
+            message_source,
+            message_length,
+            "error_message_memcpy_to_parent",
+        );
+
+        let message_length_padded = context.builder().build_and(
+            context.builder().build_int_add(
+                message_length,
+                context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
+                context
+                    .value_name("error_message_length_rounded_up")
+                    .as_str(),
+            ),
+            context
+                .field_const((compiler_common::SIZE_FIELD - 1) as u64)
+                .const_not(),
+            context.value_name("error_message_length_padded").as_str(),
+        );
+        let size = context.builder().build_int_add(
+            context
+                .field_const((compiler_common::SIZE_X32 + compiler_common::SIZE_FIELD * 2) as u64),
+            message_length_padded,
+            context.value_name("error_size").as_str(),
+        );
+        context.write_header_size(size, AddressSpace::Parent);
+    }
+}
@@ -3,6 +3,7 @@
 //!
 
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 
 use crate::context::function::block::key::Key as BlockKey;
 use crate::context::function::block::Block;
@@ -17,6 +18,8 @@ pub struct EVMData<'ctx> {
     pub blocks: BTreeMap<BlockKey, Vec<Block<'ctx>>>,
     /// The function stack size.
     pub stack_size: usize,
+    /// The contract context values loaded in the function entry block, by identifier.
+    pub context_values: HashMap<u64, inkwell::values::IntValue<'ctx>>,
 }
 
 impl<'ctx> EVMData<'ctx> {
@@ -27,6 +30,7 @@ impl<'ctx> EVMData<'ctx> {
         Self {
             blocks: BTreeMap::new(),
             stack_size,
+            context_values: HashMap::new(),
         }
     }
 
@@ -75,4 +79,18 @@ impl<'ctx> EVMData<'ctx> {
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))
     }
+
+    ///
+    /// Returns the cached context value with the `identifier`, if it is cached.
+    ///
+    pub fn context_value(&self, identifier: u64) -> Option<inkwell::values::IntValue<'ctx>> {
+        self.context_values.get(&identifier).copied()
+    }
+
+    ///
+    /// Caches the context `value` with the `identifier`.
+    ///
+    pub fn cache_context_value(&mut self, identifier: u64, value: inkwell::values::IntValue<'ctx>) {
+        self.context_values.insert(identifier, value);
+    }
 }
@@ -0,0 +1,89 @@
+//!
+//! The LLVM external interface.
+//!
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use inkwell::types::BasicType;
+
+use crate::context::Context;
+use crate::Dependency;
+use crate::WriteLLVM;
+
+///
+/// The LLVM external interface.
+///
+/// Declares the functions defined in another object without defining them, so the call graph
+/// of the module is complete when several objects are compiled together. The declarations are
+/// named as the object would mangle them, i.e. `<object>::<name>`.
+///
+#[derive(Debug)]
+pub struct ExternalInterface<D>
+where
+    D: Dependency,
+{
+    /// The name of the object defining the functions.
+    object: String,
+    /// The declared function input and output sizes, by name.
+    functions: BTreeMap<String, (usize, usize)>,
+    /// The `D` phantom data.
+    _pd: PhantomData<D>,
+}
+
+impl<D> ExternalInterface<D>
+where
+    D: Dependency,
+{
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(object: String) -> Self {
+        Self {
+            object,
+            functions: BTreeMap::new(),
+            _pd: PhantomData::default(),
+        }
+    }
+
+    ///
+    /// Adds a function with `input_size` field arguments and `output_size` field return values.
+    ///
+    pub fn with_function(mut self, name: String, input_size: usize, output_size: usize) -> Self {
+        self.functions.insert(name, (input_size, output_size));
+        self
+    }
+
+    ///
+    /// Returns the symbol name the function `name` is declared with.
+    ///
+    pub fn symbol_name(&self, name: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.object,
+            Context::<D>::MANGLING_SEPARATOR,
+            name
+        )
+    }
+}
+
+impl<D> WriteLLVM<D> for ExternalInterface<D>
+where
+    D: Dependency,
+{
+    fn declare(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        for (name, (input_size, output_size)) in self.functions.iter() {
+            let function_type = context.function_type(
+                *output_size,
+                vec![context.field_type().as_basic_type_enum(); *input_size],
+            );
+            context.declare_external_function(self.symbol_name(name).as_str(), function_type)?;
+        }
+
+        Ok(())
+    }
+
+    fn into_llvm(self, _context: &mut Context<D>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
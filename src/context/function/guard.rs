@@ -0,0 +1,230 @@
+//!
+//! The LLVM guard runtime function.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM guard runtime function.
+///
+/// The guards are emitted once per module on the first request.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Guard {
+    /// Throws if the storage slot passed as the argument is set, and sets it otherwise.
+    ReentrancyEnter,
+    /// Clears the storage slot passed as the argument.
+    ReentrancyExit,
+    /// Increments the call depth and throws if it exceeds the limit passed as the argument.
+    CallDepthEnter,
+    /// Decrements the call depth.
+    CallDepthExit,
+}
+
+impl Guard {
+    /// The reentrancy error message.
+    pub const ERROR_REENTRANT_CALL: &'static str = "Reentrant call";
+    /// The call depth error message.
+    pub const ERROR_CALL_DEPTH_EXCEEDED: &'static str = "Call depth exceeded";
+
+    /// The call depth counter global variable name.
+    pub const GLOBAL_CALL_DEPTH: &'static str = "__call_depth";
+
+    ///
+    /// Returns the runtime function name.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ReentrancyEnter => "__reentrancy_guard_enter",
+            Self::ReentrancyExit => "__reentrancy_guard_exit",
+            Self::CallDepthEnter => "__call_depth_enter",
+            Self::CallDepthExit => "__call_depth_exit",
+        }
+    }
+
+    ///
+    /// Returns the number of the field arguments.
+    ///
+    pub fn arguments_count(&self) -> usize {
+        match self {
+            Self::ReentrancyEnter => 1,
+            Self::ReentrancyExit => 1,
+            Self::CallDepthEnter => 1,
+            Self::CallDepthExit => 0,
+        }
+    }
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(&self, context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        context.build_runtime_function(
+            self.name(),
+            self.arguments_count(),
+            0,
+            |context, _parameters| {
+                match self {
+                    Self::ReentrancyEnter => Self::build_reentrancy_enter(context)?,
+                    Self::ReentrancyExit => Self::build_reentrancy_exit(context)?,
+                    Self::CallDepthEnter => Self::build_call_depth_enter(context),
+                    Self::CallDepthExit => Self::build_call_depth_exit(context),
+                }
+                Ok(None)
+            },
+        )
+    }
+
+    ///
+    /// Builds the reentrancy guard entering body.
+    ///
+    fn build_reentrancy_enter<D>(context: &mut Context<D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let slot = context
+            .function()
+            .value
+            .get_nth_param(0)
+            .expect("Always exists");
+
+        let value = context
+            .build_call(
+                context.get_intrinsic_function(IntrinsicFunction::StorageLoad),
+                &[slot, context.field_const(0).as_basic_value_enum()],
+                "reentrancy_guard_value",
+            )
+            .expect("Contract storage always returns a value");
+        let is_locked = context.builder().build_int_compare(
+            inkwell::IntPredicate::NE,
+            value.into_int_value(),
+            context.field_const(0),
+            context.value_name("reentrancy_guard_is_locked").as_str(),
+        );
+
+        let locked_block = context.append_basic_block("reentrancy_guard_locked_block");
+        let unlocked_block = context.append_basic_block("reentrancy_guard_unlocked_block");
+        context.build_conditional_branch(is_locked, locked_block, unlocked_block);
+
+        context.set_basic_block(locked_block);
+        context.write_error(Self::ERROR_REENTRANT_CALL);
+        context.build_unconditional_branch(context.function().throw_block);
+
+        context.set_basic_block(unlocked_block);
+        context.build_storage_store(
+            context.field_const(1),
+            slot.into_int_value(),
+            context.field_const(0),
+            "reentrancy_guard_lock",
+        )?;
+        context.build_unconditional_branch(context.function().return_block);
+        Ok(())
+    }
+
+    ///
+    /// Builds the reentrancy guard exiting body.
+    ///
+    fn build_reentrancy_exit<D>(context: &mut Context<D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let slot = context
+            .function()
+            .value
+            .get_nth_param(0)
+            .expect("Always exists");
+
+        context.build_storage_store(
+            context.field_const(0),
+            slot.into_int_value(),
+            context.field_const(0),
+            "reentrancy_guard_unlock",
+        )?;
+        context.build_unconditional_branch(context.function().return_block);
+        Ok(())
+    }
+
+    ///
+    /// Builds the call depth guard entering body.
+    ///
+    fn build_call_depth_enter<D>(context: &mut Context<D>)
+    where
+        D: Dependency,
+    {
+        let limit = context
+            .function()
+            .value
+            .get_nth_param(0)
+            .expect("Always exists")
+            .into_int_value();
+        let depth_pointer = Self::call_depth_pointer(context);
+
+        let depth = context
+            .build_load(depth_pointer, "call_depth_value")
+            .into_int_value();
+        let depth_incremented = context.builder().build_int_add(
+            depth,
+            context.field_const(1),
+            context.value_name("call_depth_value_incremented").as_str(),
+        );
+        let is_exceeded = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            depth_incremented,
+            limit,
+            context.value_name("call_depth_is_exceeded").as_str(),
+        );
+
+        let exceeded_block = context.append_basic_block("call_depth_exceeded_block");
+        let non_exceeded_block = context.append_basic_block("call_depth_non_exceeded_block");
+        context.build_conditional_branch(is_exceeded, exceeded_block, non_exceeded_block);
+
+        context.set_basic_block(exceeded_block);
+        context.write_error(Self::ERROR_CALL_DEPTH_EXCEEDED);
+        context.build_unconditional_branch(context.function().throw_block);
+
+        context.set_basic_block(non_exceeded_block);
+        context.build_store(depth_pointer, depth_incremented);
+        context.build_unconditional_branch(context.function().return_block);
+    }
+
+    ///
+    /// Builds the call depth guard exiting body.
+    ///
+    fn build_call_depth_exit<D>(context: &mut Context<D>)
+    where
+        D: Dependency,
+    {
+        let depth_pointer = Self::call_depth_pointer(context);
+
+        let depth = context
+            .build_load(depth_pointer, "call_depth_value")
+            .into_int_value();
+        let depth_decremented = context.builder().build_int_sub(
+            depth,
+            context.field_const(1),
+            context.value_name("call_depth_value_decremented").as_str(),
+        );
+        context.build_store(depth_pointer, depth_decremented);
+        context.build_unconditional_branch(context.function().return_block);
+    }
+
+    ///
+    /// Returns the pointer to the call depth counter, declaring it on the first use.
+    ///
+    fn call_depth_pointer<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+    ) -> inkwell::values::PointerValue<'ctx>
+    where
+        D: Dependency,
+    {
+        context.get_global_field(Self::GLOBAL_CALL_DEPTH)
+    }
+}
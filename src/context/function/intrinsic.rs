@@ -21,11 +21,15 @@ pub enum Intrinsic {
     SetStorage,
     /// The event emitting.
     Event,
+    /// The native `keccak256` hashing.
+    Keccak256,
 
     /// The contract context switch.
     SwitchContext,
     /// The contract context getter.
     GetFromContext,
+    /// The contract context setter. Requires the system intrinsics capability.
+    SetToContext,
     /// The external contract call.
     FarCall,
     /// The external contract code call.
@@ -34,6 +38,12 @@ pub enum Intrinsic {
     DelegateCall,
     /// The external contract static call.
     StaticCall,
+    /// The external contract call on behalf of another account. Requires the system intrinsics
+    /// capability.
+    MimicCall,
+
+    /// The optimizer assumption.
+    Assume,
 
     /// The memory copy within the heap.
     MemoryCopy,
@@ -47,6 +57,13 @@ pub enum Intrinsic {
     MemoryCopyToChild,
     /// The memory copy from child to parent.
     MemoryCopyFromChildToParent,
+    /// The memory move within the heap, which allows the regions to overlap.
+    MemoryMove,
+
+    /// The stack allocation lifetime start marker.
+    LifetimeStart,
+    /// The stack allocation lifetime end marker.
+    LifetimeEnd,
 }
 
 impl Intrinsic {
@@ -59,13 +76,18 @@ impl Intrinsic {
             Intrinsic::StorageStore => "llvm.syncvm.sstore",
             Intrinsic::SetStorage => "llvm.syncvm.setstorage",
             Intrinsic::Event => "llvm.syncvm.event",
+            Intrinsic::Keccak256 => "llvm.syncvm.keccak256",
 
             Intrinsic::SwitchContext => "llvm.syncvm.switchcontext",
             Intrinsic::GetFromContext => "llvm.syncvm.getfromcontext",
+            Intrinsic::SetToContext => "llvm.syncvm.settocontext",
             Intrinsic::FarCall => "llvm.syncvm.farcall.rc",
             Intrinsic::CallCode => "llvm.syncvm.callcode.rc",
             Intrinsic::DelegateCall => "llvm.syncvm.delegatecall.rc",
             Intrinsic::StaticCall => "llvm.syncvm.staticcall.rc",
+            Intrinsic::MimicCall => "llvm.syncvm.mimiccall.rc",
+
+            Intrinsic::Assume => "llvm.assume",
 
             Intrinsic::MemoryCopy => "llvm.memcpy",
             Intrinsic::MemoryCopyFromParent => "llvm.memcpy",
@@ -73,6 +95,10 @@ impl Intrinsic {
             Intrinsic::MemoryCopyFromChild => "llvm.memcpy",
             Intrinsic::MemoryCopyToChild => "llvm.memcpy",
             Intrinsic::MemoryCopyFromChildToParent => "llvm.memcpy",
+            Intrinsic::MemoryMove => "llvm.memmove",
+
+            Intrinsic::LifetimeStart => "llvm.lifetime.start",
+            Intrinsic::LifetimeEnd => "llvm.lifetime.end",
         }
     }
 
@@ -91,13 +117,18 @@ impl Intrinsic {
             Self::StorageStore => vec![],
             Self::SetStorage => vec![],
             Self::Event => vec![],
+            Self::Keccak256 => vec![],
 
             Self::SwitchContext => vec![],
             Self::GetFromContext => vec![],
+            Self::SetToContext => vec![],
             Self::FarCall => vec![],
             Self::CallCode => vec![],
             Self::DelegateCall => vec![],
             Self::StaticCall => vec![],
+            Self::MimicCall => vec![],
+
+            Self::Assume => vec![],
 
             Self::MemoryCopy => vec![
                 context
@@ -165,6 +196,26 @@ impl Intrinsic {
                     .as_basic_type_enum(),
                 context.field_type().as_basic_type_enum(),
             ],
+            Self::MemoryMove => vec![
+                context
+                    .field_type()
+                    .ptr_type(AddressSpace::Heap.into())
+                    .as_basic_type_enum(),
+                context
+                    .field_type()
+                    .ptr_type(AddressSpace::Heap.into())
+                    .as_basic_type_enum(),
+                context.field_type().as_basic_type_enum(),
+            ],
+
+            Self::LifetimeStart => vec![context
+                .integer_type(compiler_common::BITLENGTH_BYTE)
+                .ptr_type(AddressSpace::Stack.into())
+                .as_basic_type_enum()],
+            Self::LifetimeEnd => vec![context
+                .integer_type(compiler_common::BITLENGTH_BYTE)
+                .ptr_type(AddressSpace::Stack.into())
+                .as_basic_type_enum()],
         }
     }
 }
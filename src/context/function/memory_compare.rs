@@ -0,0 +1,82 @@
+//!
+//! The LLVM memory comparison runtime function.
+//!
+
+use crate::context::address_space::AddressSpace;
+use crate::context::function::stdlib::StdlibFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM memory comparison runtime function.
+///
+/// Compares the memory ranges in the `lhs` and `rhs` address spaces, e.g. the return data with
+/// the expected magic value. The function takes the two offsets and the length, and returns 1 if
+/// the ranges are equal and 0 otherwise. One function is emitted per pair of address spaces on
+/// the first request.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryCompare {
+    /// The left-hand side address space.
+    pub lhs: AddressSpace,
+    /// The right-hand side address space.
+    pub rhs: AddressSpace,
+}
+
+impl MemoryCompare {
+    /// The runtime function name prefix.
+    pub const NAME_PREFIX: &'static str = "__memcmp";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(lhs: AddressSpace, rhs: AddressSpace) -> Self {
+        Self { lhs, rhs }
+    }
+
+    ///
+    /// Returns the runtime function name.
+    ///
+    pub fn name(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            Self::NAME_PREFIX,
+            Self::address_space_name(self.lhs),
+            Self::address_space_name(self.rhs)
+        )
+    }
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(&self, context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let name = self.name();
+
+        context.build_runtime_function(name.as_str(), 3, 1, |context, parameters| {
+            let result = StdlibFunction::build_memory_compare(
+                context,
+                (self.lhs, parameters[0]),
+                (self.rhs, parameters[1]),
+                parameters[2],
+            )?;
+            Ok(Some(result))
+        })
+    }
+
+    ///
+    /// Returns the `address_space` name used in the function names.
+    ///
+    fn address_space_name(address_space: AddressSpace) -> &'static str {
+        match address_space {
+            AddressSpace::Stack => "stack",
+            AddressSpace::Heap => "heap",
+            AddressSpace::Parent => "parent",
+            AddressSpace::Child => "child",
+        }
+    }
+}
@@ -3,18 +3,31 @@
 //!
 
 pub mod block;
+pub mod bytes_copy;
 pub mod constructor;
 pub mod entry;
+pub mod error_encoder;
 pub mod evm_data;
+pub mod external_interface;
+pub mod guard;
 pub mod intrinsic;
+pub mod memory_compare;
+pub mod nonreentrant_guard;
+pub mod paymaster_flow;
 pub mod r#return;
+pub mod revert_on_failure;
 pub mod runtime;
 pub mod selector;
+pub mod statistics;
+pub mod stdlib;
 
 use std::collections::HashMap;
 
+use self::block::key::Key as BlockKey;
+use self::block::Block;
 use self::evm_data::EVMData;
 use self::r#return::Return;
+use self::statistics::Statistics;
 
 ///
 /// The LLVM generator function.
@@ -28,9 +41,9 @@ pub struct Function<'ctx> {
 
     /// The entry block.
     pub entry_block: inkwell::basic_block::BasicBlock<'ctx>,
-    /// The throw/revert block.
+    /// The throw/revert block. Removed while finalizing the function if unreferenced.
     pub throw_block: inkwell::basic_block::BasicBlock<'ctx>,
-    /// The catch block.
+    /// The catch block. Removed while finalizing the function if unreferenced.
     pub catch_block: inkwell::basic_block::BasicBlock<'ctx>,
     /// The return/leave block.
     pub return_block: inkwell::basic_block::BasicBlock<'ctx>,
@@ -46,6 +59,9 @@ pub struct Function<'ctx> {
 
     /// The EVM compiler data.
     pub evm_data: Option<EVMData<'ctx>>,
+
+    /// Whether the function never unwinds, so it is called without the unwinding edge.
+    pub is_nothrow: bool,
 }
 
 impl<'ctx> Function<'ctx> {
@@ -80,6 +96,8 @@ impl<'ctx> Function<'ctx> {
             label_arguments: HashMap::new(),
 
             evm_data: None,
+
+            is_nothrow: false,
         }
     }
 
@@ -113,6 +131,22 @@ impl<'ctx> Function<'ctx> {
         object
     }
 
+    ///
+    /// Sets the EVM data, consuming the function.
+    ///
+    pub fn with_evm_data(mut self, evm_data: EVMData<'ctx>) -> Self {
+        self.evm_data = Some(evm_data);
+        self
+    }
+
+    ///
+    /// Sets the function return data, consuming the function.
+    ///
+    pub fn with_return(mut self, r#return: Return<'ctx>) -> Self {
+        self.r#return = Some(r#return);
+        self
+    }
+
     ///
     /// Sets the function return data.
     ///
@@ -156,4 +190,66 @@ impl<'ctx> Function<'ctx> {
             .as_mut()
             .expect("The EVM data must have been initialized")
     }
+
+    ///
+    /// Returns the EVM data reference, if it has been initialized.
+    ///
+    pub fn try_evm(&self) -> anyhow::Result<&EVMData<'ctx>> {
+        self.evm_data
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The function `{}` has no EVM data", self.name))
+    }
+
+    ///
+    /// Returns the EVM data mutable reference, if it has been initialized.
+    ///
+    pub fn try_evm_mut(&mut self) -> anyhow::Result<&mut EVMData<'ctx>> {
+        let name = self.name.as_str();
+        self.evm_data
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("The function `{}` has no EVM data", name))
+    }
+
+    ///
+    /// Returns the EVM stack height of the function.
+    ///
+    pub fn stack_height(&self) -> anyhow::Result<usize> {
+        self.try_evm().map(|evm_data| evm_data.stack_size)
+    }
+
+    ///
+    /// Returns the EVM block with the specified tag and initial stack pattern.
+    ///
+    pub fn block(&self, key: &BlockKey, stack_hash: &md5::Digest) -> anyhow::Result<Block<'ctx>> {
+        self.try_evm()?
+            .find_block(key, stack_hash)
+            .map_err(|error| anyhow::anyhow!("Function `{}`: {}", self.name, error))
+    }
+
+    ///
+    /// Sets the pointer to the stack slot of the variable `name`.
+    ///
+    pub fn set_stack_slot(&mut self, name: String, pointer: inkwell::values::PointerValue<'ctx>) {
+        self.stack.insert(name, pointer);
+    }
+
+    ///
+    /// Returns the pointer to the stack slot of the variable `name`.
+    ///
+    pub fn stack_slot(&self, name: &str) -> anyhow::Result<inkwell::values::PointerValue<'ctx>> {
+        self.stack.get(name).copied().ok_or_else(|| {
+            anyhow::anyhow!(
+                "The variable `{}` is not declared in the function `{}`",
+                name,
+                self.name
+            )
+        })
+    }
+
+    ///
+    /// Computes the function statistics from its current IR.
+    ///
+    pub fn statistics(&self) -> Statistics {
+        Statistics::new(self.value)
+    }
 }
@@ -0,0 +1,94 @@
+//!
+//! The LLVM nonreentrant guard runtime functions.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM nonreentrant guard runtime functions.
+///
+/// The `enter` function throws if the mutex storage slot passed as the argument is set, and sets
+/// it otherwise. The `exit` function clears the slot. The functions are emitted once per module
+/// on the first request.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonreentrantGuard {}
+
+impl NonreentrantGuard {
+    /// The entering runtime function name.
+    pub const NAME_ENTER: &'static str = "__nonreentrant_enter";
+
+    /// The exiting runtime function name.
+    pub const NAME_EXIT: &'static str = "__nonreentrant_exit";
+
+    /// The error thrown on the reentrant call.
+    pub const ERROR_REENTRANT_CALL: &'static str = "Reentrant call";
+
+    ///
+    /// Declares and defines both runtime functions in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        Self::build_function(context, Self::NAME_ENTER, true)?;
+        Self::build_function(context, Self::NAME_EXIT, false)
+    }
+
+    ///
+    /// Declares and defines the runtime function `name`.
+    ///
+    fn build_function<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+        name: &str,
+        is_enter: bool,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        context.build_runtime_function(name, 1, 0, |context, parameters| {
+            let slot = parameters[0];
+
+            if is_enter {
+                let intrinsic =
+                    context.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?;
+                let mutex = context
+                    .build_call(
+                        intrinsic,
+                        &[
+                            slot.as_basic_value_enum(),
+                            context.field_const(0).as_basic_value_enum(),
+                        ],
+                        "nonreentrant_mutex_load",
+                    )
+                    .expect("Contract storage always returns a value")
+                    .into_int_value();
+                let is_locked = context.builder().build_int_compare(
+                    inkwell::IntPredicate::NE,
+                    mutex,
+                    context.field_const(0),
+                    context.value_name("nonreentrant_is_locked").as_str(),
+                );
+                context.build_if(is_locked, "nonreentrant_locked", |context| {
+                    context.write_error(Self::ERROR_REENTRANT_CALL);
+                    context.build_unconditional_branch(context.try_function()?.throw_block);
+                    Ok(())
+                })?;
+            }
+
+            context.build_storage_store(
+                context.field_const(if is_enter { 1 } else { 0 }),
+                slot,
+                context.field_const(0),
+                "nonreentrant_mutex_store",
+            )?;
+            Ok(None)
+        })
+    }
+}
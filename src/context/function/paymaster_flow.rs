@@ -0,0 +1,115 @@
+//!
+//! The LLVM paymaster flow runtime functions.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::call_definition::CallDefinition;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM paymaster flow runtime functions.
+///
+/// The `approve` function calls the `approve` method of the token contract passed as the first
+/// argument, allowing the spender to take the amount passed as the second and third arguments.
+/// It throws if the token contract rejects the approval. The function is emitted once per module
+/// on the first request.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaymasterFlow {}
+
+impl PaymasterFlow {
+    /// The approving runtime function name.
+    pub const NAME_APPROVE: &'static str = "__paymaster_approve";
+
+    /// The general flow signature.
+    pub const SIGNATURE_GENERAL: &'static str = "general(bytes)";
+
+    /// The approval-based flow signature.
+    pub const SIGNATURE_APPROVAL_BASED: &'static str = "approvalBased(address,uint256,bytes)";
+
+    /// The token approval method signature.
+    pub const SIGNATURE_APPROVE: &'static str = "approve(address,uint256)";
+
+    /// The paymaster validation method signature, whose selector is the validation magic value.
+    pub const SIGNATURE_VALIDATE_AND_PAY: &'static str = "validateAndPayForPaymasterTransaction(bytes32,bytes32,(uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256[4],bytes,bytes,bytes32[],bytes,bytes))";
+
+    /// The error thrown if the token contract rejects the approval.
+    pub const ERROR_APPROVAL_FAILED: &'static str = "Paymaster token approval failed";
+
+    /// The error thrown if the paymaster input selects an unsupported flow.
+    pub const ERROR_UNSUPPORTED_FLOW: &'static str = "Unsupported paymaster flow";
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        context.build_runtime_function(Self::NAME_APPROVE, 3, 0, |context, parameters| {
+            let token = context.build_address_mask(parameters[0]);
+            let spender = context.build_address_mask(parameters[1]);
+            let amount = parameters[2];
+
+            context.with_child_context("paymaster_approve", |context| {
+                let input_size = compiler_common::SIZE_X32 + 2 * compiler_common::SIZE_FIELD;
+                context
+                    .write_header_size(context.field_const(input_size as u64), AddressSpace::Child);
+
+                let data_offset =
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64;
+                let selector = Context::<D>::selector(Self::SIGNATURE_APPROVE) as u64;
+                let selector = context.build_left_align_bytes(
+                    context.field_const(selector),
+                    compiler_common::SIZE_X32,
+                );
+                let words = [selector, spender, amount];
+                let offsets = [
+                    0,
+                    compiler_common::SIZE_X32,
+                    compiler_common::SIZE_X32 + compiler_common::SIZE_FIELD,
+                ];
+                for (word, offset) in words.into_iter().zip(offsets.into_iter()) {
+                    let pointer = context.access_memory(
+                        context.field_const(data_offset + offset as u64),
+                        AddressSpace::Child,
+                        "paymaster_approve_input_pointer",
+                    );
+                    context.build_store(pointer, word);
+                }
+
+                let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::FarCall)?;
+                let call_definition = CallDefinition::new(token).encode(context);
+                let is_call_successful = context
+                    .build_call(
+                        intrinsic,
+                        &[call_definition.as_basic_value_enum()],
+                        "paymaster_approve_call_external",
+                    )
+                    .expect("IntrinsicFunction always returns a flag")
+                    .into_int_value();
+                let is_call_failed = context.builder().build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    is_call_successful,
+                    context.field_const(0),
+                    context
+                        .value_name("paymaster_approve_is_call_failed")
+                        .as_str(),
+                );
+                context.build_if(is_call_failed, "paymaster_approve_failed", |context| {
+                    context.write_error(Self::ERROR_APPROVAL_FAILED);
+                    context.build_unconditional_branch(context.try_function()?.throw_block);
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+            Ok(None)
+        })
+    }
+}
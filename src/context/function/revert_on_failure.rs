@@ -0,0 +1,70 @@
+//!
+//! The LLVM revert-on-failure runtime function.
+//!
+
+use crate::context::address_space::AddressSpace;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The LLVM revert-on-failure runtime function.
+///
+/// Takes the status flag of a contract call. If the flag is zero, copies the child return data
+/// to the parent memory and throws, so the caller reverts with the child revert data. Otherwise,
+/// returns without side effects. The function is emitted once per module on the first request,
+/// so the call sites only pay for a single call instead of the explicit branch.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RevertOnFailure {}
+
+impl RevertOnFailure {
+    /// The runtime function name.
+    pub const NAME: &'static str = "__revert_on_failure";
+
+    ///
+    /// Declares and defines the runtime function in the current module.
+    ///
+    /// Changes the current function and basic block, so they must be restored by the caller.
+    ///
+    pub fn build<'ctx, D>(context: &mut Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        context.build_runtime_function(Self::NAME, 1, 0, |context, parameters| {
+            let is_failure = context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                parameters[0],
+                context.field_const(0),
+                context.value_name("revert_on_failure_is_failure").as_str(),
+            );
+            context.build_if(is_failure, "revert_on_failure", |context| {
+                let data_offset = context.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+                );
+                let size = context.read_return_data_size();
+                context.write_header_size(size, AddressSpace::Parent);
+                let destination = context.access_memory(
+                    data_offset,
+                    AddressSpace::Parent,
+                    "revert_on_failure_destination",
+                );
+                let source = context.access_memory(
+                    data_offset,
+                    AddressSpace::Child,
+                    "revert_on_failure_source",
+                );
+                context.build_memcpy(
+                    IntrinsicFunction::MemoryCopyFromChildToParent,
+                    destination,
+                    source,
+                    size,
+                    "revert_on_failure_memcpy_from_child_to_parent",
+                );
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            })?;
+            Ok(None)
+        })
+    }
+}
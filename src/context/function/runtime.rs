@@ -25,17 +25,21 @@ impl<'ctx> Runtime<'ctx> {
     ///
     /// A shortcut constructor.
     ///
+    /// The functions already declared in the `module`, e.g. restored from the cache, are reused.
+    ///
     pub fn new(
         llvm: &'ctx inkwell::context::Context,
         module: &inkwell::module::Module<'ctx>,
     ) -> Self {
-        let personality = module.add_function(
+        let personality = Self::declare(
+            module,
             compiler_common::LLVM_FUNCTION_PERSONALITY,
             llvm.i32_type().fn_type(&[], false),
             None,
         );
 
-        let cxa_throw = module.add_function(
+        let cxa_throw = Self::declare(
+            module,
             compiler_common::LLVM_FUNCTION_CXA_THROW,
             llvm.void_type().fn_type(
                 vec![
@@ -53,8 +57,16 @@ impl<'ctx> Runtime<'ctx> {
             inkwell::attributes::AttributeLoc::Function,
             llvm.create_enum_attribute(inkwell::LLVMAttributeKindCode::NoProfile, 0),
         );
+        cxa_throw.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            llvm.create_enum_attribute(
+                inkwell::attributes::Attribute::get_named_enum_kind_id("cold"),
+                0,
+            ),
+        );
 
-        let addmod = module.add_function(
+        let addmod = Self::declare(
+            module,
             compiler_common::LLVM_FUNCTION_ADDMOD,
             llvm.custom_width_int_type(compiler_common::BITLENGTH_FIELD as u32)
                 .fn_type(
@@ -68,7 +80,8 @@ impl<'ctx> Runtime<'ctx> {
                 ),
             Some(inkwell::module::Linkage::External),
         );
-        let mulmod = module.add_function(
+        let mulmod = Self::declare(
+            module,
             compiler_common::LLVM_FUNCTION_MULMOD,
             llvm.custom_width_int_type(compiler_common::BITLENGTH_FIELD as u32)
                 .fn_type(
@@ -90,4 +103,18 @@ impl<'ctx> Runtime<'ctx> {
             mulmod,
         }
     }
+
+    ///
+    /// Returns the function `name` declared in the `module`, declaring it if it does not exist.
+    ///
+    fn declare(
+        module: &inkwell::module::Module<'ctx>,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+        linkage: Option<inkwell::module::Linkage>,
+    ) -> inkwell::values::FunctionValue<'ctx> {
+        module
+            .get_function(name)
+            .unwrap_or_else(|| module.add_function(name, r#type, linkage))
+    }
 }
@@ -0,0 +1,221 @@
+//!
+//! The LLVM selector function.
+//!
+
+pub mod policy;
+
+use std::marker::PhantomData;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::code_type::CodeType;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::long_return_policy::LongReturnPolicy;
+use crate::context::Context;
+use crate::Dependency;
+use crate::WriteLLVM;
+
+use self::policy::Policy;
+
+///
+/// The LLVM selector function.
+///
+/// If the `receive` or `fallback` bodies are set, the standard routing is generated around the
+/// inner dispatcher: the empty calldata goes to `receive`, and the calldata shorter than the
+/// selector or with an unknown selector goes to `fallback`. The missing `receive` defers to
+/// `fallback`, and the missing `fallback` is replaced with the unknown selector `policy`, which
+/// reverts with empty data by default.
+///
+#[derive(Debug, Default)]
+pub struct Selector<B, D>
+where
+    B: WriteLLVM<D>,
+    D: Dependency,
+{
+    /// The selector AST representation.
+    inner: B,
+    /// The `receive` function body.
+    receive: Option<B>,
+    /// The `fallback` function body.
+    fallback: Option<B>,
+    /// The unknown selector policy.
+    policy: Option<Policy>,
+    /// The `D` phantom data.
+    _pd: PhantomData<D>,
+}
+
+impl<B, D> Selector<B, D>
+where
+    B: WriteLLVM<D>,
+    D: Dependency,
+{
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            receive: None,
+            fallback: None,
+            policy: None,
+            _pd: PhantomData::default(),
+        }
+    }
+
+    ///
+    /// Sets the `receive` function body, called with the empty calldata.
+    ///
+    pub fn with_receive(mut self, receive: B) -> Self {
+        self.receive = Some(receive);
+        self
+    }
+
+    ///
+    /// Sets the `fallback` function body, called with the short calldata or unknown selector.
+    ///
+    pub fn with_fallback(mut self, fallback: B) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    ///
+    /// Sets the unknown selector `policy`, applied if there is no `fallback` function body.
+    ///
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    ///
+    /// Translates the inner dispatcher with the `receive` and `fallback` routing.
+    ///
+    fn build_routing(self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let calldata_size = context.read_header(AddressSpace::Parent).size(context);
+
+        let fallback_block = context.append_basic_block("selector_fallback_block");
+        let dispatch_block = context.append_basic_block("selector_dispatch_block");
+        let short_calldata_block = context.append_basic_block("selector_short_calldata_block");
+
+        let is_calldata_short = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            calldata_size,
+            context.field_const(compiler_common::SIZE_X32 as u64),
+            context.value_name("selector_is_calldata_short").as_str(),
+        );
+        context.build_conditional_branch(is_calldata_short, short_calldata_block, dispatch_block);
+
+        context.set_basic_block(short_calldata_block);
+        match self.receive {
+            Some(receive) => {
+                let receive_block = context.append_basic_block("selector_receive_block");
+                let is_calldata_empty = context.builder().build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    calldata_size,
+                    context.field_const(0),
+                    context.value_name("selector_is_calldata_empty").as_str(),
+                );
+                context.build_conditional_branch(is_calldata_empty, receive_block, fallback_block);
+
+                context.set_basic_block(receive_block);
+                receive.into_llvm(context)?;
+                context.build_unconditional_branch(context.function().return_block);
+            }
+            None => context.build_unconditional_branch(fallback_block),
+        }
+
+        context.set_basic_block(dispatch_block);
+        self.inner.into_llvm(context)?;
+        context.build_unconditional_branch(fallback_block);
+
+        context.set_basic_block(fallback_block);
+        match self.fallback {
+            Some(fallback) => {
+                fallback.into_llvm(context)?;
+                context.build_unconditional_branch(context.function().return_block);
+            }
+            None => match self.policy.unwrap_or_default() {
+                Policy::Revert => {
+                    context.write_header_size(context.field_const(0), AddressSpace::Parent);
+                    context.build_unconditional_branch(context.function().throw_block);
+                }
+                Policy::ReturnEmpty => {
+                    context.write_header_size(context.field_const(0), AddressSpace::Parent);
+                    context.build_unconditional_branch(context.function().return_block);
+                }
+                Policy::DelegateTo(address) => {
+                    let address = context.field_const_hex(address.as_str())?;
+                    let input_offset = context.field_const(0);
+                    crate::evm::calldata::copy(
+                        context,
+                        [input_offset, input_offset, calldata_size].into(),
+                    )?;
+                    crate::evm::contract::call_and_forward(
+                        context,
+                        IntrinsicFunction::DelegateCall,
+                        address,
+                        None,
+                        input_offset,
+                        calldata_size,
+                    )?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl<B, D> WriteLLVM<D> for Selector<B, D>
+where
+    B: WriteLLVM<D>,
+    D: Dependency,
+{
+    fn declare(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let function_type = context.function_type(0, vec![]);
+        context.add_function(
+            compiler_common::LLVM_FUNCTION_SELECTOR,
+            function_type,
+            Some(inkwell::module::Linkage::Private),
+        )?;
+
+        if let Some(ref mut receive) = self.receive {
+            receive.declare(context)?;
+        }
+        if let Some(ref mut fallback) = self.fallback {
+            fallback.declare(context)?;
+        }
+        self.inner.declare(context)
+    }
+
+    fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let function = context
+            .get_function(compiler_common::LLVM_FUNCTION_SELECTOR)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Contract selector not found"))?;
+        context.set_function(function);
+
+        context.set_basic_block(context.function().entry_block);
+        context.code_type = Some(CodeType::Runtime);
+        if self.receive.is_some() || self.fallback.is_some() || self.policy.is_some() {
+            self.build_routing(context)?;
+        } else {
+            self.inner.into_llvm(context)?;
+            match context
+                .basic_block()
+                .get_last_instruction()
+                .map(|instruction| instruction.get_opcode())
+            {
+                Some(inkwell::values::InstructionOpcode::Br) => {}
+                Some(inkwell::values::InstructionOpcode::Switch) => {}
+                _ => context.build_unconditional_branch(context.function().return_block),
+            }
+        }
+
+        context.build_throw_block(LongReturnPolicy::Return);
+        context.build_catch_block(LongReturnPolicy::Return);
+
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//!
+//! The unknown selector policy.
+//!
+
+///
+/// The unknown selector policy.
+///
+/// Is applied if the calldata selector is not matched by the dispatcher and there is no
+/// `fallback` function body.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// Reverts with empty data.
+    Revert,
+    /// Returns empty data.
+    ReturnEmpty,
+    /// Delegates the call to the hexadecimal address, forwarding the calldata and return data.
+    DelegateTo(String),
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::Revert
+    }
+}
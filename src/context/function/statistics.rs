@@ -0,0 +1,84 @@
+//!
+//! The LLVM function statistics.
+//!
+
+use serde::Serialize;
+
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::gas_report::GasReport;
+
+///
+/// The LLVM function statistics.
+///
+/// Computed on demand from the current function IR, so the numbers reflect the optimizations
+/// performed so far.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Statistics {
+    /// The number of basic blocks.
+    pub blocks: usize,
+    /// The number of LLVM IR instructions.
+    pub instructions: usize,
+    /// The number of stack allocations.
+    pub allocas: usize,
+    /// The number of calls and invokes, including the intrinsic ones.
+    pub calls: usize,
+    /// The number of external contract calls.
+    pub far_calls: usize,
+}
+
+impl Statistics {
+    /// The external contract call intrinsics.
+    const FAR_CALLS: [IntrinsicFunction; 5] = [
+        IntrinsicFunction::FarCall,
+        IntrinsicFunction::CallCode,
+        IntrinsicFunction::DelegateCall,
+        IntrinsicFunction::StaticCall,
+        IntrinsicFunction::MimicCall,
+    ];
+
+    ///
+    /// Walks the `function` and counts its entities.
+    ///
+    pub fn new(function: inkwell::values::FunctionValue) -> Self {
+        let mut statistics = Self::default();
+
+        for block in function.get_basic_blocks() {
+            statistics.blocks += 1;
+
+            let mut instruction = block.get_first_instruction();
+            while let Some(current) = instruction {
+                instruction = current.get_next_instruction();
+                statistics.instructions += 1;
+
+                match current.get_opcode() {
+                    inkwell::values::InstructionOpcode::Alloca => statistics.allocas += 1,
+                    inkwell::values::InstructionOpcode::Call
+                    | inkwell::values::InstructionOpcode::Invoke => {
+                        statistics.calls += 1;
+                        let callee_name = GasReport::callee_name(current).unwrap_or_default();
+                        if Self::FAR_CALLS
+                            .iter()
+                            .any(|intrinsic| intrinsic.name() == callee_name.as_str())
+                        {
+                            statistics.far_calls += 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        statistics
+    }
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "blocks: {}, instructions: {}, allocas: {}, calls: {}, far calls: {}",
+            self.blocks, self.instructions, self.allocas, self.calls, self.far_calls
+        )
+    }
+}
@@ -0,0 +1,466 @@
+//!
+//! The LLVM standard library functions.
+//!
+
+use inkwell::types::BasicType;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::long_return_policy::LongReturnPolicy;
+use crate::context::Context;
+use crate::Dependency;
+use crate::WriteLLVM;
+
+///
+/// The LLVM standard library functions.
+///
+/// The utility functions commonly generated by the frontends, e.g. the Yul utility functions.
+/// Each function takes and returns field values, and is emitted once per module when a frontend
+/// requests the builtin.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StdlibFunction {
+    /// The `base` raised to the `exponent` power, wrapping on overflow.
+    Power,
+    /// The floor of the binary logarithm of the `value`, or zero if the `value` is zero.
+    Log2,
+    /// The unsigned minimum of two values.
+    Min,
+    /// The unsigned maximum of two values.
+    Max,
+    /// The `value` clamped to the unsigned range from `low` to `high`.
+    Clamp,
+    /// Whether the `length` bytes of the heap at two offsets are equal.
+    MemoryCompare,
+}
+
+impl StdlibFunction {
+    ///
+    /// Returns the function name.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Power => "__stdlib_power",
+            Self::Log2 => "__stdlib_log2",
+            Self::Min => "__stdlib_min",
+            Self::Max => "__stdlib_max",
+            Self::Clamp => "__stdlib_clamp",
+            Self::MemoryCompare => "__stdlib_memory_compare",
+        }
+    }
+
+    ///
+    /// Returns the number of the field arguments.
+    ///
+    pub fn input_size(&self) -> usize {
+        match self {
+            Self::Power => 2,
+            Self::Log2 => 1,
+            Self::Min => 2,
+            Self::Max => 2,
+            Self::Clamp => 3,
+            Self::MemoryCompare => 3,
+        }
+    }
+
+    ///
+    /// Builds the function body, returning the result.
+    ///
+    fn build_body<'ctx, D>(
+        &self,
+        context: &mut Context<'ctx, D>,
+        arguments: &[inkwell::values::IntValue<'ctx>],
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        D: Dependency,
+    {
+        let result = match self {
+            Self::Power => {
+                crate::evm::math::exponent(context, [arguments[0], arguments[1]].into())?
+                    .expect("Always exists")
+                    .into_int_value()
+            }
+            Self::Log2 => Self::build_log2(context, arguments[0]),
+            Self::Min => Self::build_select(
+                context,
+                inkwell::IntPredicate::ULT,
+                arguments[0],
+                arguments[1],
+                "stdlib_min",
+            ),
+            Self::Max => Self::build_select(
+                context,
+                inkwell::IntPredicate::UGT,
+                arguments[0],
+                arguments[1],
+                "stdlib_max",
+            ),
+            Self::Clamp => {
+                let value = Self::build_select(
+                    context,
+                    inkwell::IntPredicate::UGT,
+                    arguments[0],
+                    arguments[1],
+                    "stdlib_clamp_low",
+                );
+                Self::build_select(
+                    context,
+                    inkwell::IntPredicate::ULT,
+                    value,
+                    arguments[2],
+                    "stdlib_clamp_high",
+                )
+            }
+            Self::MemoryCompare => Self::build_memory_compare(
+                context,
+                (AddressSpace::Heap, arguments[0]),
+                (AddressSpace::Heap, arguments[1]),
+                arguments[2],
+            )?,
+        };
+        Ok(result)
+    }
+
+    ///
+    /// Selects `operand_1` if it compares to `operand_2` according to the `predicate`, and
+    /// `operand_2` otherwise.
+    ///
+    fn build_select<'ctx, D>(
+        context: &Context<'ctx, D>,
+        predicate: inkwell::IntPredicate,
+        operand_1: inkwell::values::IntValue<'ctx>,
+        operand_2: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let condition = context.builder().build_int_compare(
+            predicate,
+            operand_1,
+            operand_2,
+            context
+                .value_name(format!("{}_condition", name).as_str())
+                .as_str(),
+        );
+        context
+            .builder()
+            .build_select(
+                condition,
+                operand_1,
+                operand_2,
+                context.value_name(name).as_str(),
+            )
+            .into_int_value()
+    }
+
+    ///
+    /// Builds the binary logarithm as a branchless binary search over the bit positions.
+    ///
+    fn build_log2<'ctx, D>(
+        context: &Context<'ctx, D>,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let mut value = value;
+        let mut result = context.field_const(0);
+
+        let mut shift = compiler_common::BITLENGTH_FIELD / 2;
+        while shift > 0 {
+            let shift_value = context.field_const(shift as u64);
+            let shifted = context.builder().build_right_shift(
+                value,
+                shift_value,
+                false,
+                context
+                    .value_name(format!("stdlib_log2_shifted_{}", shift).as_str())
+                    .as_str(),
+            );
+            let is_shifted_non_zero = context.builder().build_int_compare(
+                inkwell::IntPredicate::NE,
+                shifted,
+                context.field_const(0),
+                context
+                    .value_name(format!("stdlib_log2_is_shifted_non_zero_{}", shift).as_str())
+                    .as_str(),
+            );
+            value = context
+                .builder()
+                .build_select(
+                    is_shifted_non_zero,
+                    shifted,
+                    value,
+                    context
+                        .value_name(format!("stdlib_log2_value_{}", shift).as_str())
+                        .as_str(),
+                )
+                .into_int_value();
+            let incremented = context.builder().build_int_add(
+                result,
+                shift_value,
+                context
+                    .value_name(format!("stdlib_log2_incremented_{}", shift).as_str())
+                    .as_str(),
+            );
+            result = context
+                .builder()
+                .build_select(
+                    is_shifted_non_zero,
+                    incremented,
+                    result,
+                    context
+                        .value_name(format!("stdlib_log2_result_{}", shift).as_str())
+                        .as_str(),
+                )
+                .into_int_value();
+            shift /= 2;
+        }
+
+        result
+    }
+
+    ///
+    /// Builds the comparison of `length` bytes at the `lhs` and `rhs` offsets in their address
+    /// spaces, returning 1 if they are equal and 0 otherwise.
+    ///
+    /// The full words are compared in a loop, which exits at the first difference, and the
+    /// remaining bytes are compared as a masked word.
+    ///
+    pub(crate) fn build_memory_compare<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+        lhs: (AddressSpace, inkwell::values::IntValue<'ctx>),
+        rhs: (AddressSpace, inkwell::values::IntValue<'ctx>),
+        length: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        D: Dependency,
+    {
+        let result_pointer = context.build_alloca(context.field_type(), "memory_compare_result");
+        context.build_store(result_pointer, context.field_const(1));
+        let index_pointer = context.build_alloca(context.field_type(), "memory_compare_index");
+        context.build_store(index_pointer, context.field_const(0));
+
+        let words_length = context.builder().build_and(
+            length,
+            context
+                .field_const((compiler_common::SIZE_FIELD - 1) as u64)
+                .const_not(),
+            context.value_name("memory_compare_words_length").as_str(),
+        );
+
+        let condition_block = context.append_basic_block("memory_compare_condition");
+        let body_block = context.append_basic_block("memory_compare_body");
+        let increment_block = context.append_basic_block("memory_compare_increment");
+        let tail_block = context.append_basic_block("memory_compare_tail");
+        let join_block = context.append_basic_block("memory_compare_join");
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(condition_block);
+        let index = context
+            .build_load(index_pointer, "memory_compare_index_value")
+            .into_int_value();
+        let is_word_left = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            index,
+            words_length,
+            context.value_name("memory_compare_is_word_left").as_str(),
+        );
+        context.build_conditional_branch(is_word_left, body_block, tail_block);
+
+        context.set_basic_block(body_block);
+        let (lhs_word, rhs_word) = Self::load_words(context, lhs, rhs, index, "memory_compare");
+        let is_word_equal = context.builder().build_int_compare(
+            inkwell::IntPredicate::EQ,
+            lhs_word,
+            rhs_word,
+            context.value_name("memory_compare_is_word_equal").as_str(),
+        );
+        let mismatch_block = context.append_basic_block("memory_compare_mismatch");
+        context.build_conditional_branch(is_word_equal, increment_block, mismatch_block);
+
+        context.set_basic_block(mismatch_block);
+        context.build_store(result_pointer, context.field_const(0));
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(increment_block);
+        let index_incremented = context.builder().build_int_add(
+            index,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context
+                .value_name("memory_compare_index_incremented")
+                .as_str(),
+        );
+        context.build_store(index_pointer, index_incremented);
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(tail_block);
+        let tail_length = context.builder().build_int_sub(
+            length,
+            words_length,
+            context.value_name("memory_compare_tail_length").as_str(),
+        );
+        let is_tail_non_empty = context.builder().build_int_compare(
+            inkwell::IntPredicate::NE,
+            tail_length,
+            context.field_const(0),
+            context
+                .value_name("memory_compare_is_tail_non_empty")
+                .as_str(),
+        );
+        context.build_if(is_tail_non_empty, "memory_compare_tail", |context| {
+            let (lhs_word, rhs_word) =
+                Self::load_words(context, lhs, rhs, words_length, "memory_compare_tail");
+            let difference = context.builder().build_xor(
+                lhs_word,
+                rhs_word,
+                context
+                    .value_name("memory_compare_tail_difference")
+                    .as_str(),
+            );
+            let tail_bits = context.builder().build_int_mul(
+                tail_length,
+                context.field_const(compiler_common::BITLENGTH_BYTE as u64),
+                context.value_name("memory_compare_tail_bits").as_str(),
+            );
+            let mask_shift = context.builder().build_int_sub(
+                context.field_const(compiler_common::BITLENGTH_FIELD as u64),
+                tail_bits,
+                context
+                    .value_name("memory_compare_tail_mask_shift")
+                    .as_str(),
+            );
+            let mask = context.builder().build_left_shift(
+                context.field_type().const_all_ones(),
+                mask_shift,
+                context.value_name("memory_compare_tail_mask").as_str(),
+            );
+            let difference = context.builder().build_and(
+                difference,
+                mask,
+                context
+                    .value_name("memory_compare_tail_difference_masked")
+                    .as_str(),
+            );
+            let is_tail_different = context.builder().build_int_compare(
+                inkwell::IntPredicate::NE,
+                difference,
+                context.field_const(0),
+                context
+                    .value_name("memory_compare_is_tail_different")
+                    .as_str(),
+            );
+            let result = context.builder().build_select(
+                is_tail_different,
+                context.field_const(0),
+                context.field_const(1),
+                context.value_name("memory_compare_tail_result").as_str(),
+            );
+            context.build_store(result_pointer, result);
+            Ok(())
+        })?;
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(join_block);
+        Ok(context
+            .build_load(result_pointer, "memory_compare_result_value")
+            .into_int_value())
+    }
+
+    ///
+    /// Loads the words at `index` bytes past the `lhs` and `rhs` offsets.
+    ///
+    fn load_words<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+        lhs: (AddressSpace, inkwell::values::IntValue<'ctx>),
+        rhs: (AddressSpace, inkwell::values::IntValue<'ctx>),
+        index: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> (
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    )
+    where
+        D: Dependency,
+    {
+        let lhs_word = Self::load_word(context, lhs, index, format!("{}_lhs", name).as_str());
+        let rhs_word = Self::load_word(context, rhs, index, format!("{}_rhs", name).as_str());
+        (lhs_word, rhs_word)
+    }
+
+    ///
+    /// Loads the word at `index` bytes past the `offset` in the `address_space`.
+    ///
+    fn load_word<'ctx, D>(
+        context: &mut Context<'ctx, D>,
+        (address_space, offset): (AddressSpace, inkwell::values::IntValue<'ctx>),
+        index: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let offset = context.builder().build_int_add(
+            offset,
+            index,
+            context
+                .value_name(format!("{}_offset", name).as_str())
+                .as_str(),
+        );
+        let pointer =
+            context.access_memory(offset, address_space, format!("{}_pointer", name).as_str());
+        context
+            .build_load(pointer, format!("{}_word", name).as_str())
+            .into_int_value()
+    }
+}
+
+impl<D> WriteLLVM<D> for StdlibFunction
+where
+    D: Dependency,
+{
+    fn declare(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let function_type = context.function_type(
+            1,
+            vec![context.field_type().as_basic_type_enum(); self.input_size()],
+        );
+        context.add_function(
+            self.name(),
+            function_type,
+            Some(inkwell::module::Linkage::Private),
+        )?;
+        Ok(())
+    }
+
+    fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let function = context.get_function(self.name()).cloned().ok_or_else(|| {
+            anyhow::anyhow!("Standard library function `{}` not found", self.name())
+        })?;
+        context.set_function(function);
+        context.set_basic_block(context.function().entry_block);
+
+        let arguments: Vec<inkwell::values::IntValue> = (0..self.input_size())
+            .map(|index| {
+                context
+                    .function()
+                    .value
+                    .get_nth_param(index as u32)
+                    .expect("Always exists")
+                    .into_int_value()
+            })
+            .collect();
+        let result = self.build_body(context, arguments.as_slice())?;
+        let result_pointer = context.build_alloca(context.field_type(), "stdlib_result_pointer");
+        context.build_store(result_pointer, result);
+        context.build_unconditional_branch(context.function().return_block);
+
+        context.build_throw_block(LongReturnPolicy::Propagate);
+        context.build_catch_block(LongReturnPolicy::Propagate);
+
+        context.set_basic_block(context.function().return_block);
+        let result = context.build_load(result_pointer, "stdlib_result");
+        context.build_return(Some(&result));
+
+        Ok(())
+    }
+}
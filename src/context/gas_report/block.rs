@@ -0,0 +1,25 @@
+//!
+//! The gas estimation report basic block entry.
+//!
+
+use serde::Serialize;
+
+///
+/// The gas estimation report basic block entry.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Block {
+    /// The basic block name.
+    pub name: String,
+    /// The sum of the basic block instruction costs.
+    pub cost: u64,
+}
+
+impl Block {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, cost: u64) -> Self {
+        Self { name, cost }
+    }
+}
@@ -0,0 +1,86 @@
+//!
+//! The gas estimation cost table.
+//!
+
+use std::collections::HashMap;
+
+///
+/// The gas estimation cost table.
+///
+/// Is provided by the target, as the weights depend on the VM implementation.
+///
+#[derive(Debug, Clone)]
+pub struct CostTable {
+    /// The cost of an instruction which is not present in the table.
+    pub default: u64,
+    /// The instruction costs.
+    pub instructions: Vec<(inkwell::values::InstructionOpcode, u64)>,
+    /// The intrinsic and runtime function call costs, which override the call instruction cost.
+    pub intrinsics: HashMap<String, u64>,
+}
+
+impl CostTable {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        default: u64,
+        instructions: Vec<(inkwell::values::InstructionOpcode, u64)>,
+        intrinsics: HashMap<String, u64>,
+    ) -> Self {
+        Self {
+            default,
+            instructions,
+            intrinsics,
+        }
+    }
+
+    ///
+    /// Returns the cost of an instruction with `opcode`.
+    ///
+    pub fn instruction(&self, opcode: inkwell::values::InstructionOpcode) -> u64 {
+        self.instructions
+            .iter()
+            .find(|(instruction, _)| instruction == &opcode)
+            .map(|(_, cost)| *cost)
+            .unwrap_or(self.default)
+    }
+
+    ///
+    /// Returns the cost of a call to the function `name`, if it is present in the table.
+    ///
+    pub fn intrinsic(&self, name: &str) -> Option<u64> {
+        self.intrinsics.get(name).copied()
+    }
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        let instructions = vec![
+            (inkwell::values::InstructionOpcode::Load, 2),
+            (inkwell::values::InstructionOpcode::Store, 2),
+            (inkwell::values::InstructionOpcode::Mul, 2),
+            (inkwell::values::InstructionOpcode::UDiv, 4),
+            (inkwell::values::InstructionOpcode::SDiv, 4),
+            (inkwell::values::InstructionOpcode::URem, 4),
+            (inkwell::values::InstructionOpcode::SRem, 4),
+            (inkwell::values::InstructionOpcode::Call, 4),
+            (inkwell::values::InstructionOpcode::Invoke, 4),
+            (inkwell::values::InstructionOpcode::Alloca, 0),
+            (inkwell::values::InstructionOpcode::IntToPtr, 0),
+            (inkwell::values::InstructionOpcode::Phi, 0),
+        ];
+
+        let mut intrinsics = HashMap::with_capacity(8);
+        intrinsics.insert("llvm.syncvm.sload".to_owned(), 200);
+        intrinsics.insert("llvm.syncvm.sstore".to_owned(), 500);
+        intrinsics.insert("llvm.syncvm.event".to_owned(), 100);
+        intrinsics.insert("llvm.syncvm.farcall.rc".to_owned(), 1000);
+        intrinsics.insert("llvm.syncvm.callcode.rc".to_owned(), 1000);
+        intrinsics.insert("llvm.syncvm.delegatecall.rc".to_owned(), 1000);
+        intrinsics.insert("llvm.syncvm.staticcall.rc".to_owned(), 1000);
+        intrinsics.insert("llvm.syncvm.mimiccall.rc".to_owned(), 1000);
+
+        Self::new(1, instructions, intrinsics)
+    }
+}
@@ -0,0 +1,43 @@
+//!
+//! The gas estimation report function entry.
+//!
+
+use serde::Serialize;
+
+use super::block::Block;
+use super::storage_access::StorageAccess;
+
+///
+/// The gas estimation report function entry.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Function {
+    /// The function name.
+    pub name: String,
+    /// The most expensive acyclic path cost from the entry block.
+    /// Loop back edges are not followed, so each loop body is counted once.
+    pub worst_case: u64,
+    /// The basic block costs in the function layout order.
+    pub blocks: Vec<Block>,
+    /// The storage accesses grouped by slot, sorted by the access count in descending order.
+    pub storage_accesses: Vec<StorageAccess>,
+}
+
+impl Function {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        name: String,
+        worst_case: u64,
+        blocks: Vec<Block>,
+        storage_accesses: Vec<StorageAccess>,
+    ) -> Self {
+        Self {
+            name,
+            worst_case,
+            blocks,
+            storage_accesses,
+        }
+    }
+}
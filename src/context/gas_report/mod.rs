@@ -0,0 +1,278 @@
+//!
+//! The gas estimation report.
+//!
+
+pub mod block;
+pub mod cost_table;
+pub mod function;
+pub mod storage_access;
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::storage_hint::StorageHint;
+
+use self::block::Block;
+use self::cost_table::CostTable;
+use self::function::Function;
+use self::storage_access::StorageAccess;
+
+///
+/// The gas estimation report.
+///
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GasReport {
+    /// The defined function entries in the module order.
+    pub functions: Vec<Function>,
+}
+
+impl GasReport {
+    ///
+    /// Walks the `module` and estimates the costs using the `cost_table` weights.
+    ///
+    /// The declarations without bodies are skipped.
+    ///
+    pub fn new(module: &inkwell::module::Module, cost_table: &CostTable) -> Self {
+        let mut functions = Vec::new();
+
+        let mut function = module.get_first_function();
+        while let Some(value) = function {
+            if value.count_basic_blocks() > 0 {
+                functions.push(Self::analyze_function(value, cost_table));
+            }
+            function = value.get_next_function();
+        }
+
+        Self { functions }
+    }
+
+    ///
+    /// Returns the function entry with the specified `name`.
+    ///
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|function| function.name.as_str() == name)
+    }
+
+    ///
+    /// Estimates the costs of the `function` blocks and its worst-case path.
+    ///
+    fn analyze_function(
+        function: inkwell::values::FunctionValue,
+        cost_table: &CostTable,
+    ) -> Function {
+        let basic_blocks = function.get_basic_blocks();
+        let costs: Vec<u64> = basic_blocks
+            .iter()
+            .map(|block| Self::block_cost(*block, cost_table))
+            .collect();
+
+        let mut worst_cases = vec![None; basic_blocks.len()];
+        let mut is_visiting = vec![false; basic_blocks.len()];
+        let worst_case = Self::worst_case(
+            0,
+            basic_blocks.as_slice(),
+            costs.as_slice(),
+            worst_cases.as_mut_slice(),
+            is_visiting.as_mut_slice(),
+        );
+
+        let blocks = basic_blocks
+            .iter()
+            .zip(costs)
+            .map(|(block, cost)| Block::new(block.get_name().to_string_lossy().to_string(), cost))
+            .collect();
+
+        Function::new(
+            function.get_name().to_string_lossy().to_string(),
+            worst_case,
+            blocks,
+            Self::storage_accesses(function),
+        )
+    }
+
+    ///
+    /// Groups the storage accesses of the `function` by slot.
+    ///
+    /// The slots are identified by the constant value or the LLVM IR value name, so the
+    /// accesses to the unnamed dynamic slots are not reported.
+    ///
+    fn storage_accesses(function: inkwell::values::FunctionValue) -> Vec<StorageAccess> {
+        let mut accesses: BTreeMap<String, StorageAccess> = BTreeMap::new();
+
+        for block in function.get_basic_blocks() {
+            let mut instruction = block.get_first_instruction();
+            while let Some(current) = instruction {
+                instruction = current.get_next_instruction();
+
+                let callee_name = match Self::callee_name(current) {
+                    Some(callee_name) => callee_name,
+                    None => continue,
+                };
+                let (is_store, slot_index) = if callee_name == IntrinsicFunction::StorageLoad.name()
+                {
+                    (false, 0)
+                } else if callee_name == IntrinsicFunction::StorageStore.name() {
+                    (true, 1)
+                } else {
+                    continue;
+                };
+
+                let slot = match current
+                    .get_operand(slot_index)
+                    .and_then(|operand| operand.left())
+                {
+                    Some(inkwell::values::BasicValueEnum::IntValue(slot)) if slot.is_const() => {
+                        slot.print_to_string().to_string()
+                    }
+                    Some(inkwell::values::BasicValueEnum::IntValue(slot)) => {
+                        let name = slot.get_name().to_string_lossy().to_string();
+                        if name.is_empty() {
+                            continue;
+                        }
+                        format!("%{}", name)
+                    }
+                    _ => continue,
+                };
+
+                let access = accesses
+                    .entry(slot.clone())
+                    .or_insert_with(|| StorageAccess::new(slot));
+                if is_store {
+                    access.stores += 1;
+                } else {
+                    access.loads += 1;
+                }
+                match StorageHint::read(function.get_type().get_context(), current) {
+                    Some(StorageHint::Cold) => access.cold += 1,
+                    Some(StorageHint::Warm) => access.warm += 1,
+                    None => {}
+                }
+            }
+        }
+
+        let mut accesses: Vec<StorageAccess> = accesses.into_values().collect();
+        accesses.sort_by(|a, b| b.count().cmp(&a.count()));
+        accesses
+    }
+
+    ///
+    /// Returns the sum of the `block` instruction costs.
+    ///
+    fn block_cost(block: inkwell::basic_block::BasicBlock, cost_table: &CostTable) -> u64 {
+        let mut cost = 0;
+
+        let mut instruction = block.get_first_instruction();
+        while let Some(current) = instruction {
+            cost += Self::instruction_cost(current, cost_table);
+            instruction = current.get_next_instruction();
+        }
+
+        cost
+    }
+
+    ///
+    /// Returns the `instruction` cost.
+    ///
+    /// Calls to functions present in the table are priced by the callee name.
+    ///
+    fn instruction_cost(
+        instruction: inkwell::values::InstructionValue,
+        cost_table: &CostTable,
+    ) -> u64 {
+        let opcode = instruction.get_opcode();
+
+        if let inkwell::values::InstructionOpcode::Call
+        | inkwell::values::InstructionOpcode::Invoke = opcode
+        {
+            if let Some(cost) =
+                Self::callee_name(instruction).and_then(|name| cost_table.intrinsic(name.as_str()))
+            {
+                return cost;
+            }
+        }
+
+        cost_table.instruction(opcode)
+    }
+
+    ///
+    /// Returns the callee name of the call or invoke `instruction`.
+    ///
+    /// The callee is always the last operand of such instructions.
+    ///
+    pub(crate) fn callee_name(instruction: inkwell::values::InstructionValue) -> Option<String> {
+        let operands_count = instruction.get_num_operands();
+        if operands_count == 0 {
+            return None;
+        }
+
+        match instruction
+            .get_operand(operands_count - 1)
+            .and_then(|operand| operand.left())
+        {
+            Some(inkwell::values::BasicValueEnum::PointerValue(pointer)) => {
+                Some(pointer.get_name().to_string_lossy().to_string())
+            }
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the successors of the `block` taken from its terminator operands.
+    ///
+    pub(crate) fn successors(
+        block: inkwell::basic_block::BasicBlock,
+    ) -> Vec<inkwell::basic_block::BasicBlock> {
+        let terminator = match block.get_terminator() {
+            Some(terminator) => terminator,
+            None => return vec![],
+        };
+
+        (0..terminator.get_num_operands())
+            .filter_map(|index| terminator.get_operand(index))
+            .filter_map(|operand| operand.right())
+            .collect()
+    }
+
+    ///
+    /// Returns the most expensive path cost starting from the block at `index`.
+    ///
+    /// The blocks which are being visited are skipped in order to ignore the loop back edges.
+    ///
+    fn worst_case(
+        index: usize,
+        blocks: &[inkwell::basic_block::BasicBlock],
+        costs: &[u64],
+        worst_cases: &mut [Option<u64>],
+        is_visiting: &mut [bool],
+    ) -> u64 {
+        if let Some(cost) = worst_cases[index] {
+            return cost;
+        }
+
+        is_visiting[index] = true;
+        let mut successors_worst_case = 0;
+        for successor in Self::successors(blocks[index]) {
+            let successor_index = match blocks.iter().position(|block| block == &successor) {
+                Some(successor_index) => successor_index,
+                None => continue,
+            };
+            if is_visiting[successor_index] {
+                continue;
+            }
+
+            successors_worst_case = std::cmp::max(
+                successors_worst_case,
+                Self::worst_case(successor_index, blocks, costs, worst_cases, is_visiting),
+            );
+        }
+        is_visiting[index] = false;
+
+        let cost = costs[index] + successors_worst_case;
+        worst_cases[index] = Some(cost);
+        cost
+    }
+}
@@ -0,0 +1,46 @@
+//!
+//! The gas estimation report storage access entry.
+//!
+
+use serde::Serialize;
+
+///
+/// The gas estimation report storage access entry.
+///
+/// Groups the accesses to the same slot within a function.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageAccess {
+    /// The slot, which is the constant value or the LLVM IR value name.
+    pub slot: String,
+    /// The number of the loads.
+    pub loads: usize,
+    /// The number of the stores.
+    pub stores: usize,
+    /// The number of the accesses hinted as cold.
+    pub cold: usize,
+    /// The number of the accesses hinted as warm.
+    pub warm: usize,
+}
+
+impl StorageAccess {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(slot: String) -> Self {
+        Self {
+            slot,
+            loads: 0,
+            stores: 0,
+            cold: 0,
+            warm: 0,
+        }
+    }
+
+    ///
+    /// Returns the total number of the accesses.
+    ///
+    pub fn count(&self) -> usize {
+        self.loads + self.stores
+    }
+}
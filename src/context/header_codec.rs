@@ -0,0 +1,207 @@
+//!
+//! The memory header codec.
+//!
+
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The memory header codec.
+///
+/// The header word is laid out as follows, starting from the lowest bit:
+/// 32 bits of the data size, the constructor call flag at bit 64, and the system call flag at bit 72.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderCodec<'ctx> {
+    /// The header word value.
+    value: inkwell::values::IntValue<'ctx>,
+}
+
+impl<'ctx> HeaderCodec<'ctx> {
+    /// The data size bit offset.
+    pub const OFFSET_SIZE: usize = 0;
+    /// The data size bitlength.
+    pub const BITLENGTH_SIZE: usize = compiler_common::BITLENGTH_X32;
+    /// The constructor call flag bit offset.
+    pub const OFFSET_CONSTRUCTOR_FLAG: usize = 8 * compiler_common::BITLENGTH_BYTE;
+    /// The system call flag bit offset.
+    pub const OFFSET_SYSTEM_FLAG: usize =
+        Self::OFFSET_CONSTRUCTOR_FLAG + compiler_common::BITLENGTH_BYTE;
+
+    ///
+    /// Creates an empty header.
+    ///
+    pub fn new<D>(context: &Context<'ctx, D>) -> Self
+    where
+        D: Dependency,
+    {
+        Self {
+            value: context.field_const(0),
+        }
+    }
+
+    ///
+    /// Wraps an existing header word.
+    ///
+    pub fn from_value(value: inkwell::values::IntValue<'ctx>) -> Self {
+        Self { value }
+    }
+
+    ///
+    /// Returns the header word value.
+    ///
+    pub fn value(&self) -> inkwell::values::IntValue<'ctx> {
+        self.value
+    }
+
+    ///
+    /// Sets the data size.
+    ///
+    pub fn set_size<D>(
+        self,
+        context: &Context<'ctx, D>,
+        size: inkwell::values::IntValue<'ctx>,
+    ) -> Self
+    where
+        D: Dependency,
+    {
+        self.set_field(context, size, Self::OFFSET_SIZE, Self::BITLENGTH_SIZE)
+    }
+
+    ///
+    /// Sets the constructor call flag.
+    ///
+    pub fn set_constructor_flag<D>(self, context: &Context<'ctx, D>, is_set: bool) -> Self
+    where
+        D: Dependency,
+    {
+        self.set_field(
+            context,
+            context.field_const(is_set as u64),
+            Self::OFFSET_CONSTRUCTOR_FLAG,
+            1,
+        )
+    }
+
+    ///
+    /// Sets the system call flag.
+    ///
+    pub fn set_system_flag<D>(self, context: &Context<'ctx, D>, is_set: bool) -> Self
+    where
+        D: Dependency,
+    {
+        self.set_field(
+            context,
+            context.field_const(is_set as u64),
+            Self::OFFSET_SYSTEM_FLAG,
+            1,
+        )
+    }
+
+    ///
+    /// Returns the data size.
+    ///
+    pub fn size<D>(&self, context: &Context<'ctx, D>) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        self.get_field(
+            context,
+            Self::OFFSET_SIZE,
+            Self::BITLENGTH_SIZE,
+            "header_size",
+        )
+    }
+
+    ///
+    /// Returns the constructor call flag as a field value.
+    ///
+    pub fn constructor_flag<D>(&self, context: &Context<'ctx, D>) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        self.get_field(
+            context,
+            Self::OFFSET_CONSTRUCTOR_FLAG,
+            1,
+            "header_constructor_flag",
+        )
+    }
+
+    ///
+    /// Returns the system call flag as a field value.
+    ///
+    pub fn system_flag<D>(&self, context: &Context<'ctx, D>) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        self.get_field(context, Self::OFFSET_SYSTEM_FLAG, 1, "header_system_flag")
+    }
+
+    ///
+    /// Replaces `bitlength` bits at `offset` with `value`.
+    ///
+    fn set_field<D>(
+        mut self,
+        context: &Context<'ctx, D>,
+        value: inkwell::values::IntValue<'ctx>,
+        offset: usize,
+        bitlength: usize,
+    ) -> Self
+    where
+        D: Dependency,
+    {
+        let mask = Self::mask(context, bitlength);
+        let offset = context.field_const(offset as u64);
+
+        let cleared = context.builder().build_and(
+            self.value,
+            mask.const_shl(offset).const_not(),
+            "header_cleared",
+        );
+        let value = context
+            .builder()
+            .build_and(value, mask, "header_field_masked");
+        let value = context
+            .builder()
+            .build_left_shift(value, offset, "header_field_shifted");
+        self.value = context
+            .builder()
+            .build_or(cleared, value, "header_value_updated");
+        self
+    }
+
+    ///
+    /// Extracts `bitlength` bits at `offset`.
+    ///
+    fn get_field<D>(
+        &self,
+        context: &Context<'ctx, D>,
+        offset: usize,
+        bitlength: usize,
+        name: &str,
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        let value = context.builder().build_right_shift(
+            self.value,
+            context.field_const(offset as u64),
+            false,
+            "header_field_shifted",
+        );
+        context
+            .builder()
+            .build_and(value, Self::mask(context, bitlength), name)
+    }
+
+    ///
+    /// Returns the low `bitlength` bits mask.
+    ///
+    fn mask<D>(context: &Context<'ctx, D>, bitlength: usize) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        context.field_const(u64::MAX >> (u64::BITS as usize - bitlength))
+    }
+}
@@ -0,0 +1,18 @@
+//!
+//! The long return handling policy.
+//!
+
+///
+/// The long return handling policy.
+///
+/// The long return is a `return` from a nested function, implemented as throwing with the
+/// long return flag set, so it must be caught at the upper level.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LongReturnPolicy {
+    /// The exception is propagated regardless of the flag, e.g. in the ordinary functions.
+    Propagate,
+    /// The flag is checked, and the function returns normally if it is set, e.g. in the upper
+    /// level functions like the selector and constructor.
+    Return,
+}
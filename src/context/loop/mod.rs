@@ -0,0 +1,87 @@
+//!
+//! The LLVM generator loop.
+//!
+
+pub mod unroll_hint;
+
+use self::unroll_hint::UnrollHint;
+
+///
+/// The LLVM generator loop.
+///
+#[derive(Debug, Clone)]
+pub struct Loop<'ctx> {
+    /// The loop current block.
+    pub body_block: inkwell::basic_block::BasicBlock<'ctx>,
+    /// The increment block before the body.
+    pub continue_block: inkwell::basic_block::BasicBlock<'ctx>,
+    /// The join block after the body.
+    pub join_block: inkwell::basic_block::BasicBlock<'ctx>,
+
+    /// The unrolling hint attached to the back edge.
+    pub unroll_hint: Option<UnrollHint>,
+    /// The known trip count.
+    pub trip_count: Option<u64>,
+}
+
+impl<'ctx> Loop<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        body_block: inkwell::basic_block::BasicBlock<'ctx>,
+        continue_block: inkwell::basic_block::BasicBlock<'ctx>,
+        join_block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) -> Self {
+        Self {
+            body_block,
+            continue_block,
+            join_block,
+
+            unroll_hint: None,
+            trip_count: None,
+        }
+    }
+
+    ///
+    /// A shortcut constructor with the optimizer hints.
+    ///
+    pub fn new_with_metadata(
+        body_block: inkwell::basic_block::BasicBlock<'ctx>,
+        continue_block: inkwell::basic_block::BasicBlock<'ctx>,
+        join_block: inkwell::basic_block::BasicBlock<'ctx>,
+        unroll_hint: UnrollHint,
+        trip_count: Option<u64>,
+    ) -> Self {
+        let mut object = Self::new(body_block, continue_block, join_block);
+        object.unroll_hint = Some(unroll_hint);
+        object.trip_count = trip_count;
+        object
+    }
+
+    ///
+    /// Returns the `llvm.loop` metadata node, if there are any hints.
+    ///
+    pub fn metadata(
+        &self,
+        llvm: &'ctx inkwell::context::Context,
+    ) -> Option<inkwell::values::MetadataValue<'ctx>> {
+        let unroll_hint = self.unroll_hint?;
+
+        let mut options = vec![llvm
+            .metadata_node(&[llvm.metadata_string(unroll_hint.option_name()).into()])
+            .into()];
+        if let (UnrollHint::Enable, Some(trip_count)) = (unroll_hint, self.trip_count) {
+            options.push(
+                llvm.metadata_node(&[
+                    llvm.metadata_string("llvm.loop.unroll.count").into(),
+                    llvm.i32_type()
+                        .const_int(trip_count.min(u32::MAX as u64), false)
+                        .into(),
+                ])
+                .into(),
+            );
+        }
+        Some(llvm.metadata_node(options.as_slice()))
+    }
+}
@@ -0,0 +1,29 @@
+//!
+//! The LLVM generator loop unrolling hint.
+//!
+
+///
+/// The LLVM generator loop unrolling hint.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnrollHint {
+    /// The loop must not be unrolled.
+    Disable,
+    /// The loop should be unrolled. If the trip count is known, it is used as the unroll count.
+    Enable,
+    /// The loop should be unrolled completely.
+    Full,
+}
+
+impl UnrollHint {
+    ///
+    /// Returns the `llvm.loop` metadata option name.
+    ///
+    pub fn option_name(&self) -> &'static str {
+        match self {
+            Self::Disable => "llvm.loop.unroll.disable",
+            Self::Enable => "llvm.loop.unroll.enable",
+            Self::Full => "llvm.loop.unroll.full",
+        }
+    }
+}
@@ -2,34 +2,124 @@
 //! The LLVM generator context.
 //!
 
+pub mod abi_type;
+pub mod account_method;
 pub mod address_space;
+pub mod alignment_verifier;
 pub mod argument;
+pub mod artifact;
+pub mod assembly_hook;
+pub mod cache;
+pub mod call_definition;
+pub mod call_result;
+pub mod calldata_mode;
+pub mod capabilities;
+pub mod catch_payload;
 pub mod code_type;
+pub mod constant_pool;
+pub mod debug_tracer;
+pub mod dump;
+pub mod eliminated_write;
+pub mod error;
 pub mod evm_data;
+pub mod evm_version;
+pub mod exit_kind;
 pub mod function;
+pub mod gas_report;
+pub mod header_codec;
+pub mod long_return_policy;
 pub mod r#loop;
+pub mod naming_policy;
+pub mod operands;
 pub mod optimizer;
+pub mod panic_code;
+pub mod privileged_operation;
+pub mod restricted_instruction;
+pub mod size_report;
+pub mod snapshot;
+pub mod statistics;
+pub mod storage_hint;
+pub mod submodule;
+pub mod symbol;
+pub mod versioned_instruction;
+pub mod warning;
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::RwLock;
 
 use inkwell::types::BasicType;
+use inkwell::values::AnyValue;
 use inkwell::values::BasicValue;
+use num::Zero;
 
+use crate::dependency::output::Output as DependencyOutput;
+use crate::dependency::request::Request as DependencyRequest;
+use crate::dump_flag::set::Set as DumpFlagSet;
 use crate::dump_flag::DumpFlag;
 use crate::Dependency;
+use crate::WriteLLVM;
 
 use self::address_space::AddressSpace;
+use self::alignment_verifier::AlignmentVerifier;
+use self::argument::Argument;
+use self::artifact::Artifact;
+use self::assembly_hook::AssemblyHook;
+use self::cache::Cache;
+use self::call_definition::CallDefinition;
+use self::calldata_mode::CalldataMode;
+use self::capabilities::keccak256::Keccak256;
+use self::capabilities::system_intrinsics::SystemIntrinsics;
+use self::capabilities::Capabilities;
+use self::catch_payload::CatchPayload;
 use self::code_type::CodeType;
+use self::constant_pool::ConstantPool;
+use self::debug_tracer::DebugTracer;
+use self::dump::Dump;
+use self::eliminated_write::EliminatedWrite;
+use self::error::Error;
 use self::evm_data::EVMData;
+use self::evm_version::EvmVersion;
+use self::exit_kind::ExitKind;
+use self::function::bytes_copy::BytesCopy;
+use self::function::error_encoder::ErrorEncoder;
 use self::function::evm_data::EVMData as FunctionEVMData;
+use self::function::guard::Guard;
 use self::function::intrinsic::Intrinsic as IntrinsicFunction;
+use self::function::memory_compare::MemoryCompare;
+use self::function::nonreentrant_guard::NonreentrantGuard;
+use self::function::paymaster_flow::PaymasterFlow;
 use self::function::r#return::Return as FunctionReturn;
+use self::function::revert_on_failure::RevertOnFailure;
 use self::function::runtime::Runtime;
+use self::function::stdlib::StdlibFunction;
 use self::function::Function;
+use self::gas_report::cost_table::CostTable;
+use self::gas_report::GasReport;
+use self::header_codec::HeaderCodec;
+use self::long_return_policy::LongReturnPolicy;
+use self::naming_policy::NamingPolicy;
+use self::optimizer::settings::Settings as OptimizerSettings;
 use self::optimizer::Optimizer;
+use self::panic_code::PanicCode;
+use self::privileged_operation::PrivilegedOperation;
+use self::r#loop::unroll_hint::UnrollHint as LoopUnrollHint;
 use self::r#loop::Loop;
+use self::restricted_instruction::RestrictedInstruction;
+use self::size_report::SizeReport;
+use self::snapshot::Snapshot;
+use self::statistics::Statistics;
+use self::storage_hint::StorageHint;
+use self::submodule::Submodule;
+use self::symbol::kind::Kind as SymbolKind;
+use self::symbol::Symbol;
+use self::versioned_instruction::VersionedInstruction;
+use self::warning::span::Span as WarningSpan;
+use self::warning::Warning;
 
 ///
 /// The LLVM generator context.
@@ -46,22 +136,98 @@ where
     optimizer: Optimizer<'ctx>,
     /// The current module.
     module: inkwell::module::Module<'ctx>,
+    /// The submodules, which are linked into the main module before the optimization.
+    submodules: BTreeMap<String, Submodule<'ctx>>,
+    /// The main module parked while the named submodule is being emitted into.
+    parked_module: Option<(String, Submodule<'ctx>)>,
+    /// The names of the submodules linked into the main module.
+    linked_submodules: Vec<String>,
+    /// The names of the dependencies linked into the module as bitcode.
+    linked_dependencies: Vec<String>,
+    /// The module-level named metadata added by the frontend.
+    module_metadata: BTreeMap<String, Vec<String>>,
+    /// The dispatched function names by the 4-byte selector.
+    selectors: BTreeMap<u32, String>,
     /// The current function.
     function: Option<Function<'ctx>>,
     /// The loop context stack.
     loop_stack: Vec<Loop<'ctx>>,
+    /// The rarely executed blocks, e.g. the revert and panic paths.
+    cold_blocks: Vec<inkwell::basic_block::BasicBlock<'ctx>>,
 
     /// The current contract code type, if known.
     pub code_type: Option<CodeType>,
+    /// The immutables set by the deploy code, written to the storage on the construction.
+    immutables: BTreeSet<String>,
+    /// The EVM version the instructions are checked against.
+    evm_version: EvmVersion,
     /// The runtime functions.
     pub runtime: Runtime<'ctx>,
     /// The declared functions.
     pub functions: HashMap<String, Function<'ctx>>,
+    /// The unreferenced functions removed before the optimization.
+    removed_functions: Vec<String>,
+    /// The storage writes eliminated by the storage write combining.
+    eliminated_writes: Vec<EliminatedWrite>,
+    /// The assembly text post-processing hook.
+    assembly_hook: Option<AssemblyHook>,
+    /// The number of the post-processed assembly instructions.
+    assembly_instructions: Option<usize>,
+    /// Whether to collect the optimization statistics.
+    is_statistics_enabled: bool,
+    /// The optimization statistics, if collected.
+    statistics: Option<Statistics>,
+    /// The function input hashes for the incremental compilation.
+    function_input_hashes: BTreeMap<String, String>,
+    /// The incremental compilation cache captured after the optimization.
+    cache: Option<Cache>,
 
     /// The project dependency manager.
     dependency_manager: Option<Arc<RwLock<D>>>,
+    /// The linker symbol table.
+    symbols: HashMap<String, Symbol>,
+    /// The function name mangling scope, e.g. the Yul object name.
+    mangling_scope: Option<String>,
+    /// The value naming policy.
+    naming_policy: NamingPolicy,
+    /// The calldata access mode.
+    calldata_mode: CalldataMode,
+    /// The prefix of the value names, e.g. the frontend namespace.
+    name_prefix: Option<String>,
     /// Whether to dump the specified IRs.
-    dump_flags: Vec<DumpFlag>,
+    dump_flags: DumpFlagSet,
+    /// The dumps requested by the dump flags.
+    dumps: RefCell<Vec<Dump>>,
+    /// Whether the privileged operations are allowed.
+    is_system_mode: bool,
+    /// Whether the heap accesses are checked against the reserved region.
+    is_memory_safety_enabled: bool,
+    /// The arithmetic overflow checking scopes.
+    overflow_checks: Vec<bool>,
+    /// Whether the child context is being written by a `with_child_context` call.
+    is_child_context_active: bool,
+    /// Whether the throws are routed through the shared throw function.
+    is_shared_throw_enabled: bool,
+    /// Whether the instructions are annotated with the originating EVM operations.
+    is_evm_op_annotation_enabled: bool,
+    /// The EVM operation being translated, if the annotation is enabled.
+    evm_op: Option<String>,
+    /// The contract context values cached by the getters in any function of the module.
+    cached_context_values: HashSet<u64>,
+    /// The contract context values invalidated in the module, which are reloaded at every use.
+    invalidated_context_values: HashSet<u64>,
+    /// The interned field constants parsed from strings.
+    constant_pool: ConstantPool<'ctx>,
+    /// The debug tracer. If unset, the debug traces are not emitted.
+    debug_tracer: Option<DebugTracer>,
+    /// Whether the storage writes are journaled via the debug tracer.
+    is_storage_journal_enabled: bool,
+    /// The target capabilities.
+    capabilities: Capabilities,
+    /// The warnings emitted during the translation.
+    warnings: Vec<Warning>,
+    /// The warning codes promoted to errors.
+    warnings_as_errors: HashSet<String>,
 
     /// The EVM compiler data.
     evm_data: Option<EVMData<'ctx>>,
@@ -71,6 +237,45 @@ impl<'ctx, D> Context<'ctx, D>
 where
     D: Dependency,
 {
+    /// The account address bitlength.
+    pub const BITLENGTH_ADDRESS: usize = 160;
+
+    /// The last call return data size global variable name.
+    pub const GLOBAL_RETURN_DATA_SIZE: &'static str = "__return_data_size";
+
+    /// The prefix of the deploy code immutable global variable names.
+    pub const GLOBAL_IMMUTABLE_PREFIX: &'static str = "__immutable_";
+
+    /// The heap offset of the staged calldata global variable name.
+    pub const GLOBAL_CALLDATA_OFFSET: &'static str = "__calldata_offset";
+
+    /// The separator between the mangling scope and the function name.
+    pub const MANGLING_SEPARATOR: &'static str = "::";
+
+    /// The maximum number of the verbatim inputs and outputs.
+    pub const VERBATIM_ARGUMENTS_LIMIT: usize = 16;
+
+    /// The heap offset of the free memory pointer, following the Solidity memory layout.
+    pub const HEAP_FREE_POINTER_OFFSET: usize = 0x40;
+
+    /// The shared throw function name.
+    pub const FUNCTION_SHARED_THROW: &'static str = "__throw";
+
+    /// The calldata staging function name.
+    pub const FUNCTION_CALLDATA_STAGE: &'static str = "__calldata_stage";
+
+    /// The instruction metadata kind of the EVM operation annotations.
+    pub const EVM_OP_METADATA_KIND: &'static str = "evm.op";
+
+    /// The heap region reserved for the header, long return flag, and scratch space.
+    pub const HEAP_RESERVED_SIZE: usize =
+        compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
+
+    /// The branch weight of the edges leading to the ordinary blocks.
+    const BRANCH_WEIGHT_HOT: u64 = 2000;
+    /// The branch weight of the edges leading to the cold blocks.
+    const BRANCH_WEIGHT_COLD: u64 = 1;
+
     /// The functions hashmap default capacity.
     const FUNCTION_HASHMAP_INITIAL_CAPACITY: usize = 64;
     /// The loop stack default capacity.
@@ -102,15 +307,53 @@ where
             builder: llvm.create_builder(),
             optimizer,
             module,
+            submodules: BTreeMap::new(),
+            parked_module: None,
+            linked_submodules: Vec::new(),
+            linked_dependencies: Vec::new(),
+            module_metadata: BTreeMap::new(),
+            selectors: BTreeMap::new(),
             function: None,
             loop_stack: Vec::with_capacity(Self::LOOP_STACK_INITIAL_CAPACITY),
+            cold_blocks: Vec::new(),
 
             code_type: None,
+            immutables: BTreeSet::new(),
+            evm_version: EvmVersion::default(),
             runtime,
             functions: HashMap::with_capacity(Self::FUNCTION_HASHMAP_INITIAL_CAPACITY),
+            removed_functions: Vec::new(),
+            eliminated_writes: Vec::new(),
+            assembly_hook: None,
+            assembly_instructions: None,
+            is_statistics_enabled: false,
+            statistics: None,
+            function_input_hashes: BTreeMap::new(),
+            cache: None,
 
             dependency_manager,
-            dump_flags,
+            symbols: HashMap::new(),
+            mangling_scope: None,
+            naming_policy: NamingPolicy::default(),
+            calldata_mode: CalldataMode::default(),
+            name_prefix: None,
+            dump_flags: dump_flags.into_iter().collect(),
+            dumps: RefCell::new(Vec::new()),
+            is_system_mode: false,
+            is_memory_safety_enabled: false,
+            overflow_checks: Vec::new(),
+            is_child_context_active: false,
+            is_shared_throw_enabled: false,
+            is_evm_op_annotation_enabled: false,
+            evm_op: None,
+            cached_context_values: HashSet::new(),
+            invalidated_context_values: HashSet::new(),
+            constant_pool: ConstantPool::default(),
+            debug_tracer: None,
+            is_storage_journal_enabled: false,
+            capabilities: Capabilities::default(),
+            warnings: Vec::new(),
+            warnings_as_errors: HashSet::new(),
 
             evm_data: None,
         }
@@ -150,6 +393,74 @@ where
         &self.builder
     }
 
+    ///
+    /// Runs `closure` with the raw LLVM IR builder.
+    ///
+    /// Is an escape hatch for the inkwell features not wrapped by the context. Checks that the
+    /// insertion point is inside the current function before the call, and that the resulting
+    /// basic block has no instructions after its terminator.
+    ///
+    pub fn with_raw_builder<F, T>(&self, closure: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&inkwell::builder::Builder<'ctx>) -> T,
+    {
+        let function = self
+            .function
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The raw builder requires the current function"))?;
+        let block = self
+            .builder
+            .get_insert_block()
+            .ok_or_else(|| anyhow::anyhow!("The raw builder requires the current basic block"))?;
+        if block.get_parent() != Some(function.value) {
+            anyhow::bail!(
+                "The insertion point is outside of the current function `{}`",
+                function.name
+            );
+        }
+
+        let result = closure(&self.builder);
+
+        let block = self
+            .builder
+            .get_insert_block()
+            .ok_or_else(|| anyhow::anyhow!("The raw builder has left no current basic block"))?;
+        let mut instruction = block.get_first_instruction();
+        while let Some(current) = instruction {
+            let next = current.get_next_instruction();
+            if next.is_some() && Self::is_terminator(current) {
+                anyhow::bail!(
+                    "Basic block `{}` of function `{}` has instructions after its terminator",
+                    block.get_name().to_string_lossy(),
+                    function.name
+                );
+            }
+            instruction = next;
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Checks whether `instruction` is a basic block terminator.
+    ///
+    pub fn is_terminator(instruction: inkwell::values::InstructionValue<'ctx>) -> bool {
+        matches!(
+            instruction.get_opcode(),
+            inkwell::values::InstructionOpcode::Return
+                | inkwell::values::InstructionOpcode::Br
+                | inkwell::values::InstructionOpcode::Switch
+                | inkwell::values::InstructionOpcode::IndirectBr
+                | inkwell::values::InstructionOpcode::Invoke
+                | inkwell::values::InstructionOpcode::Unreachable
+                | inkwell::values::InstructionOpcode::Resume
+                | inkwell::values::InstructionOpcode::CallBr
+                | inkwell::values::InstructionOpcode::CatchRet
+                | inkwell::values::InstructionOpcode::CatchSwitch
+                | inkwell::values::InstructionOpcode::CleanupRet
+        )
+    }
+
     ///
     /// Returns the current module reference.
     ///
@@ -161,708 +472,4470 @@ where
     /// Checks whether the specified dump flag is set.
     ///
     pub fn has_dump_flag(&self, dump_flag: DumpFlag) -> bool {
-        self.dump_flags.contains(&dump_flag)
+        self.dump_flags.contains(dump_flag)
     }
 
     ///
-    /// Optimizes the current module.
-    ///
-    /// Should be only run when the entire module has been translated.
-    ///
-    /// Only returns `true` if any of the passes modified the function.
+    /// Collects the dump built by `text` for the artifact, if the dump `flag` is set.
     ///
-    pub fn optimize(&self) -> bool {
-        let mut is_optimized = false;
-
-        for (_, function) in self.functions.iter() {
-            is_optimized |= self.optimizer.run_on_function(function.value);
+    fn dump<F>(&self, flag: DumpFlag, text: F)
+    where
+        F: FnOnce() -> String,
+    {
+        if self.has_dump_flag(flag) {
+            self.dumps.borrow_mut().push(Dump::new(flag, text()));
         }
-        is_optimized |= self.optimizer.run_on_module(self.module());
-
-        is_optimized
     }
 
     ///
-    /// Verifies the current module.
+    /// Enables or disables the system mode.
     ///
-    /// # Panics
-    /// If verification fails.
+    /// The privileged operations, e.g. mimic calls or accessing the storage of other accounts,
+    /// are only emitted in the system mode.
     ///
-    pub fn verify(&self) -> anyhow::Result<()> {
-        self.module()
-            .verify()
-            .map_err(|error| anyhow::anyhow!(error.to_string()))
+    pub fn set_system_mode(&mut self, value: bool) {
+        self.is_system_mode = value;
     }
 
     ///
-    /// Compiles a contract dependency, if the dependency manager is set.
+    /// Checks whether the system mode is enabled.
     ///
-    pub fn compile_dependency(&mut self, name: &str) -> anyhow::Result<String> {
-        self.dependency_manager
-            .to_owned()
-            .ok_or_else(|| anyhow::anyhow!("The dependency manager is unset"))
-            .and_then(|manager| {
-                Dependency::compile(
-                    manager,
-                    name,
-                    self.module.get_name().to_str().expect("Always valid"),
-                    self.optimizer.level_middle(),
-                    self.optimizer.level_back(),
-                    self.dump_flags.clone(),
-                )
-            })
+    pub fn is_system_mode(&self) -> bool {
+        self.is_system_mode
     }
 
     ///
-    /// Gets a deployed library address.
+    /// Enables or disables the memory safety mode.
     ///
-    pub fn resolve_library(&self, path: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
-        self.dependency_manager
-            .to_owned()
-            .ok_or_else(|| anyhow::anyhow!("The dependency manager is unset"))
-            .and_then(|manager| {
-                let address = Dependency::resolve_library(manager, path)?;
-                Ok(self.field_const_str(address.as_str()))
-            })
+    /// In the memory safety mode, every heap access below `HEAP_RESERVED_SIZE` throws, so the
+    /// frontend layout collisions with the reserved region are caught at runtime.
+    ///
+    pub fn set_memory_safety(&mut self, value: bool) {
+        self.is_memory_safety_enabled = value;
     }
 
     ///
-    /// Appends a function to the current module.
+    /// Sets the shared throw mode.
     ///
-    pub fn add_function(
-        &mut self,
-        name: &str,
-        r#type: inkwell::types::FunctionType<'ctx>,
-        linkage: Option<inkwell::module::Linkage>,
-    ) {
-        let value = self.module().add_function(name, r#type, linkage);
-        for index in 0..value.count_params() {
-            if value
-                .get_nth_param(index)
-                .map(|argument| argument.get_type().is_pointer_type())
-                .unwrap_or_default()
-            {
-                value.set_param_alignment(index, compiler_common::SIZE_FIELD as u32);
-            }
-        }
-
-        value.set_personality_function(self.runtime.personality);
-
-        let entry_block = self.llvm.append_basic_block(value, "entry");
-        let throw_block = self.llvm.append_basic_block(value, "throw");
-        let catch_block = self.llvm.append_basic_block(value, "catch");
-        let return_block = self.llvm.append_basic_block(value, "return");
-
-        let function = Function::new(
-            name.to_owned(),
-            value,
-            entry_block,
-            throw_block,
-            catch_block,
-            return_block,
-            None,
-        );
-        self.functions.insert(name.to_string(), function.clone());
+    /// In the shared throw mode, all throws in the module call one internal function instead of
+    /// calling the exception throwing function with its arguments, which reduces the code size.
+    ///
+    pub fn set_shared_throw(&mut self, value: bool) {
+        self.is_shared_throw_enabled = value;
     }
 
     ///
-    /// Appends a function to the current module.
+    /// Sets the EVM operation annotation mode.
     ///
-    pub fn add_function_evm(
-        &mut self,
-        name: &str,
-        r#type: inkwell::types::FunctionType<'ctx>,
-        linkage: Option<inkwell::module::Linkage>,
-        evm_data: FunctionEVMData<'ctx>,
-    ) {
-        self.add_function(name, r#type, linkage);
-        self.functions
-            .get_mut(name)
-            .expect("Always exists")
-            .evm_data = Some(evm_data);
+    /// In the annotation mode, every instruction emitted by the EVM translators is tagged with
+    /// the `evm.op` metadata naming the originating operation, e.g. `!{!"SSTORE"}`, so the IR
+    /// dumps can be mapped back to the EVM-level code.
+    ///
+    pub fn set_evm_op_annotation(&mut self, value: bool) {
+        self.is_evm_op_annotation_enabled = value;
     }
 
     ///
-    /// Returns the current function.
+    /// Enters a scope where the arithmetic overflow checks are enabled or disabled according to
+    /// `value`, e.g. the Solidity `unchecked` blocks.
     ///
-    pub fn function(&self) -> &Function<'ctx> {
-        self.function.as_ref().expect("Must be declared before use")
+    /// The scopes are nested and must be closed with `pop_overflow_checks`.
+    ///
+    pub fn set_overflow_checks(&mut self, value: bool) {
+        self.overflow_checks.push(value);
     }
 
     ///
-    /// Returns the current function as a mutable reference.
+    /// Exits the current overflow checking scope.
     ///
-    pub fn function_mut(&mut self) -> &mut Function<'ctx> {
-        self.function.as_mut().expect("Must be declared before use")
+    pub fn pop_overflow_checks(&mut self) {
+        self.overflow_checks.pop();
     }
 
     ///
-    /// Sets the current function.
+    /// Whether the arithmetic overflow checks are enabled in the current scope.
     ///
-    /// # Panics
-    /// If the function with `name` does not exist.
+    /// The checks are disabled outside of any scope, following the EVM semantics.
     ///
-    pub fn set_function(&mut self, function: Function<'ctx>) {
-        self.function = Some(function);
+    pub fn are_overflow_checks_enabled(&self) -> bool {
+        self.overflow_checks.last().copied().unwrap_or_default()
     }
 
     ///
-    /// Sets the return entity for the current function.
+    /// Returns an error if `operation` is requested outside of the system mode.
     ///
-    pub fn set_function_return(&mut self, r#return: FunctionReturn<'ctx>) {
-        let name = self.function().name.clone();
+    pub fn check_system_mode(&self, operation: PrivilegedOperation) -> anyhow::Result<()> {
+        if !self.is_system_mode {
+            return Err(Error::PrivilegedOperation(operation).into());
+        }
 
-        self.functions
-            .get_mut(name.as_str())
-            .expect("Always exists")
-            .set_return(r#return.clone());
-        self.function_mut().set_return(r#return);
+        Ok(())
     }
 
     ///
-    /// Returns the specified intrinsic function.
+    /// Returns an error if `operation` relies on the system intrinsics, which the target does not
+    /// provide.
     ///
-    pub fn get_intrinsic_function(
-        &self,
-        intrinsic: IntrinsicFunction,
-    ) -> inkwell::values::FunctionValue<'ctx> {
-        self.module()
-            .get_intrinsic_function(intrinsic.name(), intrinsic.argument_types(self).as_slice())
-            .unwrap_or_else(|| panic!("Intrinsic function `{}` does not exist", intrinsic.name()))
+    pub fn check_system_intrinsics(&self, operation: PrivilegedOperation) -> anyhow::Result<()> {
+        match self.capabilities.system_intrinsics {
+            SystemIntrinsics::Supported => Ok(()),
+            SystemIntrinsics::Unsupported => {
+                Err(Error::UnsupportedSystemIntrinsic(operation).into())
+            }
+        }
     }
 
     ///
-    /// Appends a new basic block to the current function.
+    /// Returns an error if `instruction` is requested outside of the code type it is allowed in.
     ///
-    pub fn append_basic_block(&self, name: &str) -> inkwell::basic_block::BasicBlock<'ctx> {
-        self.llvm.append_basic_block(self.function().value, name)
+    /// Nothing is checked if the code type is unset.
+    ///
+    pub fn check_code_type(&self, instruction: RestrictedInstruction) -> anyhow::Result<()> {
+        match self.code_type {
+            Some(code_type) if code_type != instruction.allowed_code_type() => {
+                Err(Error::RestrictedInstruction {
+                    instruction,
+                    code_type,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
     }
 
     ///
-    /// Sets the current basic block.
+    /// Sets the EVM version the instructions are checked against.
     ///
-    pub fn set_basic_block(&self, block: inkwell::basic_block::BasicBlock<'ctx>) {
-        self.builder.position_at_end(block);
+    pub fn set_evm_version(&mut self, evm_version: EvmVersion) {
+        self.evm_version = evm_version;
     }
 
     ///
-    /// Returns the current basic block.
+    /// Returns the EVM version the instructions are checked against.
     ///
-    pub fn basic_block(&self) -> inkwell::basic_block::BasicBlock<'ctx> {
-        self.builder.get_insert_block().expect("Always exists")
+    pub fn evm_version(&self) -> EvmVersion {
+        self.evm_version
     }
 
     ///
-    /// Pushes a new loop context to the stack.
+    /// Returns an error if `instruction` is not available in the selected EVM version.
     ///
-    pub fn push_loop(
-        &mut self,
-        body_block: inkwell::basic_block::BasicBlock<'ctx>,
-        continue_block: inkwell::basic_block::BasicBlock<'ctx>,
-        join_block: inkwell::basic_block::BasicBlock<'ctx>,
-    ) {
-        self.loop_stack
-            .push(Loop::new(body_block, continue_block, join_block));
+    pub fn check_evm_version(&self, instruction: VersionedInstruction) -> anyhow::Result<()> {
+        if self.evm_version < instruction.introduced_in() {
+            return Err(Error::UnsupportedInstruction {
+                instruction,
+                evm_version: self.evm_version,
+            }
+            .into());
+        }
+
+        Ok(())
     }
 
     ///
-    /// Pops the current loop context from the stack.
+    /// Recreates the optimizer with the specified tuning `settings`.
     ///
-    pub fn pop_loop(&mut self) {
-        self.loop_stack.pop();
+    pub fn set_optimizer_settings(&mut self, settings: OptimizerSettings) {
+        self.optimizer = Optimizer::new_with_settings(
+            &self.module,
+            self.optimizer.level_middle(),
+            self.optimizer.level_back(),
+            settings,
+        );
     }
 
     ///
-    /// Returns the current loop context.
+    /// Enables the optimization statistics collection.
     ///
-    pub fn r#loop(&self) -> &Loop<'ctx> {
-        self.loop_stack
-            .last()
-            .expect("The current context is not in a loop")
+    pub fn set_statistics_enabled(&mut self, value: bool) {
+        self.is_statistics_enabled = value;
     }
 
     ///
-    /// Builds a stack allocation instruction.
+    /// Sets the debug tracer.
     ///
-    /// Sets the alignment to 256 bits.
+    /// If `None` is passed, the debug traces compile to nothing, which is the release mode.
     ///
-    pub fn build_alloca<T: BasicType<'ctx>>(
-        &self,
-        r#type: T,
-        name: &str,
-    ) -> inkwell::values::PointerValue<'ctx> {
-        let pointer = self.builder.build_alloca(r#type, name);
-        self.basic_block()
-            .get_last_instruction()
-            .expect("Always exists")
-            .set_alignment(compiler_common::SIZE_FIELD as u32)
-            .expect("Alignment is valid");
-        pointer
+    pub fn set_debug_tracer(&mut self, debug_tracer: Option<DebugTracer>) {
+        self.debug_tracer = debug_tracer;
     }
 
     ///
-    /// Builds a stack store instruction.
+    /// Enables or disables the storage write journaling.
     ///
-    /// Sets the alignment to 256 bits for stack and 1 bit for heap, parent, and child.
+    /// When enabled, every storage store built by `build_storage_store` is preceded by a load of
+    /// the old value, and followed by the `sstore.slot`, `sstore.old`, and `sstore.new` debug traces, so the
+    /// test frameworks can assert on the storage diffs. Requires a valid debug tracer.
     ///
-    pub fn build_store<V: BasicValue<'ctx>>(
-        &self,
-        pointer: inkwell::values::PointerValue<'ctx>,
-        value: V,
-    ) {
-        let instruction = self.builder.build_store(pointer, value);
-
-        let alignment = if inkwell::AddressSpace::from(AddressSpace::Stack)
-            == pointer.get_type().get_address_space()
-        {
-            compiler_common::SIZE_FIELD
-        } else {
-            1
-        };
+    pub fn set_storage_journal(&mut self, value: bool) -> anyhow::Result<()> {
+        if value {
+            match self.debug_tracer {
+                Some(DebugTracer::Contract(ref address)) => {
+                    self.field_const_hex(address.as_str())?;
+                }
+                Some(DebugTracer::Event) => {}
+                None => anyhow::bail!("The storage journal requires the debug tracer"),
+            }
+        }
 
-        instruction
-            .set_alignment(alignment as u32)
-            .expect("Alignment is valid");
+        self.is_storage_journal_enabled = value;
+        Ok(())
     }
 
     ///
-    /// Builds a stack load instruction.
-    ///
-    /// Sets the alignment to 256 bits for stack and 1 bit for heap, parent, and child.
+    /// Sets the target capabilities.
     ///
-    pub fn build_load(
-        &self,
-        pointer: inkwell::values::PointerValue<'ctx>,
-        name: &str,
-    ) -> inkwell::values::BasicValueEnum<'ctx> {
-        let value = self.builder.build_load(pointer, name);
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = capabilities;
+    }
 
-        let alignment = if inkwell::AddressSpace::from(AddressSpace::Stack)
-            == pointer.get_type().get_address_space()
-        {
-            compiler_common::SIZE_FIELD
-        } else {
-            1
-        };
+    ///
+    /// Returns the target capabilities.
+    ///
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
 
-        self.basic_block()
-            .get_last_instruction()
-            .expect("Always exists")
-            .set_alignment(alignment as u32)
-            .expect("Alignment is valid");
-        value
+    ///
+    /// Sets the warning codes promoted to errors.
+    ///
+    pub fn set_warnings_as_errors(&mut self, codes: HashSet<String>) {
+        self.warnings_as_errors = codes;
     }
 
     ///
-    /// Builds a conditional branch.
+    /// Emits a warning with the specified `code`.
     ///
-    /// Checks if there are no other terminators in the block.
+    /// # Errors
+    /// If the `code` is promoted to an error.
     ///
-    pub fn build_conditional_branch(
-        &self,
-        comparison: inkwell::values::IntValue<'ctx>,
-        then_block: inkwell::basic_block::BasicBlock<'ctx>,
-        else_block: inkwell::basic_block::BasicBlock<'ctx>,
-    ) {
-        if self.basic_block().get_terminator().is_some() {
-            return;
+    pub fn warn(
+        &mut self,
+        code: &str,
+        message: String,
+        span: Option<WarningSpan>,
+    ) -> anyhow::Result<()> {
+        let warning = Warning::new(code.to_owned(), message, span);
+        if self.warnings_as_errors.contains(code) {
+            return Err(Error::PromotedWarning(warning).into());
         }
 
-        self.builder
-            .build_conditional_branch(comparison, then_block, else_block);
+        self.warnings.push(warning);
+        Ok(())
     }
 
     ///
-    /// Builds an unconditional branch.
-    ///
-    /// Checks if there are no other terminators in the block.
+    /// Returns the warnings emitted so far.
     ///
-    pub fn build_unconditional_branch(
-        &self,
-        destination_block: inkwell::basic_block::BasicBlock<'ctx>,
-    ) {
-        if self.basic_block().get_terminator().is_some() {
-            return;
-        }
-
-        self.builder.build_unconditional_branch(destination_block);
+    pub fn warnings(&self) -> &[Warning] {
+        self.warnings.as_slice()
     }
 
     ///
-    /// Builds a call.
+    /// Optimizes the current module.
     ///
-    /// Checks if there are no other terminators in the block.
+    /// Should be only run when the entire module has been translated.
     ///
-    pub fn build_call(
-        &self,
-        function: inkwell::values::FunctionValue<'ctx>,
-        args: &[inkwell::values::BasicValueEnum<'ctx>],
-        name: &str,
-    ) -> Option<inkwell::values::BasicValueEnum<'ctx>> {
-        let call_site_value = self.builder.build_call(function, args, name);
+    /// Only returns `true` if any of the passes modified the function.
+    ///
+    /// The unreferenced private functions are removed before the main run. If the size budget is
+    /// set and exceeded, the size-optimizing pipeline is run once more. The incremental
+    /// compilation cache is captured afterwards, if the function inputs have been recorded.
+    ///
+    /// Collects the statistics if enabled or the corresponding flag is set.
+    /// Collects the code size report and statistics dumps if the corresponding flags are set.
+    ///
+    pub fn optimize(&mut self) -> bool {
+        let is_statistics_enabled =
+            self.is_statistics_enabled || self.has_dump_flag(DumpFlag::Statistics);
+        let mut statistics = if is_statistics_enabled {
+            let size_report = self.size_report();
+            Some(Statistics::new(
+                size_report.functions.len(),
+                size_report.instructions,
+            ))
+        } else {
+            None
+        };
 
-        if name == compiler_common::LLVM_FUNCTION_CXA_THROW {
-            return call_site_value.try_as_basic_value().left();
+        let mut is_optimized = self.remove_unreferenced_exception_blocks();
+        self.apply_cold_blocks();
+
+        let start = std::time::Instant::now();
+        let is_modified = self.eliminate_dead_functions();
+        if let Some(statistics) = statistics.as_mut() {
+            statistics.push_phase("dead function elimination", start, is_modified);
         }
+        is_optimized |= is_modified;
 
-        for index in 0..function.count_params() {
-            if function
-                .get_nth_param(index)
-                .map(|argument| argument.get_type().is_pointer_type())
-                .unwrap_or_default()
-            {
-                call_site_value.set_alignment_attribute(
-                    inkwell::attributes::AttributeLoc::Param(index),
-                    compiler_common::SIZE_FIELD as u32,
-                );
+        if self.optimizer.settings().is_storage_combining_enabled {
+            let start = std::time::Instant::now();
+            let is_modified = self.combine_storage_writes();
+            if let Some(statistics) = statistics.as_mut() {
+                statistics.push_phase("storage write combining", start, is_modified);
             }
+            is_optimized |= is_modified;
         }
 
-        if call_site_value
-            .try_as_basic_value()
-            .map_left(|value| value.is_pointer_value())
-            .left_or_default()
-        {
-            call_site_value.set_alignment_attribute(
-                inkwell::attributes::AttributeLoc::Return,
-                compiler_common::SIZE_FIELD as u32,
-            );
+        let start = std::time::Instant::now();
+        let mut is_modified = false;
+        for (_, function) in self.functions.iter() {
+            is_modified |= self.optimizer.run_on_function(function.value);
         }
+        if let Some(statistics) = statistics.as_mut() {
+            statistics.push_phase("function passes", start, is_modified);
+        }
+        is_optimized |= is_modified;
 
-        call_site_value.try_as_basic_value().left()
-    }
-
-    ///
-    /// Builds an invoke.
-    ///
-    /// Checks if there are no other terminators in the block.
-    ///
-    pub fn build_invoke(
-        &self,
-        function: inkwell::values::FunctionValue<'ctx>,
-        args: &[inkwell::values::BasicValueEnum<'ctx>],
-        name: &str,
-    ) -> Option<inkwell::values::BasicValueEnum<'ctx>> {
-        let join_block = self.append_basic_block("join");
+        let start = std::time::Instant::now();
+        let is_modified = self.optimizer.run_on_module(self.module());
+        if let Some(statistics) = statistics.as_mut() {
+            statistics.push_phase("module passes", start, is_modified);
+        }
+        is_optimized |= is_modified;
 
-        let call_site_value = self.builder.build_invoke(
-            function,
-            args,
-            join_block,
-            self.function().catch_block,
-            name,
-        );
+        if !self.linked_dependencies.is_empty() {
+            let start = std::time::Instant::now();
+            let is_modified = self.optimizer.run_on_module(self.module());
+            if let Some(statistics) = statistics.as_mut() {
+                statistics.push_phase("cross-object re-run", start, is_modified);
+            }
+            is_optimized |= is_modified;
+        }
 
-        for index in 0..function.count_params() {
-            if function
-                .get_nth_param(index)
-                .map(|argument| argument.get_type().is_pointer_type())
-                .unwrap_or_default()
-            {
-                call_site_value.set_alignment_attribute(
-                    inkwell::attributes::AttributeLoc::Param(index),
-                    compiler_common::SIZE_FIELD as u32,
-                );
+        if let Some(size_budget) = self.optimizer.settings().size_budget {
+            if self.size_report().estimated_size > size_budget {
+                let start = std::time::Instant::now();
+                let mut is_modified = false;
+                for (_, function) in self.functions.iter() {
+                    is_modified |= self.optimizer.run_on_function_for_size(function.value);
+                }
+                is_modified |= self.optimizer.run_on_module_for_size(self.module());
+                if let Some(statistics) = statistics.as_mut() {
+                    statistics.push_phase("size budget re-run", start, is_modified);
+                }
+                is_optimized |= is_modified;
             }
         }
 
-        if call_site_value
-            .try_as_basic_value()
-            .map_left(|value| value.is_pointer_value())
-            .left_or_default()
-        {
-            call_site_value.set_alignment_attribute(
-                inkwell::attributes::AttributeLoc::Return,
-                compiler_common::SIZE_FIELD as u32,
-            );
+        if !self.function_input_hashes.is_empty() {
+            self.cache = Some(Cache::new(
+                self.cache_key(),
+                self.module.write_bitcode_to_memory().as_slice().to_vec(),
+            ));
         }
 
-        self.set_basic_block(join_block);
+        if let Some(mut statistics) = statistics {
+            let size_report = self.size_report();
+            statistics.functions_after = size_report.functions.len();
+            statistics.instructions_after = size_report.instructions;
+            self.dump(DumpFlag::Statistics, || statistics.to_string());
+            self.statistics = Some(statistics);
+        }
 
-        call_site_value.try_as_basic_value().left()
+        self.dump(DumpFlag::SizeReport, || self.size_report().to_string());
+
+        is_optimized
     }
 
     ///
-    /// Builds a memory copy call.
+    /// Returns the code size report of the current module.
     ///
-    /// Sets the alignment to 1 bit for heap, parent, and child.
+    /// Should be only run when the module has been optimized.
     ///
-    pub fn build_memcpy(
-        &self,
-        intrinsic: IntrinsicFunction,
-        destination: inkwell::values::PointerValue<'ctx>,
-        source: inkwell::values::PointerValue<'ctx>,
-        size: inkwell::values::IntValue<'ctx>,
-        name: &str,
-    ) {
-        let intrinsic = self.get_intrinsic_function(intrinsic);
-
-        let call_site_value = self.builder.build_call(
-            intrinsic,
-            &[
-                destination.as_basic_value_enum(),
-                source.as_basic_value_enum(),
-                size.as_basic_value_enum(),
-                self.integer_type(compiler_common::BITLENGTH_BOOLEAN)
-                    .const_zero()
-                    .as_basic_value_enum(),
-            ],
-            name,
-        );
+    pub fn size_report(&self) -> SizeReport {
+        SizeReport::new(self.module())
+            .with_removed_functions(self.removed_functions.clone())
+            .with_assembly_instructions(self.assembly_instructions)
+    }
 
-        call_site_value.set_alignment_attribute(inkwell::attributes::AttributeLoc::Param(0), 1);
-        call_site_value.set_alignment_attribute(inkwell::attributes::AttributeLoc::Param(1), 1);
+    ///
+    /// Sets the assembly text post-processing hook.
+    ///
+    pub fn set_assembly_hook(&mut self, hook: Option<AssemblyHook>) {
+        self.assembly_hook = hook;
     }
 
     ///
-    /// Builds a return.
+    /// Applies the assembly text post-processing hook, if set, to the emitted `assembly`.
     ///
-    /// Checks if there are no other terminators in the block.
+    /// The instructions are counted again after the hook for the size report. If the hook has
+    /// changed the count, a warning is emitted, since the IR-based estimates no longer match.
     ///
-    pub fn build_return(&self, value: Option<&dyn BasicValue<'ctx>>) {
-        if self.basic_block().get_terminator().is_some() {
-            return;
+    pub fn post_process_assembly(&mut self, assembly: String) -> anyhow::Result<String> {
+        let hook = match self.assembly_hook {
+            Some(hook) => hook,
+            None => return Ok(assembly),
+        };
+
+        let instructions_before = AssemblyHook::count_instructions(assembly.as_str());
+        let assembly = hook.apply(assembly);
+        let instructions_after = AssemblyHook::count_instructions(assembly.as_str());
+        self.assembly_instructions = Some(instructions_after);
+
+        if instructions_after != instructions_before {
+            self.warn(
+                "assembly-hook",
+                format!(
+                    "The assembly hook has changed the instruction count from {} to {}",
+                    instructions_before, instructions_after
+                ),
+                None,
+            )?;
         }
 
-        self.builder.build_return(value);
+        self.dump(DumpFlag::Assembly, || assembly.clone());
+
+        Ok(assembly)
     }
 
     ///
-    /// Builds an unreachable.
+    /// Removes the unreferenced private functions from the module and the function table.
     ///
-    /// Checks if there are no other terminators in the block.
+    /// Only returns `true` if any function has been removed.
     ///
-    pub fn build_unreachable(&self) {
-        if self.basic_block().get_terminator().is_some() {
-            return;
+    fn eliminate_dead_functions(&mut self) -> bool {
+        if !self.optimizer.run_dead_function_elimination(&self.module) {
+            return false;
         }
 
-        self.builder.build_unreachable();
+        let mut removed_functions: Vec<String> = self
+            .functions
+            .keys()
+            .filter(|name| self.module.get_function(name.as_str()).is_none())
+            .cloned()
+            .collect();
+        removed_functions.sort();
+        for name in removed_functions.iter() {
+            self.functions.remove(name.as_str());
+        }
+        let is_removed = !removed_functions.is_empty();
+        self.removed_functions.extend(removed_functions);
+        is_removed
     }
 
     ///
-    /// Builds an exception catching block sequence.
+    /// Eliminates the storage writes to the constant slots, which are overwritten later in the
+    /// same basic block with no storage reads or other calls in between.
     ///
-    pub fn build_catch_block(&self, handles_long_return: bool) {
-        self.set_basic_block(self.function().catch_block);
+    /// The writes are only combined if both the slot and the external storage flag are constant
+    /// and equal, since the own and external storage writes to the same slot are independent.
+    /// A read of a constant slot keeps the pending writes to the slot in both storages, since the
+    /// external storage may be the own one.
+    ///
+    /// The calls except for the storage writes and the target-independent LLVM intrinsics are
+    /// treated as barriers, since they may read the storage or throw.
+    ///
+    fn combine_storage_writes(&mut self) -> bool {
+        let mut dead_writes = Vec::new();
 
-        let landing_pad_type = self.structure_type(vec![
-            self.integer_type(compiler_common::BITLENGTH_BYTE)
-                .ptr_type(AddressSpace::Stack.into())
-                .as_basic_type_enum(),
-            self.integer_type(compiler_common::BITLENGTH_X32)
-                .as_basic_type_enum(),
-        ]);
-        self.builder.build_landing_pad(
-            landing_pad_type,
-            self.runtime.personality,
-            vec![self
-                .integer_type(compiler_common::BITLENGTH_BYTE)
-                .ptr_type(AddressSpace::Stack.into())
-                .const_zero()
-                .as_basic_value_enum()],
-            "landing",
-        );
+        for (name, function) in self.functions.iter() {
+            for block in function.value.get_basic_blocks() {
+                let mut pending: HashMap<
+                    (String, String),
+                    inkwell::values::InstructionValue<'ctx>,
+                > = HashMap::new();
 
-        if handles_long_return {
-            let no_long_return_block = self.append_basic_block("no_long_return_block");
-            let long_return_flag_pointer = self.access_memory(
-                self.field_const(
-                    (compiler_common::ABI_MEMORY_OFFSET_LONG_RETURN * compiler_common::SIZE_FIELD)
-                        as u64,
-                ),
-                AddressSpace::Heap,
-                "long_return_flag_pointer",
-            );
-            let long_return_flag = self.build_load(long_return_flag_pointer, "long_return_flag");
-            let is_long_return_flag_set = self.builder.build_int_compare(
-                inkwell::IntPredicate::EQ,
-                long_return_flag.into_int_value(),
-                self.field_const(1),
-                "is_long_return_flag_set",
-            );
-            self.build_conditional_branch(
-                is_long_return_flag_set,
-                self.function().return_block,
-                no_long_return_block,
-            );
-            self.set_basic_block(no_long_return_block);
+                let mut instruction = block.get_first_instruction();
+                while let Some(current) = instruction {
+                    instruction = current.get_next_instruction();
+
+                    if !matches!(
+                        current.get_opcode(),
+                        inkwell::values::InstructionOpcode::Call
+                            | inkwell::values::InstructionOpcode::Invoke
+                    ) {
+                        continue;
+                    }
+
+                    let callee_name = GasReport::callee_name(current).unwrap_or_default();
+                    if callee_name == IntrinsicFunction::StorageStore.name() {
+                        let key = match (
+                            Self::constant_operand(current, 1),
+                            Self::constant_operand(current, 2),
+                        ) {
+                            (Some(slot), Some(is_external)) => (slot, is_external),
+                            _ => continue,
+                        };
+                        if let Some(overwritten) = pending.insert(key.clone(), current) {
+                            dead_writes.push((
+                                overwritten,
+                                EliminatedWrite::new(
+                                    name.to_owned(),
+                                    block.get_name().to_string_lossy().to_string(),
+                                    key.0,
+                                ),
+                            ));
+                        }
+                    } else if callee_name == IntrinsicFunction::StorageLoad.name() {
+                        match Self::constant_operand(current, 0) {
+                            Some(slot) => {
+                                pending.retain(|(pending_slot, _), _| *pending_slot != slot)
+                            }
+                            None => pending.clear(),
+                        }
+                    } else if !callee_name.starts_with("llvm.")
+                        || callee_name.starts_with("llvm.syncvm.")
+                    {
+                        pending.clear();
+                    }
+                }
+            }
         }
 
-        self.build_call(
-            self.runtime.cxa_throw,
-            vec![
-                self.integer_type(compiler_common::BITLENGTH_BYTE)
-                    .ptr_type(AddressSpace::Stack.into())
-                    .const_null()
-                    .as_basic_value_enum();
-                3
-            ]
-            .as_slice(),
-            compiler_common::LLVM_FUNCTION_CXA_THROW,
-        );
-        self.build_unreachable();
+        let is_modified = !dead_writes.is_empty();
+        for (instruction, eliminated_write) in dead_writes.into_iter() {
+            instruction.erase_from_basic_block();
+            self.eliminated_writes.push(eliminated_write);
+        }
+        is_modified
     }
 
     ///
-    /// Builds an error throwing block sequence.
+    /// Returns the storage intrinsic call operand at `index`, if it is a constant.
     ///
-    pub fn build_throw_block(&self, is_upper_level: bool) {
-        self.set_basic_block(self.function().throw_block);
-
-        if is_upper_level {
-            let no_long_return_block = self.append_basic_block("no_long_return_block");
-            let long_return_flag_pointer = self.access_memory(
-                self.field_const(
-                    (compiler_common::ABI_MEMORY_OFFSET_LONG_RETURN * compiler_common::SIZE_FIELD)
-                        as u64,
-                ),
-                AddressSpace::Heap,
-                "long_return_flag_pointer",
-            );
-            let long_return_flag = self.build_load(long_return_flag_pointer, "long_return_flag");
-            let is_long_return_flag_set = self.builder.build_int_compare(
-                inkwell::IntPredicate::EQ,
-                long_return_flag.into_int_value(),
-                self.field_const(1),
-                "is_long_return_flag_set",
-            );
-            self.build_conditional_branch(
-                is_long_return_flag_set,
-                self.function().return_block,
-                no_long_return_block,
-            );
-            self.set_basic_block(no_long_return_block);
+    fn constant_operand(
+        instruction: inkwell::values::InstructionValue<'ctx>,
+        index: u32,
+    ) -> Option<String> {
+        match instruction
+            .get_operand(index)
+            .and_then(|operand| operand.left())
+        {
+            Some(inkwell::values::BasicValueEnum::IntValue(operand)) if operand.is_const() => {
+                Some(operand.print_to_string().to_string())
+            }
+            _ => None,
         }
+    }
 
-        self.build_call(
-            self.runtime.cxa_throw,
-            vec![
-                self.integer_type(compiler_common::BITLENGTH_BYTE)
-                    .ptr_type(AddressSpace::Stack.into())
-                    .const_null()
-                    .as_basic_value_enum();
-                3
-            ]
-            .as_slice(),
-            compiler_common::LLVM_FUNCTION_CXA_THROW,
-        );
-        self.build_unreachable();
+    ///
+    /// Collects the auxiliary data of the current module.
+    ///
+    /// Should be only run when the module has been optimized.
+    ///
+    pub fn artifact(&self) -> Artifact {
+        Artifact::new(
+            self.size_report(),
+            self.warnings.clone(),
+            self.statistics.clone(),
+        )
+        .with_submodules(self.linked_submodules.clone())
+        .with_eliminated_writes(self.eliminated_writes.clone())
+        .with_metadata(self.module_metadata.clone())
+        .with_selectors(&self.selectors)
+        .with_dumps(self.dumps.borrow().clone())
     }
 
     ///
-    /// Reads the data size from the specified memory.
+    /// Registers the dispatched function `name` with the 4-byte `selector` for the artifact
+    /// selector table.
     ///
-    pub fn read_header(&self, address_space: AddressSpace) -> inkwell::values::IntValue<'ctx> {
-        let header_pointer = self.access_memory(
-            self.field_const(
-                (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
+    /// Registering the same pair again is allowed, whereas a selector clash is an error.
+    ///
+    pub fn register_selector(&mut self, selector: u32, name: &str) -> anyhow::Result<()> {
+        match self.selectors.get(&selector) {
+            Some(existing) if existing != name => anyhow::bail!(
+                "The selector 0x{:08x} of `{}` clashes with `{}`",
+                selector,
+                name,
+                existing
             ),
-            address_space,
-            "header_pointer",
-        );
-        self.build_load(header_pointer, "header_value")
-            .into_int_value()
+            Some(_) => Ok(()),
+            None => {
+                self.selectors.insert(selector, name.to_owned());
+                Ok(())
+            }
+        }
     }
 
     ///
-    /// Writes the data size to the specified memory.
+    /// Returns the 4-byte selector of the function `signature`, e.g. `transfer(address,uint256)`.
     ///
-    pub fn write_header(
-        &self,
-        header: inkwell::values::IntValue<'ctx>,
-        address_space: AddressSpace,
-    ) {
-        let header_pointer = self.access_memory(
-            self.field_const(
-                (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-            ),
-            address_space,
-            "header_pointer",
-        );
-        self.build_store(header_pointer, header);
+    pub fn selector(signature: &str) -> u32 {
+        let hash = compiler_common::keccak256(signature.as_bytes());
+        let hash = hash.strip_prefix("0x").unwrap_or(hash.as_str());
+        u32::from_str_radix(&hash[..compiler_common::SIZE_X32 * 2], 16).expect("Always valid")
     }
 
     ///
-    /// Writes the error data to the parent memory.
+    /// Adds the module-level named metadata `key` with string `values`, e.g. the compiler
+    /// version, frontend name, or settings hash.
     ///
-    pub fn write_error(&self, message: &'static str) {
-        self.write_header(
-            self.field_const(compiler_common::SIZE_X32 as u64),
-            AddressSpace::Parent,
-        );
+    /// The metadata is emitted into the LLVM IR and preserved into the artifact. Adding the same
+    /// key again appends another node to it.
+    ///
+    pub fn add_module_metadata(&mut self, key: &str, values: &[&str]) -> anyhow::Result<()> {
+        let strings: Vec<inkwell::values::BasicMetadataValueEnum> = values
+            .iter()
+            .map(|value| self.llvm.metadata_string(value).into())
+            .collect();
+        let node = self.llvm.metadata_node(strings.as_slice());
+        self.module
+            .add_global_metadata(key, &node)
+            .map_err(|error| anyhow::anyhow!("Module metadata `{}` error: {}", key, error))?;
 
-        let error_hash = compiler_common::keccak256(message.as_bytes());
-        let error_code = self.field_const_str(error_hash.as_str());
-        let error_code_shifted = self.builder.build_left_shift(
-            error_code,
-            self.field_const(
-                (compiler_common::BITLENGTH_BYTE
-                    * (compiler_common::SIZE_FIELD - compiler_common::SIZE_X32))
-                    as u64,
-            ),
-            "error_code_shifted",
-        );
-        let parent_error_code_pointer = self.access_memory(
-            self.field_const(
-                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-            ),
-            AddressSpace::Parent,
-            "parent_error_code_pointer",
-        );
-        self.build_store(parent_error_code_pointer, error_code_shifted);
+        self.module_metadata
+            .entry(key.to_owned())
+            .or_default()
+            .extend(values.iter().map(|value| value.to_string()));
+        Ok(())
     }
 
     ///
-    /// Returns a field type constant.
+    /// Records the function `input` for the incremental compilation.
     ///
-    pub fn field_const(&self, value: u64) -> inkwell::values::IntValue<'ctx> {
-        self.field_type().const_int(value, false)
+    /// The `name` is the declared function name, qualified with the mangling scope if it is set.
+    /// All the function inputs and the context settings must be set before calling
+    /// `restore_from_cache`.
+    ///
+    pub fn set_function_input(&mut self, name: &str, input: &[u8]) {
+        self.function_input_hashes
+            .insert(name.to_owned(), Cache::hash(input));
     }
 
     ///
-    /// Returns a field type constant from a decimal or hexadecimal string.
+    /// Creates an empty submodule with the same target settings as the main module.
     ///
-    pub fn field_const_str(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
-        match value.strip_prefix("0x") {
-            Some(hexadecimal) => self.field_const_str_hex(hexadecimal),
-            None => self.field_const_str_hex(value),
+    pub fn create_submodule(&mut self, name: &str) -> anyhow::Result<()> {
+        let is_current = matches!(self.parked_module, Some((ref current, _)) if current == name);
+        if is_current || self.submodules.contains_key(name) {
+            anyhow::bail!("The submodule `{}` already exists", name);
         }
+
+        let submodule = Submodule::new(self.llvm, &self.module, name);
+        self.submodules.insert(name.to_owned(), submodule);
+        Ok(())
     }
 
     ///
-    /// Returns a field type constant from a hexadecimal string.
+    /// Makes the submodule `name` current, so the functions are emitted into it.
+    ///
+    /// The submodules cannot be nested, so the main module must be current.
     ///
-    pub fn field_const_str_dec(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
-        self.field_type()
-            .const_int_from_string(value, inkwell::types::StringRadix::Decimal)
-            .unwrap_or_else(|| panic!("Invalid string constant `{}`", value))
+    pub fn enter_submodule(&mut self, name: &str) -> anyhow::Result<()> {
+        if let Some((ref current, _)) = self.parked_module {
+            anyhow::bail!(
+                "Cannot enter the submodule `{}` while in the submodule `{}`",
+                name,
+                current
+            );
+        }
+        let mut submodule = self
+            .submodules
+            .remove(name)
+            .ok_or_else(|| anyhow::anyhow!("The submodule `{}` does not exist", name))?;
+
+        std::mem::swap(&mut self.module, &mut submodule.module);
+        std::mem::swap(&mut self.runtime, &mut submodule.runtime);
+        self.parked_module = Some((name.to_owned(), submodule));
+        self.function = None;
+        Ok(())
     }
 
     ///
-    /// Returns a field type constant from a hexadecimal string.
+    /// Makes the main module current again.
     ///
-    pub fn field_const_str_hex(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
-        self.field_type()
-            .const_int_from_string(
-                value.strip_prefix("0x").unwrap_or(value),
-                inkwell::types::StringRadix::Hexadecimal,
-            )
-            .unwrap_or_else(|| panic!("Invalid string constant `{}`", value))
+    pub fn exit_submodule(&mut self) -> anyhow::Result<()> {
+        let (name, mut submodule) = self
+            .parked_module
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("The context is not in a submodule"))?;
+
+        std::mem::swap(&mut self.module, &mut submodule.module);
+        std::mem::swap(&mut self.runtime, &mut submodule.runtime);
+        self.submodules.insert(name, submodule);
+        self.function = None;
+        Ok(())
     }
 
     ///
-    /// Returns the void type.
+    /// Returns the function `name` declared in the current module.
     ///
-    pub fn void_type(&self) -> inkwell::types::VoidType<'ctx> {
-        self.llvm.void_type()
+    /// If the function has been defined in another module, its linkage is made external, and
+    /// a declaration is added to the current module, which is resolved when the submodules
+    /// are linked.
+    ///
+    pub fn import_function(
+        &mut self,
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>> {
+        let value = self
+            .get_function(name)
+            .map(|function| function.value)
+            .ok_or_else(|| anyhow::anyhow!("Function `{}` does not exist", name))?;
+        let symbol_name = value.get_name().to_string_lossy().to_string();
+
+        match self.module.get_function(symbol_name.as_str()) {
+            Some(local) => Ok(local),
+            None => {
+                value.set_linkage(inkwell::module::Linkage::External);
+                Ok(self.module.add_function(
+                    symbol_name.as_str(),
+                    value.get_type(),
+                    Some(inkwell::module::Linkage::External),
+                ))
+            }
+        }
     }
 
     ///
-    /// Returns the integer type of the specified bitlength.
+    /// Declares the function `name` defined in another object.
     ///
-    pub fn integer_type(&self, bitlength: usize) -> inkwell::types::IntType<'ctx> {
-        self.llvm.custom_width_int_type(bitlength as u32)
+    /// Only the external declaration is added to the module, without the basic blocks and the
+    /// current function wrapper. If the function is already declared with the same type, the
+    /// existing declaration is returned.
+    ///
+    pub fn declare_external_function(
+        &mut self,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+    ) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>> {
+        if let Some(value) = self.module.get_function(name) {
+            if value.get_type() != r#type {
+                anyhow::bail!(
+                    "The function `{}` is already declared with another signature",
+                    name
+                );
+            }
+            return Ok(value);
+        }
+
+        let value =
+            self.module
+                .add_function(name, r#type, Some(inkwell::module::Linkage::External));
+        self.set_pointer_alignments(value);
+        Ok(value)
+    }
+
+    ///
+    /// Links all the submodules into the main module, so they are optimized and emitted as a
+    /// single artifact.
+    ///
+    pub fn link_submodules(&mut self) -> anyhow::Result<()> {
+        if let Some((ref current, _)) = self.parked_module {
+            anyhow::bail!(
+                "Cannot link the submodules while in the submodule `{}`",
+                current
+            );
+        }
+
+        let linked_functions = self.linked_function_layouts();
+        for (name, submodule) in std::mem::take(&mut self.submodules).into_iter() {
+            self.module
+                .link_in_module(submodule.module)
+                .map_err(|error| {
+                    anyhow::anyhow!("The submodule `{}` linking error: {}", name, error)
+                })?;
+            self.linked_submodules.push(name);
+        }
+        self.rebind_linked_functions(linked_functions);
+        Ok(())
+    }
+
+    ///
+    /// Returns the layouts of the functions defined outside of the main module, keyed by name.
+    ///
+    /// The layout is the list of the function blocks, and the positions of the entry, throw,
+    /// catch, and return blocks therein.
+    ///
+    fn linked_function_layouts(
+        &self,
+    ) -> HashMap<
+        String,
+        (
+            Vec<inkwell::basic_block::BasicBlock<'ctx>>,
+            [Option<usize>; 4],
+        ),
+    > {
+        self.functions
+            .iter()
+            .filter(|(name, function)| {
+                self.module.get_function(name.as_str()) != Some(function.value)
+            })
+            .map(|(name, function)| {
+                let blocks = function.value.get_basic_blocks();
+                let position = |block: inkwell::basic_block::BasicBlock<'ctx>| {
+                    blocks.iter().position(|current| *current == block)
+                };
+                let positions = [
+                    position(function.entry_block),
+                    position(function.throw_block),
+                    position(function.catch_block),
+                    position(function.return_block),
+                ];
+                (name.to_owned(), (blocks, positions))
+            })
+            .collect()
+    }
+
+    ///
+    /// Rebinds the functions of the linked submodules to their definitions in the main module.
+    ///
+    /// Linking consumes the submodules, so the function values and blocks of their wrappers are
+    /// freed. The wrappers are looked up again by name, and the ones which no longer exist are
+    /// dropped. The stack and EVM data of the rebound functions are cleared, since the linked
+    /// functions are complete and their values cannot be recovered.
+    ///
+    fn rebind_linked_functions(
+        &mut self,
+        layouts: HashMap<
+            String,
+            (
+                Vec<inkwell::basic_block::BasicBlock<'ctx>>,
+                [Option<usize>; 4],
+            ),
+        >,
+    ) {
+        for (name, (blocks, positions)) in layouts.into_iter() {
+            self.cold_blocks.retain(|block| !blocks.contains(block));
+
+            let value = match self.module.get_function(name.as_str()) {
+                Some(value) => value,
+                None => {
+                    self.functions.remove(name.as_str());
+                    continue;
+                }
+            };
+            let linked_blocks = value.get_basic_blocks();
+            let rebound = positions
+                .map(|position| position.and_then(|position| linked_blocks.get(position).copied()));
+            let (entry_block, throw_block, catch_block, return_block) =
+                match (rebound[0], rebound[1], rebound[2], rebound[3]) {
+                    (Some(entry), Some(throw), Some(catch), Some(r#return)) => {
+                        (entry, throw, catch, r#return)
+                    }
+                    _ => {
+                        self.functions.remove(name.as_str());
+                        continue;
+                    }
+                };
+
+            self.cold_blocks.push(throw_block);
+            self.cold_blocks.push(catch_block);
+            if let Some(function) = self.functions.get_mut(name.as_str()) {
+                function.value = value;
+                function.entry_block = entry_block;
+                function.throw_block = throw_block;
+                function.catch_block = catch_block;
+                function.return_block = return_block;
+                function.stack.clear();
+                function.evm_data = None;
+            }
+        }
+        self.function = None;
+    }
+
+    ///
+    /// Restores the optimized module from the `cache`, if neither the function inputs nor the
+    /// context settings have changed since it was captured.
+    ///
+    /// If `true` is returned, the translation and optimization must be skipped. The module-level
+    /// state registered during the translation, e.g. the selectors or the linker symbols, is not
+    /// restored.
+    ///
+    /// # Errors
+    /// If the cached bitcode is invalid.
+    ///
+    pub fn restore_from_cache(&mut self, cache: &Cache) -> anyhow::Result<bool> {
+        if !cache.is_valid(self.cache_key().as_str()) {
+            return Ok(false);
+        }
+
+        let buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+            cache.bitcode.as_slice(),
+            self.module.get_name().to_string_lossy().as_ref(),
+        );
+        let module = inkwell::module::Module::parse_bitcode_from_buffer(&buffer, self.llvm)
+            .map_err(|error| anyhow::anyhow!("The cached module is invalid: {}", error))?;
+        let replaced = std::mem::replace(&mut self.module, module);
+        self.rebuild_module_state();
+        drop(replaced);
+
+        Ok(true)
+    }
+
+    ///
+    /// Rebuilds the state derived from the current module after it has been replaced.
+    ///
+    /// The runtime functions are looked up in the new module, and the optimizer is recreated for
+    /// it. The function wrappers, blocks, and values referring to the old module are dropped,
+    /// since the replaced functions are not translated again.
+    ///
+    fn rebuild_module_state(&mut self) {
+        self.builder.clear_insertion_position();
+        self.runtime = Runtime::new(self.llvm, &self.module);
+        self.optimizer = Optimizer::new_with_settings(
+            &self.module,
+            self.optimizer.level_middle(),
+            self.optimizer.level_back(),
+            self.optimizer.settings().to_owned(),
+        );
+
+        self.functions.clear();
+        self.function = None;
+        self.loop_stack.clear();
+        self.cold_blocks.clear();
+        self.cached_context_values.clear();
+        self.invalidated_context_values.clear();
+        if let Some(evm_data) = self.evm_data.as_mut() {
+            evm_data.stack.clear();
+        }
+    }
+
+    ///
+    /// Returns the incremental compilation cache captured after the optimization.
+    ///
+    /// Is only captured if any function inputs have been recorded.
+    ///
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    ///
+    /// Computes the incremental compilation cache key of the recorded function inputs and the
+    /// context settings affecting the generated code.
+    ///
+    fn cache_key(&self) -> String {
+        let settings = format!(
+            "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {} {} {} {} {} {:?}",
+            self.optimizer.level_middle(),
+            self.optimizer.level_back(),
+            self.optimizer.settings(),
+            self.code_type,
+            self.evm_version,
+            self.naming_policy,
+            self.calldata_mode,
+            self.name_prefix,
+            self.mangling_scope,
+            self.debug_tracer,
+            self.is_system_mode,
+            self.is_memory_safety_enabled,
+            self.is_shared_throw_enabled,
+            self.is_evm_op_annotation_enabled,
+            self.is_storage_journal_enabled,
+            self.capabilities,
+        );
+        Cache::key(&self.function_input_hashes, settings.as_str())
+    }
+
+    ///
+    /// Verifies the current module.
+    ///
+    /// Also checks the alignment policy, whose violations would otherwise crash the backend.
+    ///
+    /// Collects the verified module dump if the corresponding flag is set.
+    ///
+    /// # Panics
+    /// If verification fails.
+    ///
+    pub fn verify(&self) -> anyhow::Result<()> {
+        self.module()
+            .verify()
+            .map_err(|error| anyhow::anyhow!(error.to_string()))?;
+        AlignmentVerifier::new(self.module())
+            .check(self.module.get_name().to_string_lossy().as_ref())?;
+
+        self.dump(DumpFlag::VerifiedLLVM, || {
+            self.module.print_to_string().to_string()
+        });
+
+        Ok(())
+    }
+
+    ///
+    /// Estimates the gas costs of the current module using the target `cost_table`.
+    ///
+    /// Should be only run when the entire module has been translated.
+    ///
+    pub fn gas_report(&self, cost_table: &CostTable) -> GasReport {
+        GasReport::new(self.module(), cost_table)
+    }
+
+    ///
+    /// Compiles a contract dependency deploy code, if the dependency manager is set.
+    ///
+    /// Returns the dependency bytecode hash.
+    ///
+    pub fn compile_dependency(&mut self, name: &str) -> anyhow::Result<String> {
+        let request = self.dependency_request(name, CodeType::Deploy);
+        self.compile_dependency_request(request)
+            .map(|output| output.hash)
+    }
+
+    ///
+    /// Compiles a contract dependency described by the `request`, if the dependency manager is set.
+    ///
+    pub fn compile_dependency_request(
+        &mut self,
+        request: DependencyRequest,
+    ) -> anyhow::Result<DependencyOutput> {
+        let parent_name = request.parent_name.clone();
+        let name = request.name.clone();
+        let output = self
+            .dependency_manager
+            .to_owned()
+            .ok_or_else(|| anyhow::anyhow!("The dependency manager is unset"))
+            .and_then(|manager| Dependency::compile_request(manager, request))?;
+
+        self.dump(DumpFlag::Dependencies, || {
+            format!(
+                "Contract `{}` compiled dependency `{}` with hash {}",
+                parent_name, name, output.hash
+            )
+        });
+
+        Ok(output)
+    }
+
+    ///
+    /// Creates a dependency request with the current module name and optimization settings.
+    ///
+    pub fn dependency_request(&self, name: &str, code_type: CodeType) -> DependencyRequest {
+        DependencyRequest::new(
+            name.to_owned(),
+            self.module
+                .get_name()
+                .to_str()
+                .expect("Always valid")
+                .to_owned(),
+            code_type,
+            self.optimizer.level_middle(),
+            self.optimizer.level_back(),
+            self.dump_flags.to_vec(),
+        )
+    }
+
+    ///
+    /// Links the dependency `name` bitcode into the module for the cross-object optimization.
+    ///
+    /// Is meant for the internalizable helpers, not for the deployable children. The dependency
+    /// definitions resolving the module declarations are internalized after linking, and the
+    /// rest of them are made private, so they do not clash with the module symbols. The module
+    /// passes are run once more if any dependencies have been linked.
+    ///
+    pub fn link_dependency(&mut self, name: &str) -> anyhow::Result<()> {
+        let request = self
+            .dependency_request(name, CodeType::Runtime)
+            .with_bitcode_requested();
+        let bitcode = self
+            .compile_dependency_request(request)?
+            .bitcode
+            .ok_or_else(|| anyhow::anyhow!("The dependency `{}` bitcode is not provided", name))?;
+
+        let buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+            bitcode.as_slice(),
+            name,
+        );
+        let module = self.llvm.create_module_from_ir(buffer).map_err(|error| {
+            anyhow::anyhow!("The dependency `{}` bitcode parsing error: {}", name, error)
+        })?;
+
+        let mut resolved = Vec::new();
+        let mut function = module.get_first_function();
+        while let Some(current) = function {
+            function = current.get_next_function();
+            if current.count_basic_blocks() == 0 {
+                continue;
+            }
+
+            let symbol_name = current.get_name().to_string_lossy().to_string();
+            match self.module.get_function(symbol_name.as_str()) {
+                Some(declaration) if declaration.count_basic_blocks() == 0 => {
+                    resolved.push(symbol_name)
+                }
+                _ => current.set_linkage(inkwell::module::Linkage::Private),
+            }
+        }
+
+        self.module.link_in_module(module).map_err(|error| {
+            anyhow::anyhow!("The dependency `{}` linking error: {}", name, error)
+        })?;
+        for symbol_name in resolved.into_iter() {
+            if let Some(function) = self.module.get_function(symbol_name.as_str()) {
+                function.set_linkage(inkwell::module::Linkage::Internal);
+            }
+        }
+
+        self.linked_dependencies.push(name.to_owned());
+        Ok(())
+    }
+
+    ///
+    /// Gets a deployed library address.
+    ///
+    pub fn resolve_library(&self, path: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        self.dependency_manager
+            .to_owned()
+            .ok_or_else(|| anyhow::anyhow!("The dependency manager is unset"))
+            .and_then(|manager| {
+                let address = Dependency::resolve_library(manager, path)?;
+
+                self.dump(DumpFlag::Dependencies, || {
+                    format!(
+                        "Contract `{}` resolved library `{}` to address {}",
+                        self.module.get_name().to_string_lossy(),
+                        path,
+                        address
+                    )
+                });
+
+                self.field_const_hex(address.as_str())
+            })
+    }
+
+    ///
+    /// Registers a linker symbol.
+    ///
+    /// Registering the same symbol twice is only allowed if the kinds match, and the value is
+    /// not changed once it is known.
+    ///
+    pub fn register_symbol(
+        &mut self,
+        name: &str,
+        kind: SymbolKind,
+        value: Option<String>,
+    ) -> anyhow::Result<()> {
+        match self.symbols.get_mut(name) {
+            Some(symbol) if symbol.kind != kind => anyhow::bail!(
+                "The linker symbol `{}` is already registered as a {}",
+                name,
+                symbol.kind
+            ),
+            Some(symbol) => match (symbol.value.as_ref(), value) {
+                (Some(existing), Some(value)) if existing != &value => anyhow::bail!(
+                    "The linker symbol `{}` is already resolved to {}",
+                    name,
+                    existing
+                ),
+                (None, value) => symbol.value = value,
+                _ => {}
+            },
+            None => {
+                self.symbols
+                    .insert(name.to_owned(), Symbol::new(kind, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Resolves a linker symbol.
+    ///
+    /// Unregistered symbols are treated as library addresses. The constants unknown to the
+    /// dependency manager are loaded from external globals, which are resolved at link time.
+    ///
+    pub fn resolve_symbol(&self, name: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let symbol = match self.symbols.get(name) {
+            Some(symbol) => symbol,
+            None => return self.resolve_library(name),
+        };
+        if let Some(ref value) = symbol.value {
+            return self.field_const_hex(value.as_str());
+        }
+
+        match symbol.kind {
+            SymbolKind::LibraryAddress => self.resolve_library(name),
+            SymbolKind::Constant => {
+                let value = match self.dependency_manager.to_owned() {
+                    Some(manager) => Dependency::resolve_symbol(manager, name)?,
+                    None => None,
+                };
+                if let Some(value) = value {
+                    return self.field_const_hex(value.as_str());
+                }
+
+                let global_name = format!("{}{}", Symbol::GLOBAL_PREFIX, name);
+                let global = match self.module.get_global(global_name.as_str()) {
+                    Some(global) => global,
+                    None => {
+                        let global = self.module.add_global(
+                            self.field_type(),
+                            Some(AddressSpace::Stack.into()),
+                            global_name.as_str(),
+                        );
+                        global.set_linkage(inkwell::module::Linkage::External);
+                        global.set_constant(true);
+                        global
+                    }
+                };
+                Ok(self
+                    .build_load(global.as_pointer_value(), name)
+                    .into_int_value())
+            }
+        }
+    }
+
+    ///
+    /// Sets the function name mangling scope.
+    ///
+    /// If set, the non-external functions are qualified with the scope, so the functions with
+    /// the same name may be declared in different scopes, e.g. Yul objects.
+    ///
+    pub fn set_mangling_scope(&mut self, scope: Option<String>) {
+        self.mangling_scope = scope;
+    }
+
+    ///
+    /// Qualifies the function `name` with the current mangling scope.
+    ///
+    pub fn mangle_name(&self, name: &str) -> String {
+        match self.mangling_scope {
+            Some(ref scope) => format!("{}{}{}", scope, Self::MANGLING_SEPARATOR, name),
+            None => name.to_owned(),
+        }
+    }
+
+    ///
+    /// Sets the value naming policy.
+    ///
+    pub fn set_naming_policy(&mut self, policy: NamingPolicy) {
+        self.naming_policy = policy;
+    }
+
+    ///
+    /// Sets the calldata access mode.
+    ///
+    pub fn set_calldata_mode(&mut self, mode: CalldataMode) {
+        self.calldata_mode = mode;
+    }
+
+    ///
+    /// Returns the calldata access mode.
+    ///
+    pub fn calldata_mode(&self) -> CalldataMode {
+        self.calldata_mode
+    }
+    ///
+    /// Sets the prefix of the value names, so the frontends can namespace their temporaries.
+    ///
+    pub fn set_name_prefix(&mut self, prefix: Option<String>) {
+        self.name_prefix = prefix;
+    }
+
+    ///
+    /// Returns the value `name` according to the naming policy and prefix.
+    ///
+    /// The stripped names are empty, so LLVM numbers the values.
+    ///
+    pub fn value_name(&self, name: &str) -> String {
+        match (self.naming_policy, self.name_prefix.as_deref()) {
+            (NamingPolicy::Stripped, _) => String::new(),
+            (NamingPolicy::Verbose, Some(prefix)) if !name.is_empty() => {
+                format!("{}_{}", prefix, name)
+            }
+            (NamingPolicy::Verbose, _) => name.to_owned(),
+        }
+    }
+
+    ///
+    /// Strips the mangling scope from the function `name`.
+    ///
+    pub fn demangle_name(name: &str) -> &str {
+        name.rsplit_once(Self::MANGLING_SEPARATOR)
+            .map(|(_scope, name)| name)
+            .unwrap_or(name)
+    }
+
+    ///
+    /// Declares all the `items` before any of them is defined.
+    ///
+    /// Used as the first phase of the two-phase translation, so the items may reference each
+    /// other regardless of the order, e.g. the mutually recursive functions.
+    ///
+    pub fn declare_all<T>(&mut self, items: &mut [T]) -> anyhow::Result<()>
+    where
+        T: WriteLLVM<D>,
+    {
+        for item in items.iter_mut() {
+            item.declare(self)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Declares all the `items` and then translates them into LLVM IR.
+    ///
+    /// Heterogeneous items can be passed as `Box<dyn WriteLLVMBoxed<D>>`.
+    ///
+    pub fn define_all<T>(&mut self, mut items: Vec<T>) -> anyhow::Result<()>
+    where
+        T: WriteLLVM<D>,
+    {
+        self.declare_all(items.as_mut_slice())?;
+        for item in items.into_iter() {
+            item.into_llvm(self)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the declared function, looking it up in the current mangling scope first.
+    ///
+    pub fn get_function(&self, name: &str) -> Option<&Function<'ctx>> {
+        self.functions
+            .get(self.mangle_name(name).as_str())
+            .or_else(|| self.functions.get(name))
+    }
+
+    ///
+    /// Appends a function to the current module.
+    ///
+    /// The non-external function names are mangled if the mangling scope is set.
+    ///
+    /// # Errors
+    /// If a function with the same name has already been declared.
+    ///
+    pub fn add_function(
+        &mut self,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+        linkage: Option<inkwell::module::Linkage>,
+    ) -> anyhow::Result<()> {
+        let name = match linkage {
+            Some(inkwell::module::Linkage::External) => name.to_owned(),
+            _ => self.mangle_name(name),
+        };
+        let name = name.as_str();
+        if self.functions.contains_key(name) || self.module.get_function(name).is_some() {
+            anyhow::bail!("The function `{}` is already declared", name);
+        }
+
+        let value = self.module().add_function(name, r#type, linkage);
+        self.set_pointer_alignments(value);
+
+        value.set_personality_function(self.runtime.personality);
+
+        let entry_block = self
+            .llvm
+            .append_basic_block(value, self.value_name("entry").as_str());
+        let throw_block = self
+            .llvm
+            .append_basic_block(value, self.value_name("throw").as_str());
+        let catch_block = self
+            .llvm
+            .append_basic_block(value, self.value_name("catch").as_str());
+        let return_block = self
+            .llvm
+            .append_basic_block(value, self.value_name("return").as_str());
+
+        self.cold_blocks.push(throw_block);
+        self.cold_blocks.push(catch_block);
+
+        let function = Function::new(
+            name.to_owned(),
+            value,
+            entry_block,
+            throw_block,
+            catch_block,
+            return_block,
+            None,
+        );
+        self.functions.insert(name.to_string(), function);
+
+        Ok(())
+    }
+
+    ///
+    /// Marks the declared function `name` as never unwinding.
+    ///
+    /// The function gets the `nounwind` attribute, and the invokes of it are emitted as plain
+    /// calls, so the unused catch blocks of the callers are removed. The invokes emitted before
+    /// the function is marked are kept.
+    ///
+    /// The unwinding check only sees the code emitted so far, so the function body must be
+    /// translated, that is, its return block must be terminated.
+    ///
+    /// # Errors
+    /// If the function has not been declared, its body has not been translated yet, or its throw
+    /// or catch block is referenced, i.e. the function can unwind.
+    ///
+    pub fn set_function_nothrow(&mut self, name: &str) -> anyhow::Result<()> {
+        let mangled_name = self.mangle_name(name);
+        let function = match self.functions.get_mut(mangled_name.as_str()) {
+            Some(function) => function,
+            None => self
+                .functions
+                .get_mut(name)
+                .ok_or_else(|| anyhow::anyhow!("The function `{}` is not declared", name))?,
+        };
+        if function.return_block.get_terminator().is_none() {
+            anyhow::bail!(
+                "The function `{}` body has not been translated yet, so it cannot be checked for unwinding",
+                name
+            );
+        }
+        if function.throw_block.get_first_use().is_some()
+            || function.catch_block.get_first_use().is_some()
+        {
+            anyhow::bail!(
+                "The function `{}` can unwind, so it cannot be marked as never unwinding",
+                name
+            );
+        }
+        function.is_nothrow = true;
+        let (function_name, value) = (function.name.clone(), function.value);
+
+        value.add_attribute(
+            inkwell::attributes::AttributeLoc::Function,
+            self.llvm.create_enum_attribute(
+                inkwell::attributes::Attribute::get_named_enum_kind_id("nounwind"),
+                0,
+            ),
+        );
+        if let Some(function) = self.function.as_mut() {
+            if function.name == function_name {
+                function.is_nothrow = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Appends a function with the EVM data to the current module.
+    ///
+    /// # Errors
+    /// If a function with the same name has already been declared.
+    ///
+    pub fn add_function_evm(
+        &mut self,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+        linkage: Option<inkwell::module::Linkage>,
+        evm_data: FunctionEVMData<'ctx>,
+    ) -> anyhow::Result<()> {
+        self.add_function(name, r#type, linkage)?;
+        let name = match linkage {
+            Some(inkwell::module::Linkage::External) => name.to_owned(),
+            _ => self.mangle_name(name),
+        };
+        self.functions
+            .get_mut(name.as_str())
+            .expect("Always exists")
+            .evm_data = Some(evm_data);
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the current function.
+    ///
+    /// # Panics
+    /// If there is no current function. See `try_function` for the fallible variant.
+    ///
+    pub fn function(&self) -> &Function<'ctx> {
+        self.function.as_ref().expect("Must be declared before use")
+    }
+
+    ///
+    /// Returns the current function as a mutable reference.
+    ///
+    /// # Panics
+    /// If there is no current function. See `try_function_mut` for the fallible variant.
+    ///
+    pub fn function_mut(&mut self) -> &mut Function<'ctx> {
+        self.function.as_mut().expect("Must be declared before use")
+    }
+
+    ///
+    /// Returns the current function, or an error if there is none.
+    ///
+    pub fn try_function(&self) -> anyhow::Result<&Function<'ctx>> {
+        self.function
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("There is no current function in {}", self.location()))
+    }
+
+    ///
+    /// Returns the current function as a mutable reference, or an error if there is none.
+    ///
+    pub fn try_function_mut(&mut self) -> anyhow::Result<&mut Function<'ctx>> {
+        let location = self.location();
+        self.function
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("There is no current function in {}", location))
+    }
+
+    ///
+    /// Sets the current function.
+    ///
+    /// # Panics
+    /// If the function with `name` does not exist.
+    ///
+    pub fn set_function(&mut self, function: Function<'ctx>) {
+        self.function = Some(function);
+    }
+
+    ///
+    /// Checkpoints the code generation state for speculative translation.
+    ///
+    /// The insertion point, the current function, the declared functions, and the loop stack
+    /// are saved, along with the module functions, basic blocks, and their last instructions.
+    ///
+    pub fn snapshot(&self) -> Snapshot<'ctx> {
+        Snapshot::new(
+            self.function.clone(),
+            self.builder.get_insert_block(),
+            self.loop_stack.clone(),
+            self.functions.clone(),
+            &self.module,
+        )
+    }
+
+    ///
+    /// Rolls the code generation state back to the `snapshot`.
+    ///
+    /// The instructions appended to the existing basic blocks are erased, along with the new
+    /// basic blocks and functions. The uses remaining in the instruction cycles through the phi
+    /// nodes are replaced with poison before erasing.
+    ///
+    /// The following cannot be rolled back: the stack allocations hoisted into the entry blocks,
+    /// which are unused and later removed by the optimizer, the globals, the intrinsic
+    /// declarations, the constant pool, the symbols, the warnings, the module metadata, the
+    /// selector table, the submodules, and the linked dependencies.
+    ///
+    pub fn restore(&mut self, snapshot: Snapshot<'ctx>) {
+        let mut instructions = Vec::new();
+        let mut new_blocks = Vec::new();
+        let mut new_functions = Vec::new();
+
+        for (block, last_instruction) in snapshot.blocks.iter() {
+            let mut instruction = match last_instruction {
+                Some(last_instruction) => last_instruction.get_next_instruction(),
+                None => block.get_first_instruction(),
+            };
+            while let Some(current) = instruction {
+                instruction = current.get_next_instruction();
+                instructions.push(current);
+            }
+        }
+
+        let mut function = self.module.get_first_function();
+        while let Some(current) = function {
+            function = current.get_next_function();
+            if !snapshot.module_functions.contains(&current) {
+                new_functions.push(current);
+                continue;
+            }
+
+            for block in current.get_basic_blocks() {
+                if snapshot
+                    .blocks
+                    .iter()
+                    .any(|(snapshot_block, _)| *snapshot_block == block)
+                {
+                    continue;
+                }
+
+                let mut instruction = block.get_first_instruction();
+                while let Some(current) = instruction {
+                    instruction = current.get_next_instruction();
+                    instructions.push(current);
+                }
+                new_blocks.push(block);
+            }
+        }
+
+        loop {
+            let count = instructions.len();
+            instructions.retain(|instruction| {
+                if instruction.get_first_use().is_some() {
+                    return true;
+                }
+                instruction.erase_from_basic_block();
+                false
+            });
+            if instructions.is_empty() || instructions.len() == count {
+                break;
+            }
+        }
+        for instruction in instructions.iter() {
+            Self::replace_uses_with_poison(*instruction);
+        }
+        for instruction in instructions.into_iter() {
+            instruction.erase_from_basic_block();
+        }
+
+        for function in new_functions.iter() {
+            new_blocks.extend(function.get_basic_blocks());
+        }
+        self.cold_blocks
+            .retain(|cold_block| !new_blocks.contains(cold_block));
+        for block in new_blocks.into_iter() {
+            unsafe {
+                let _ = block.delete();
+            }
+        }
+        for function in new_functions.into_iter() {
+            unsafe {
+                function.delete();
+            }
+        }
+
+        self.function = snapshot.function;
+        self.loop_stack = snapshot.loop_stack;
+        self.functions = snapshot.functions;
+        match snapshot.block {
+            Some(block) => self.set_basic_block(block),
+            None => self.builder.clear_insertion_position(),
+        }
+    }
+
+    ///
+    /// Replaces all uses of the `instruction` result with poison, so it can be erased.
+    ///
+    fn replace_uses_with_poison(instruction: inkwell::values::InstructionValue<'ctx>) {
+        match instruction.as_any_value_enum() {
+            inkwell::values::AnyValueEnum::IntValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            inkwell::values::AnyValueEnum::PointerValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            inkwell::values::AnyValueEnum::StructValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            inkwell::values::AnyValueEnum::ArrayValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            inkwell::values::AnyValueEnum::VectorValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            inkwell::values::AnyValueEnum::FloatValue(value) => {
+                value.replace_all_uses_with(value.get_type().get_poison())
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Sets the return entity for the current function.
+    ///
+    pub fn set_function_return(&mut self, r#return: FunctionReturn<'ctx>) {
+        let name = self.function().name.clone();
+
+        self.functions
+            .get_mut(name.as_str())
+            .expect("Always exists")
+            .set_return(r#return.clone());
+        self.function_mut().set_return(r#return);
+    }
+
+    ///
+    /// Returns the specified guard runtime function, building it on the first request.
+    ///
+    /// Preserves the current function and basic block.
+    ///
+    pub fn get_guard_function(
+        &mut self,
+        guard: Guard,
+    ) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>> {
+        self.get_or_build_runtime_function(guard.name(), |context| guard.build(context))
+    }
+
+    ///
+    /// Returns the runtime function `name`, building it with `builder` on the first request.
+    ///
+    /// The runtime functions restored from the incremental compilation cache along with their
+    /// callers are defined in the module without the wrappers, so they are reused as well.
+    ///
+    /// Preserves the current function and basic block.
+    ///
+    pub fn get_or_build_runtime_function<F>(
+        &mut self,
+        name: &str,
+        builder: F,
+    ) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>>
+    where
+        F: FnOnce(&mut Self) -> anyhow::Result<()>,
+    {
+        if let Some(function) = self.get_function(name) {
+            return Ok(function.value);
+        }
+        if let Some(value) = self
+            .module
+            .get_function(self.mangle_name(name).as_str())
+            .or_else(|| self.module.get_function(name))
+            .filter(|value| value.count_basic_blocks() > 0)
+        {
+            return Ok(value);
+        }
+
+        let current_function = self.function.clone();
+        let current_block = self.builder.get_insert_block();
+        let result = builder(self);
+        self.function = current_function;
+        if let Some(block) = current_block {
+            self.set_basic_block(block);
+        }
+        result?;
+
+        self.get_function(name)
+            .map(|function| function.value)
+            .ok_or_else(|| anyhow::anyhow!("Runtime function `{}` not found", name))
+    }
+
+    ///
+    /// Declares and defines the private runtime function `name` with `input_size` field
+    /// parameters in the current module.
+    ///
+    /// The `body` is translated in the entry block with the parameters, and returns the field
+    /// result if `output_size` is non-zero. Unless the `body` terminates the block itself, it is
+    /// followed by the branch to the return block. The exceptions are propagated to the caller.
+    ///
+    /// Changes the current function and basic block, so it must be called by the `builder` of
+    /// `get_or_build_runtime_function`.
+    ///
+    pub fn build_runtime_function<F>(
+        &mut self,
+        name: &str,
+        input_size: usize,
+        output_size: usize,
+        body: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnOnce(
+            &mut Self,
+            &[inkwell::values::IntValue<'ctx>],
+        ) -> anyhow::Result<Option<inkwell::values::IntValue<'ctx>>>,
+    {
+        let function_type = self.function_type(
+            output_size,
+            vec![self.field_type().as_basic_type_enum(); input_size],
+        );
+        self.add_function(name, function_type, Some(inkwell::module::Linkage::Private))?;
+
+        let function = self
+            .get_function(name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Runtime function `{}` not found", name))?;
+        self.set_function(function);
+        self.set_basic_block(self.function().entry_block);
+
+        let parameters: Vec<inkwell::values::IntValue<'ctx>> = self
+            .function()
+            .value
+            .get_param_iter()
+            .map(|parameter| parameter.into_int_value())
+            .collect();
+        let result = body(self, parameters.as_slice())?;
+        let result_pointer = match result {
+            Some(result) => {
+                let pointer = self.build_alloca(
+                    self.field_type(),
+                    format!("{}_result_pointer", name).as_str(),
+                );
+                self.build_store(pointer, result);
+                Some(pointer)
+            }
+            None => None,
+        };
+        if self.basic_block().get_terminator().is_none() {
+            self.build_unconditional_branch(self.function().return_block);
+        }
+
+        self.build_throw_block(LongReturnPolicy::Propagate);
+        self.build_catch_block(LongReturnPolicy::Propagate);
+
+        self.set_basic_block(self.function().return_block);
+        match result_pointer {
+            Some(pointer) => {
+                let result = self.build_load(pointer, format!("{}_result", name).as_str());
+                self.build_return(Some(&result));
+            }
+            None => self.build_return(None),
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Builds a guard runtime function invocation.
+    ///
+    pub fn build_guard(
+        &mut self,
+        guard: Guard,
+        arguments: &[inkwell::values::BasicValueEnum<'ctx>],
+    ) -> anyhow::Result<()> {
+        if arguments.len() != guard.arguments_count() {
+            anyhow::bail!(
+                "Guard function `{}` expects {} arguments, found {}",
+                guard.name(),
+                guard.arguments_count(),
+                arguments.len()
+            );
+        }
+
+        let function = self.get_guard_function(guard)?;
+        self.build_invoke(function, arguments, guard.name());
+        Ok(())
+    }
+
+    ///
+    /// Returns the specified intrinsic function.
+    ///
+    /// # Panics
+    /// If the intrinsic does not exist. See `try_get_intrinsic_function` for the fallible variant.
+    ///
+    pub fn get_intrinsic_function(
+        &self,
+        intrinsic: IntrinsicFunction,
+    ) -> inkwell::values::FunctionValue<'ctx> {
+        self.module()
+            .get_intrinsic_function(intrinsic.name(), intrinsic.argument_types(self).as_slice())
+            .unwrap_or_else(|| panic!("Intrinsic function `{}` does not exist", intrinsic.name()))
+    }
+
+    ///
+    /// Returns the specified intrinsic function, or an error if it does not exist.
+    ///
+    pub fn try_get_intrinsic_function(
+        &self,
+        intrinsic: IntrinsicFunction,
+    ) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>> {
+        self.module()
+            .get_intrinsic_function(intrinsic.name(), intrinsic.argument_types(self).as_slice())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Intrinsic function `{}` does not exist, requested in {}",
+                    intrinsic.name(),
+                    self.location()
+                )
+            })
+    }
+
+    ///
+    /// Appends a new basic block to the current function.
+    ///
+    pub fn append_basic_block(&self, name: &str) -> inkwell::basic_block::BasicBlock<'ctx> {
+        self.llvm
+            .append_basic_block(self.function().value, self.value_name(name).as_str())
+    }
+
+    ///
+    /// Sets the current basic block.
+    ///
+    pub fn set_basic_block(&self, block: inkwell::basic_block::BasicBlock<'ctx>) {
+        self.builder.position_at_end(block);
+    }
+
+    ///
+    /// Returns the current basic block.
+    ///
+    pub fn basic_block(&self) -> inkwell::basic_block::BasicBlock<'ctx> {
+        self.builder.get_insert_block().expect("Always exists")
+    }
+
+    ///
+    /// Pushes a new loop context to the stack.
+    ///
+    pub fn push_loop(
+        &mut self,
+        body_block: inkwell::basic_block::BasicBlock<'ctx>,
+        continue_block: inkwell::basic_block::BasicBlock<'ctx>,
+        join_block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) {
+        self.loop_stack
+            .push(Loop::new(body_block, continue_block, join_block));
+    }
+
+    ///
+    /// Pushes a new loop context with the optimizer hints to the stack.
+    ///
+    /// The hints are attached to the back edge when the loop is popped.
+    ///
+    pub fn push_loop_with_metadata(
+        &mut self,
+        body_block: inkwell::basic_block::BasicBlock<'ctx>,
+        continue_block: inkwell::basic_block::BasicBlock<'ctx>,
+        join_block: inkwell::basic_block::BasicBlock<'ctx>,
+        unroll_hint: LoopUnrollHint,
+        trip_count: Option<u64>,
+    ) {
+        self.loop_stack.push(Loop::new_with_metadata(
+            body_block,
+            continue_block,
+            join_block,
+            unroll_hint,
+            trip_count,
+        ));
+    }
+
+    ///
+    /// Pops the current loop context from the stack.
+    ///
+    /// Attaches the loop hints to the back edge, if any.
+    ///
+    pub fn pop_loop(&mut self) {
+        let r#loop = match self.loop_stack.pop() {
+            Some(r#loop) => r#loop,
+            None => return,
+        };
+        let metadata = match r#loop.metadata(self.llvm) {
+            Some(metadata) => metadata,
+            None => return,
+        };
+        let back_edge = match Self::loop_back_edge(&r#loop) {
+            Some(back_edge) => back_edge,
+            None => return,
+        };
+
+        let kind_id = self.llvm.get_kind_id("llvm.loop");
+        back_edge
+            .set_metadata(metadata, kind_id)
+            .expect("Always valid");
+    }
+
+    ///
+    /// Returns the back edge of `loop`, which is the branch to the loop header.
+    ///
+    /// The increment code may span several blocks, so the blocks reachable from the continue
+    /// block are searched for the branch to the body block, or to the header block branching to
+    /// the body block.
+    ///
+    fn loop_back_edge(r#loop: &Loop<'ctx>) -> Option<inkwell::values::InstructionValue<'ctx>> {
+        let successors = |block: inkwell::basic_block::BasicBlock<'ctx>| {
+            let terminator = block.get_terminator();
+            (0..terminator.map_or(0, |terminator| terminator.get_num_operands()))
+                .filter_map(move |index| {
+                    terminator
+                        .and_then(|terminator| terminator.get_operand(index))
+                        .and_then(|operand| operand.right())
+                })
+                .collect::<Vec<inkwell::basic_block::BasicBlock<'ctx>>>()
+        };
+
+        let mut visited = Vec::new();
+        let mut queue = vec![r#loop.continue_block];
+        while let Some(block) = queue.pop() {
+            if block == r#loop.join_block || visited.contains(&block) {
+                continue;
+            }
+            visited.push(block);
+
+            for successor in successors(block).into_iter() {
+                if successor == r#loop.body_block
+                    || successors(successor).contains(&r#loop.body_block)
+                {
+                    return block.get_terminator();
+                }
+                queue.push(successor);
+            }
+        }
+        None
+    }
+
+    ///
+    /// Returns the current loop context.
+    ///
+    /// # Panics
+    /// If the current context is not in a loop. See `try_loop` for the fallible variant.
+    ///
+    pub fn r#loop(&self) -> &Loop<'ctx> {
+        self.loop_stack
+            .last()
+            .expect("The current context is not in a loop")
+    }
+
+    ///
+    /// Returns the current loop context, or an error if the current context is not in a loop.
+    ///
+    pub fn try_loop(&self) -> anyhow::Result<&Loop<'ctx>> {
+        self.loop_stack.last().ok_or_else(|| {
+            anyhow::anyhow!(
+                "The current context is not in a loop in {}",
+                self.location()
+            )
+        })
+    }
+
+    ///
+    /// Describes the current function and basic block for the error messages.
+    ///
+    fn location(&self) -> String {
+        let block = self
+            .builder
+            .get_insert_block()
+            .map(|block| block.get_name().to_string_lossy().to_string())
+            .unwrap_or_else(|| "<none>".to_owned());
+        match self.function {
+            Some(ref function) => format!("function `{}`, block `{}`", function.name, block),
+            None => format!(
+                "module `{}`, block `{}`",
+                self.module.get_name().to_string_lossy(),
+                block
+            ),
+        }
+    }
+
+    ///
+    /// Builds a stack allocation instruction.
+    ///
+    /// The allocation is always placed after the other allocations in the function entry block,
+    /// regardless of the current insertion point, so it can be promoted to registers.
+    ///
+    /// Sets the alignment to 256 bits.
+    ///
+    pub fn build_alloca<T: BasicType<'ctx>>(
+        &self,
+        r#type: T,
+        name: &str,
+    ) -> inkwell::values::PointerValue<'ctx> {
+        let builder = self.entry_builder();
+        let pointer = builder.build_alloca(r#type, self.value_name(name).as_str());
+        pointer
+            .as_instruction()
+            .expect("Always exists")
+            .set_alignment(compiler_common::SIZE_FIELD as u32)
+            .expect("Alignment is valid");
+        pointer
+    }
+
+    ///
+    /// Builds a call in the function entry block, regardless of the current insertion point.
+    ///
+    /// The call is placed after the allocations, so its result dominates every block of the
+    /// function and can be reused, e.g. for the values invariant within a contract call.
+    ///
+    pub fn build_entry_call(
+        &self,
+        function: inkwell::values::FunctionValue<'ctx>,
+        args: &[inkwell::values::BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Option<inkwell::values::BasicValueEnum<'ctx>> {
+        let builder = self.entry_builder();
+        let call_site_value = builder.build_call(function, args, self.value_name(name).as_str());
+        for (location, alignment) in Self::pointer_alignments(function.get_type()) {
+            call_site_value.set_alignment_attribute(location, alignment);
+        }
+        call_site_value.try_as_basic_value().left()
+    }
+
+    ///
+    /// Returns a builder positioned after the allocations in the current function entry block.
+    ///
+    fn entry_builder(&self) -> inkwell::builder::Builder<'ctx> {
+        let entry_block = self
+            .basic_block()
+            .get_parent()
+            .and_then(|function| function.get_first_basic_block())
+            .expect("Always exists");
+
+        let builder = self.llvm.create_builder();
+        let mut instruction = entry_block.get_first_instruction();
+        while let Some(current) = instruction {
+            if current.get_opcode() != inkwell::values::InstructionOpcode::Alloca {
+                break;
+            }
+            instruction = current.get_next_instruction();
+        }
+        match instruction {
+            Some(instruction) => builder.position_before(&instruction),
+            None => builder.position_at_end(entry_block),
+        }
+        builder
+    }
+
+    ///
+    /// Builds a stack allocation, which is only live within the `scope`.
+    ///
+    /// The allocation is wrapped into the lifetime markers, so the backend can reuse its stack
+    /// slot after the scope. The markers take the byte pointer, so the allocation may be of any
+    /// type. The end marker is omitted if the scope has terminated the block. The pointer must
+    /// not escape the scope.
+    ///
+    pub fn build_scoped_alloca<T, F, R>(
+        &mut self,
+        r#type: T,
+        name: &str,
+        scope: F,
+    ) -> anyhow::Result<R>
+    where
+        T: BasicType<'ctx>,
+        F: FnOnce(&mut Self, inkwell::values::PointerValue<'ctx>) -> anyhow::Result<R>,
+    {
+        let pointer = self.build_alloca(r#type, name);
+        let size = self.llvm.i64_type().const_all_ones();
+        let byte_pointer = self.builder.build_pointer_cast(
+            pointer,
+            self.integer_type(compiler_common::BITLENGTH_BYTE)
+                .ptr_type(AddressSpace::Stack.into()),
+            self.value_name(format!("{}_byte_pointer", name).as_str())
+                .as_str(),
+        );
+
+        let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::LifetimeStart)?;
+        self.builder.build_call(
+            intrinsic,
+            &[
+                size.as_basic_value_enum(),
+                byte_pointer.as_basic_value_enum(),
+            ],
+            self.value_name(format!("{}_lifetime_start", name).as_str())
+                .as_str(),
+        );
+
+        let result = scope(self, pointer)?;
+
+        if self.basic_block().get_terminator().is_none() {
+            let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::LifetimeEnd)?;
+            self.builder.build_call(
+                intrinsic,
+                &[
+                    size.as_basic_value_enum(),
+                    byte_pointer.as_basic_value_enum(),
+                ],
+                self.value_name(format!("{}_lifetime_end", name).as_str())
+                    .as_str(),
+            );
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Builds a pointer to the structure field at `index`.
+    ///
+    /// # Errors
+    /// If `pointer` does not point to a structure, or the structure has no field at `index`.
+    ///
+    pub fn build_struct_gep_checked(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        index: u32,
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::PointerValue<'ctx>> {
+        let structure_type = match pointer.get_type().get_element_type() {
+            inkwell::types::AnyTypeEnum::StructType(structure_type) => structure_type,
+            r#type => anyhow::bail!("Expected a pointer to a structure, found `{:?}`", r#type),
+        };
+        let fields_count = structure_type.count_fields();
+        if index >= fields_count {
+            anyhow::bail!(
+                "The structure field index {} is out of bounds of {} fields",
+                index,
+                fields_count
+            );
+        }
+
+        self.builder
+            .build_struct_gep(pointer, index, self.value_name(name).as_str())
+            .map_err(|_| anyhow::anyhow!("The structure field {} is not accessible", index))
+    }
+
+    ///
+    /// Builds a pointer to the array element at `index`.
+    ///
+    /// The constant indexes are checked at compile time. The dynamic indexes are checked at
+    /// runtime, panicking with `PanicCode::IndexOutOfBounds` if out of bounds.
+    ///
+    /// # Errors
+    /// If `pointer` does not point to an array, or the constant `index` is out of bounds.
+    ///
+    pub fn build_array_index(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        index: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::PointerValue<'ctx>> {
+        let array_type = match pointer.get_type().get_element_type() {
+            inkwell::types::AnyTypeEnum::ArrayType(array_type) => array_type,
+            r#type => anyhow::bail!("Expected a pointer to an array, found `{:?}`", r#type),
+        };
+        let length = array_type.len() as u64;
+
+        match index.get_zero_extended_constant() {
+            Some(constant) if constant >= length => anyhow::bail!(
+                "The array index {} is out of bounds of {} elements",
+                constant,
+                length
+            ),
+            Some(_) => {}
+            None => {
+                let is_in_bounds = self.builder.build_int_compare(
+                    inkwell::IntPredicate::ULT,
+                    index,
+                    index.get_type().const_int(length, false),
+                    self.value_name(format!("{}_is_in_bounds", name).as_str())
+                        .as_str(),
+                );
+                let out_of_bounds_block =
+                    self.append_basic_block(format!("{}_out_of_bounds", name).as_str());
+                let in_bounds_block =
+                    self.append_basic_block(format!("{}_in_bounds", name).as_str());
+                self.build_conditional_branch(is_in_bounds, in_bounds_block, out_of_bounds_block);
+
+                self.set_basic_block(out_of_bounds_block);
+                self.build_panic(PanicCode::IndexOutOfBounds);
+
+                self.set_basic_block(in_bounds_block);
+            }
+        }
+
+        // SAFETY: the index has been checked against the array length above.
+        let pointer = unsafe {
+            self.builder.build_in_bounds_gep(
+                pointer,
+                &[index.get_type().const_zero(), index],
+                self.value_name(name).as_str(),
+            )
+        };
+        Ok(pointer)
+    }
+
+    ///
+    /// Builds a stack store instruction.
+    ///
+    /// Sets the alignment to 256 bits for stack and 1 bit for heap, parent, and child.
+    ///
+    pub fn build_store<V: BasicValue<'ctx>>(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        value: V,
+    ) {
+        let instruction = self.builder.build_store(pointer, value);
+
+        let alignment = AddressSpace::alignment_of(pointer.get_type().get_address_space());
+
+        instruction
+            .set_alignment(alignment)
+            .expect("Alignment is valid");
+    }
+
+    ///
+    /// Builds a store of consecutive field `values` to the heap starting at `base_offset`.
+    ///
+    /// The values are stored as a single array aggregate, which is a constant if all the values
+    /// are constants, so the sequences like ABI encoding produce fewer instructions.
+    ///
+    pub fn build_store_multiple(
+        &self,
+        base_offset: inkwell::values::IntValue<'ctx>,
+        values: &[inkwell::values::IntValue<'ctx>],
+    ) {
+        match values.len() {
+            0 => return,
+            1 => {
+                let pointer =
+                    self.access_memory(base_offset, AddressSpace::Heap, "store_multiple_pointer");
+                self.build_store(pointer, values[0]);
+                return;
+            }
+            _ => {}
+        }
+
+        let array_type = self.field_type().array_type(values.len() as u32);
+        let aggregate = if values.iter().all(|value| value.is_const()) {
+            self.field_type().const_array(values)
+        } else {
+            let mut aggregate = array_type.get_undef();
+            for (index, value) in values.iter().enumerate() {
+                aggregate = self
+                    .builder
+                    .build_insert_value(
+                        aggregate,
+                        *value,
+                        index as u32,
+                        self.value_name("store_multiple_aggregate").as_str(),
+                    )
+                    .expect("Always valid")
+                    .into_array_value();
+            }
+            aggregate
+        };
+
+        let pointer = self.builder.build_int_to_ptr(
+            base_offset,
+            array_type.ptr_type(AddressSpace::Heap.into()),
+            self.value_name("store_multiple_pointer").as_str(),
+        );
+        self.builder
+            .build_store(pointer, aggregate)
+            .set_alignment(1)
+            .expect("Alignment is valid");
+    }
+
+    ///
+    /// Allocates `size` bytes of the heap, bumping the free memory pointer.
+    ///
+    /// Returns the offset of the allocated region.
+    ///
+    pub fn build_heap_alloc(
+        &self,
+        size: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx> {
+        let free_pointer_pointer = self.access_memory(
+            self.field_const(Self::HEAP_FREE_POINTER_OFFSET as u64),
+            AddressSpace::Heap,
+            "heap_free_pointer_pointer",
+        );
+        let offset = self
+            .build_load(free_pointer_pointer, "heap_alloc_offset")
+            .into_int_value();
+        let free_pointer = self.builder.build_int_add(
+            offset,
+            size,
+            self.value_name("heap_free_pointer_bumped").as_str(),
+        );
+        self.build_store(free_pointer_pointer, free_pointer);
+        offset
+    }
+
+    ///
+    /// Builds a stack load instruction.
+    ///
+    /// Sets the alignment to 256 bits for stack and 1 bit for heap, parent, and child.
+    ///
+    pub fn build_load(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        name: &str,
+    ) -> inkwell::values::BasicValueEnum<'ctx> {
+        let value = self
+            .builder
+            .build_load(pointer, self.value_name(name).as_str());
+
+        let alignment = AddressSpace::alignment_of(pointer.get_type().get_address_space());
+
+        self.basic_block()
+            .get_last_instruction()
+            .expect("Always exists")
+            .set_alignment(alignment)
+            .expect("Alignment is valid");
+        value
+    }
+
+    ///
+    /// Builds a stack load instruction and attaches the range metadata to it.
+    ///
+    /// The loaded value is declared to be less than `2^bitlength`.
+    ///
+    pub fn build_load_with_range(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        bitlength: usize,
+        name: &str,
+    ) -> inkwell::values::BasicValueEnum<'ctx> {
+        let value = self.build_load(pointer, name);
+        if bitlength < compiler_common::BITLENGTH_FIELD {
+            let range = self.llvm.metadata_node(&[
+                self.field_const(0).into(),
+                self.bitlength_bound(bitlength).into(),
+            ]);
+            value
+                .as_instruction_value()
+                .expect("Always exists")
+                .set_metadata(range, self.llvm.get_kind_id("range"))
+                .expect("Always valid");
+        }
+        value
+    }
+
+    ///
+    /// Builds an optimizer assumption that `predicate` is always true.
+    ///
+    pub fn assume(&self, predicate: inkwell::values::IntValue<'ctx>) {
+        let predicate = self.as_bool(predicate);
+        self.build_call(
+            self.get_intrinsic_function(IntrinsicFunction::Assume),
+            &[predicate.as_basic_value_enum()],
+            "assume",
+        );
+    }
+
+    ///
+    /// Builds an optimizer assumption that `value` is less than `2^bitlength`.
+    ///
+    /// Is useful for sizes and addresses, which are known to fit into 32 and 160 bits.
+    ///
+    pub fn assume_bitlength(&self, value: inkwell::values::IntValue<'ctx>, bitlength: usize) {
+        if bitlength >= compiler_common::BITLENGTH_FIELD {
+            return;
+        }
+
+        let is_in_range = self.builder.build_int_compare(
+            inkwell::IntPredicate::ULT,
+            value,
+            self.bitlength_bound(bitlength),
+            self.value_name("assume_is_in_range").as_str(),
+        );
+        self.assume(is_in_range);
+    }
+
+    ///
+    /// Clears the bits of `value` above the account address bitlength.
+    ///
+    /// The constant values are folded without emitting instructions.
+    ///
+    pub fn build_address_mask(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx> {
+        let mask = self
+            .bitlength_bound(Self::BITLENGTH_ADDRESS)
+            .const_sub(self.field_const(1));
+
+        if value.is_const() {
+            return value.const_and(mask);
+        }
+
+        self.builder
+            .build_and(value, mask, self.value_name("address_masked").as_str())
+    }
+
+    ///
+    /// Returns the `2^bitlength` field constant.
+    ///
+    fn bitlength_bound(&self, bitlength: usize) -> inkwell::values::IntValue<'ctx> {
+        self.field_const(1)
+            .const_shl(self.field_const(bitlength as u64))
+    }
+
+    ///
+    /// Marks the `block` as rarely executed.
+    ///
+    /// The conditional branches leading to the cold blocks are weighted before the optimization,
+    /// so the hot paths get a better layout. The throw and catch blocks are cold by default.
+    ///
+    pub fn mark_cold(&mut self, block: inkwell::basic_block::BasicBlock<'ctx>) {
+        if !self.cold_blocks.contains(&block) {
+            self.cold_blocks.push(block);
+        }
+    }
+
+    ///
+    /// Attaches the storage access `hint` to the last instruction of the current block.
+    ///
+    /// The blocks with the cold accesses are also marked as rarely executed, since such accesses
+    /// are expected off the hot path, e.g. in the lazy initialization.
+    ///
+    pub fn annotate_storage_access(&mut self, hint: StorageHint) {
+        let block = self.basic_block();
+        let instruction = match block.get_last_instruction() {
+            Some(instruction) => instruction,
+            None => return,
+        };
+
+        let kind_id = self.llvm.get_kind_id(StorageHint::METADATA_KIND);
+        let metadata = self
+            .llvm
+            .metadata_node(&[self.llvm.metadata_string(hint.as_str()).into()]);
+        instruction
+            .set_metadata(metadata, kind_id)
+            .expect("Always valid");
+
+        if let StorageHint::Cold = hint {
+            self.mark_cold(block);
+        }
+    }
+
+    ///
+    /// Sets the branch weights on the conditional branches leading to the cold blocks.
+    ///
+    fn apply_cold_blocks(&self) {
+        let kind_id = self.llvm.get_kind_id("prof");
+
+        for function in self.module.get_functions() {
+            for block in function.get_basic_blocks() {
+                let terminator = match block.get_terminator() {
+                    Some(terminator) => terminator,
+                    None => continue,
+                };
+                if terminator.get_opcode() != inkwell::values::InstructionOpcode::Br
+                    || terminator.get_num_operands() != 3
+                {
+                    continue;
+                }
+
+                let is_cold = |index: u32| {
+                    terminator
+                        .get_operand(index)
+                        .and_then(|operand| operand.right())
+                        .map(|block| self.cold_blocks.contains(&block))
+                        .unwrap_or_default()
+                };
+                let is_false_cold = is_cold(1);
+                let is_true_cold = is_cold(2);
+                if is_true_cold == is_false_cold {
+                    continue;
+                }
+
+                let weight = |is_cold: bool| {
+                    let weight = if is_cold {
+                        Self::BRANCH_WEIGHT_COLD
+                    } else {
+                        Self::BRANCH_WEIGHT_HOT
+                    };
+                    self.llvm.i32_type().const_int(weight, false).into()
+                };
+                let weights = self.llvm.metadata_node(&[
+                    self.llvm.metadata_string("branch_weights").into(),
+                    weight(is_true_cold),
+                    weight(is_false_cold),
+                ]);
+                terminator
+                    .set_metadata(weights, kind_id)
+                    .expect("Always valid");
+            }
+        }
+    }
+
+    ///
+    /// Builds a conditional branch.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_conditional_branch(
+        &self,
+        comparison: inkwell::values::IntValue<'ctx>,
+        then_block: inkwell::basic_block::BasicBlock<'ctx>,
+        else_block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) {
+        if self.basic_block().get_terminator().is_some() {
+            return;
+        }
+
+        self.builder
+            .build_conditional_branch(comparison, then_block, else_block);
+    }
+
+    ///
+    /// Builds a switch over the `value` constant `cases`.
+    ///
+    /// If the `default` block is not set, the frontend guarantees that the dispatch is exhaustive,
+    /// so the default case is marked as unreachable, allowing LLVM to drop the last comparison.
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_switch(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+        default: Option<inkwell::basic_block::BasicBlock<'ctx>>,
+        cases: &[(
+            inkwell::values::IntValue<'ctx>,
+            inkwell::basic_block::BasicBlock<'ctx>,
+        )],
+        name: &str,
+    ) {
+        if self.basic_block().get_terminator().is_some() {
+            return;
+        }
+
+        let default = match default {
+            Some(default) => default,
+            None => {
+                let current_block = self.basic_block();
+                let unreachable_block =
+                    self.append_basic_block(format!("{}_default_unreachable", name).as_str());
+                self.set_basic_block(unreachable_block);
+                self.build_unreachable();
+                self.set_basic_block(current_block);
+                unreachable_block
+            }
+        };
+
+        self.builder.build_switch(value, default, cases);
+    }
+
+    ///
+    /// Builds an unconditional branch.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_unconditional_branch(
+        &self,
+        destination_block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) {
+        if self.basic_block().get_terminator().is_some() {
+            return;
+        }
+
+        self.builder.build_unconditional_branch(destination_block);
+    }
+
+    ///
+    /// Switches to a fresh child context and translates `body`, which writes the child input and
+    /// makes the call consuming it.
+    ///
+    /// The child context is shared by all calls, so the switches must not be nested, otherwise
+    /// the inner call would overwrite the input of the outer one. Nesting is reported as an
+    /// error.
+    ///
+    pub fn with_child_context<T, R>(&mut self, name: &str, body: T) -> anyhow::Result<R>
+    where
+        T: FnOnce(&mut Self) -> anyhow::Result<R>,
+    {
+        if self.is_child_context_active {
+            anyhow::bail!(
+                "The child context switch `{}` is nested into another one",
+                name
+            );
+        }
+
+        let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::SwitchContext)?;
+        self.build_call(intrinsic, &[], format!("{}_switch_context", name).as_str());
+
+        self.is_child_context_active = true;
+        let result = body(self);
+        self.is_child_context_active = false;
+        result
+    }
+
+    ///
+    /// Translates `body` as the EVM operation `op`, annotating the instructions emitted into the
+    /// current function in the annotation mode.
+    ///
+    /// The new instructions are the ones following the insertion point in the current block,
+    /// and the ones in the blocks appended to the function by `body`, so only the emitted code
+    /// is walked. The outermost operation takes precedence. The runtime functions emitted on
+    /// demand are shared and are not annotated.
+    ///
+    pub fn with_evm_op<T, R>(&mut self, op: &str, body: T) -> anyhow::Result<R>
+    where
+        T: FnOnce(&mut Self) -> anyhow::Result<R>,
+    {
+        if !self.is_evm_op_annotation_enabled || self.evm_op.is_some() {
+            return body(self);
+        }
+        let (function, block) = match (self.function.as_ref(), self.builder.get_insert_block()) {
+            (Some(function), Some(block)) => (function.value, block),
+            _ => return body(self),
+        };
+        let last_instruction = block.get_last_instruction();
+        let last_block = function
+            .get_last_basic_block()
+            .expect("The insertion block always exists");
+
+        self.evm_op = Some(op.to_owned());
+        let result = body(self);
+        self.evm_op = None;
+
+        let kind = self.llvm.get_kind_id(Self::EVM_OP_METADATA_KIND);
+        let node = self
+            .llvm
+            .metadata_node(&[self.llvm.metadata_string(op).into()]);
+        let mut instruction = match last_instruction {
+            Some(last_instruction) => last_instruction.get_next_instruction(),
+            None => block.get_first_instruction(),
+        };
+        let mut next_block = last_block.get_next_basic_block();
+        loop {
+            while let Some(current) = instruction {
+                current.set_metadata(node, kind).map_err(|error| {
+                    anyhow::anyhow!("EVM operation `{}` annotation: {}", op, error)
+                })?;
+                instruction = current.get_next_instruction();
+            }
+            match next_block {
+                Some(block) => {
+                    instruction = block.get_first_instruction();
+                    next_block = block.get_next_basic_block();
+                }
+                None => break,
+            }
+        }
+        result
+    }
+
+    ///
+    /// Builds the `if` statement.
+    ///
+    /// The `then` closure is translated into the block executed if `condition` is true, which
+    /// falls through to the join block unless the closure has terminated it.
+    ///
+    pub fn build_if<T>(
+        &mut self,
+        condition: inkwell::values::IntValue<'ctx>,
+        name: &str,
+        then: T,
+    ) -> anyhow::Result<()>
+    where
+        T: FnOnce(&mut Self) -> anyhow::Result<()>,
+    {
+        let then_block = self.append_basic_block(format!("{}_then", name).as_str());
+        let join_block = self.append_basic_block(format!("{}_join", name).as_str());
+        self.build_conditional_branch(condition, then_block, join_block);
+
+        self.set_basic_block(then_block);
+        then(self)?;
+        self.build_unconditional_branch(join_block);
+
+        self.set_basic_block(join_block);
+        Ok(())
+    }
+
+    ///
+    /// Builds the `if-else` statement.
+    ///
+    /// The `then` and `r#else` closures are translated into the blocks executed if `condition`
+    /// is true and false respectively. Each of them falls through to the join block unless the
+    /// closure has terminated it.
+    ///
+    pub fn build_if_else<T, E>(
+        &mut self,
+        condition: inkwell::values::IntValue<'ctx>,
+        name: &str,
+        then: T,
+        r#else: E,
+    ) -> anyhow::Result<()>
+    where
+        T: FnOnce(&mut Self) -> anyhow::Result<()>,
+        E: FnOnce(&mut Self) -> anyhow::Result<()>,
+    {
+        let then_block = self.append_basic_block(format!("{}_then", name).as_str());
+        let else_block = self.append_basic_block(format!("{}_else", name).as_str());
+        let join_block = self.append_basic_block(format!("{}_join", name).as_str());
+        self.build_conditional_branch(condition, then_block, else_block);
+
+        self.set_basic_block(then_block);
+        then(self)?;
+        self.build_unconditional_branch(join_block);
+
+        self.set_basic_block(else_block);
+        r#else(self)?;
+        self.build_unconditional_branch(join_block);
+
+        self.set_basic_block(join_block);
+        Ok(())
+    }
+
+    ///
+    /// Builds the short-circuit logical AND.
+    ///
+    /// The `rhs` closure is only translated into the block executed if `lhs` is true.
+    /// Returns the field value holding either 0 or 1.
+    ///
+    pub fn build_short_circuit_and<L, R>(
+        &mut self,
+        lhs: L,
+        rhs: R,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        L: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+        R: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+    {
+        self.build_short_circuit(true, lhs, rhs)
+    }
+
+    ///
+    /// Builds the short-circuit logical OR.
+    ///
+    /// The `rhs` closure is only translated into the block executed if `lhs` is false.
+    /// Returns the field value holding either 0 or 1.
+    ///
+    pub fn build_short_circuit_or<L, R>(
+        &mut self,
+        lhs: L,
+        rhs: R,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        L: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+        R: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+    {
+        self.build_short_circuit(false, lhs, rhs)
+    }
+
+    ///
+    /// Builds the short-circuit logical operation.
+    ///
+    /// If `is_and` is set, `rhs` is evaluated if `lhs` is true, otherwise if `lhs` is false.
+    /// In both cases, the result equals `lhs` if `rhs` is not evaluated.
+    ///
+    fn build_short_circuit<L, R>(
+        &mut self,
+        is_and: bool,
+        lhs: L,
+        rhs: R,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        L: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+        R: FnOnce(&mut Self) -> anyhow::Result<inkwell::values::IntValue<'ctx>>,
+    {
+        let result_pointer = self.build_alloca(self.field_type(), "short_circuit_result_pointer");
+
+        let lhs = lhs(self)?;
+        let lhs = self.as_bool(lhs);
+        self.build_store(result_pointer, self.as_field_bool(lhs));
+
+        let rhs_block = self.append_basic_block("short_circuit_rhs");
+        let join_block = self.append_basic_block("short_circuit_join");
+        if is_and {
+            self.build_conditional_branch(lhs, rhs_block, join_block);
+        } else {
+            self.build_conditional_branch(lhs, join_block, rhs_block);
+        }
+
+        self.set_basic_block(rhs_block);
+        let rhs = rhs(self)?;
+        self.build_store(result_pointer, self.as_field_bool(rhs));
+        self.build_unconditional_branch(join_block);
+
+        self.set_basic_block(join_block);
+        Ok(self
+            .build_load(result_pointer, "short_circuit_result")
+            .into_int_value())
+    }
+
+    ///
+    /// Builds a call.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_call(
+        &self,
+        function: inkwell::values::FunctionValue<'ctx>,
+        args: &[inkwell::values::BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Option<inkwell::values::BasicValueEnum<'ctx>> {
+        let call_site_value =
+            self.builder
+                .build_call(function, args, self.value_name(name).as_str());
+
+        if name == compiler_common::LLVM_FUNCTION_CXA_THROW {
+            return call_site_value.try_as_basic_value().left();
+        }
+
+        for (location, alignment) in Self::pointer_alignments(function.get_type()) {
+            call_site_value.set_alignment_attribute(location, alignment);
+        }
+
+        call_site_value.try_as_basic_value().left()
+    }
+
+    ///
+    /// Returns the alignments of the pointer parameters and return value of the function `type`.
+    ///
+    /// The single source of the parameter ABI decoration, so the declarations and call sites
+    /// always agree, including the return pointer of the functions with several return values.
+    /// The non-pointer parameters are left undecorated.
+    ///
+    fn pointer_alignments(
+        r#type: inkwell::types::FunctionType<'ctx>,
+    ) -> Vec<(inkwell::attributes::AttributeLoc, u32)> {
+        let alignment_of = |r#type: inkwell::types::BasicTypeEnum<'ctx>| match r#type {
+            inkwell::types::BasicTypeEnum::PointerType(r#type) => {
+                Some(AddressSpace::alignment_of(r#type.get_address_space()))
+            }
+            _ => None,
+        };
+
+        let mut alignments: Vec<_> = r#type
+            .get_param_types()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, r#type)| {
+                alignment_of(r#type).map(|alignment| {
+                    (
+                        inkwell::attributes::AttributeLoc::Param(index as u32),
+                        alignment,
+                    )
+                })
+            })
+            .collect();
+        if let Some(alignment) = r#type.get_return_type().and_then(alignment_of) {
+            alignments.push((inkwell::attributes::AttributeLoc::Return, alignment));
+        }
+        alignments
+    }
+
+    ///
+    /// Decorates the pointer parameters and return value of the function declaration `value`.
+    ///
+    fn set_pointer_alignments(&self, value: inkwell::values::FunctionValue<'ctx>) {
+        for (location, alignment) in Self::pointer_alignments(value.get_type()) {
+            let attribute = self.llvm.create_enum_attribute(
+                inkwell::attributes::Attribute::get_named_enum_kind_id("align"),
+                alignment as u64,
+            );
+            value.add_attribute(location, attribute);
+        }
+    }
+
+    ///
+    /// Builds an invoke.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    /// The functions marked with `nounwind` are called without the unwinding edge.
+    ///
+    pub fn build_invoke(
+        &self,
+        function: inkwell::values::FunctionValue<'ctx>,
+        args: &[inkwell::values::BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Option<inkwell::values::BasicValueEnum<'ctx>> {
+        let is_nothrow = self
+            .functions
+            .get(function.get_name().to_string_lossy().as_ref())
+            .map(|declared| declared.value == function && declared.is_nothrow)
+            .unwrap_or_default();
+        if is_nothrow
+            || function
+                .get_enum_attribute(
+                    inkwell::attributes::AttributeLoc::Function,
+                    inkwell::attributes::Attribute::get_named_enum_kind_id("nounwind"),
+                )
+                .is_some()
+        {
+            return self.build_call(function, args, name);
+        }
+
+        let join_block = self.append_basic_block("join");
+
+        let call_site_value = self.builder.build_invoke(
+            function,
+            args,
+            join_block,
+            self.function().catch_block,
+            self.value_name(name).as_str(),
+        );
+
+        for (location, alignment) in Self::pointer_alignments(function.get_type()) {
+            call_site_value.set_alignment_attribute(location, alignment);
+        }
+
+        self.set_basic_block(join_block);
+
+        call_site_value.try_as_basic_value().left()
+    }
+
+    ///
+    /// Builds a memory copy call.
+    ///
+    /// Sets the alignment to 1 bit for heap, parent, and child.
+    ///
+    pub fn build_memcpy(
+        &self,
+        intrinsic: IntrinsicFunction,
+        destination: inkwell::values::PointerValue<'ctx>,
+        source: inkwell::values::PointerValue<'ctx>,
+        size: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) {
+        let intrinsic = self.get_intrinsic_function(intrinsic);
+
+        let call_site_value = self.builder.build_call(
+            intrinsic,
+            &[
+                destination.as_basic_value_enum(),
+                source.as_basic_value_enum(),
+                size.as_basic_value_enum(),
+                self.integer_type(compiler_common::BITLENGTH_BOOLEAN)
+                    .const_zero()
+                    .as_basic_value_enum(),
+            ],
+            self.value_name(name).as_str(),
+        );
+
+        call_site_value.set_alignment_attribute(inkwell::attributes::AttributeLoc::Param(0), 1);
+        call_site_value.set_alignment_attribute(inkwell::attributes::AttributeLoc::Param(1), 1);
+    }
+
+    ///
+    /// Builds a memory copy call, which is skipped if the `size` is zero.
+    ///
+    /// Nothing is generated for the constant zero size. The dynamic size is checked at runtime,
+    /// with the copy branched around if it is zero.
+    ///
+    pub fn build_memcpy_non_empty(
+        &self,
+        intrinsic: IntrinsicFunction,
+        destination: inkwell::values::PointerValue<'ctx>,
+        source: inkwell::values::PointerValue<'ctx>,
+        size: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) {
+        if let Some(constant) = Argument::constant_of(size.as_basic_value_enum()) {
+            if !constant.is_zero() {
+                self.build_memcpy(intrinsic, destination, source, size, name);
+            }
+            return;
+        }
+
+        let copy_block = self.append_basic_block(format!("{}_copy", name).as_str());
+        let join_block = self.append_basic_block(format!("{}_join", name).as_str());
+
+        let is_size_zero = self.builder.build_int_compare(
+            inkwell::IntPredicate::EQ,
+            size,
+            size.get_type().const_zero(),
+            self.value_name(format!("{}_is_size_zero", name).as_str())
+                .as_str(),
+        );
+        self.build_conditional_branch(is_size_zero, join_block, copy_block);
+
+        self.set_basic_block(copy_block);
+        self.build_memcpy(intrinsic, destination, source, size, name);
+        self.build_unconditional_branch(join_block);
+
+        self.set_basic_block(join_block);
+    }
+
+    ///
+    /// Builds the verbatim target `bytes` as an inline assembly block.
+    ///
+    /// The `inputs` are passed in registers, and `output_count` field values are returned.
+    /// The block is treated as having side effects, so it is never removed by the optimizer.
+    ///
+    pub fn build_verbatim(
+        &self,
+        bytes: &[u8],
+        inputs: &[inkwell::values::IntValue<'ctx>],
+        output_count: usize,
+    ) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>> {
+        if bytes.is_empty() {
+            anyhow::bail!("The verbatim bytes cannot be empty");
+        }
+        if inputs.len() > Self::VERBATIM_ARGUMENTS_LIMIT {
+            anyhow::bail!(
+                "The verbatim inputs count {} exceeds the limit of {}",
+                inputs.len(),
+                Self::VERBATIM_ARGUMENTS_LIMIT
+            );
+        }
+        if output_count > Self::VERBATIM_ARGUMENTS_LIMIT {
+            anyhow::bail!(
+                "The verbatim outputs count {} exceeds the limit of {}",
+                output_count,
+                Self::VERBATIM_ARGUMENTS_LIMIT
+            );
+        }
+
+        let argument_types = vec![self.field_type().as_basic_type_enum(); inputs.len()];
+        let function_type = match output_count {
+            0 => self.void_type().fn_type(argument_types.as_slice(), false),
+            1 => self.field_type().fn_type(argument_types.as_slice(), false),
+            length => self
+                .structure_type(vec![self.field_type().as_basic_type_enum(); length])
+                .fn_type(argument_types.as_slice(), false),
+        };
+
+        let assembly = format!(
+            ".byte {}",
+            bytes
+                .iter()
+                .map(|byte| format!("0x{:02x}", byte))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        let constraints = std::iter::repeat("=r")
+            .take(output_count)
+            .chain(std::iter::repeat("r").take(inputs.len()))
+            .collect::<Vec<&str>>()
+            .join(",");
+
+        let inline_assembly = self.llvm.create_inline_asm(
+            function_type,
+            assembly,
+            constraints,
+            true,
+            false,
+            None,
+            false,
+        );
+        let callable = inkwell::values::CallableValue::try_from(inline_assembly)
+            .map_err(|_| anyhow::anyhow!("The verbatim inline assembly is not callable"))?;
+        let inputs: Vec<inkwell::values::BasicValueEnum<'ctx>> = inputs
+            .iter()
+            .map(|input| input.as_basic_value_enum())
+            .collect();
+        let call_site_value = self.builder.build_call(
+            callable,
+            inputs.as_slice(),
+            self.value_name("verbatim").as_str(),
+        );
+
+        let result = match call_site_value.try_as_basic_value().left() {
+            Some(result) => result,
+            None => return Ok(vec![]),
+        };
+        if output_count == 1 {
+            return Ok(vec![result.into_int_value()]);
+        }
+
+        let mut outputs = Vec::with_capacity(output_count);
+        for index in 0..output_count {
+            let output = self
+                .builder
+                .build_extract_value(
+                    result.into_struct_value(),
+                    index as u32,
+                    self.value_name(format!("verbatim_output_{}", index).as_str())
+                        .as_str(),
+                )
+                .expect("Always exists");
+            outputs.push(output.into_int_value());
+        }
+        Ok(outputs)
+    }
+
+    ///
+    /// Builds a return.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_return(&self, value: Option<&dyn BasicValue<'ctx>>) {
+        if self.basic_block().get_terminator().is_some() {
+            return;
+        }
+
+        self.builder.build_return(value);
+    }
+
+    ///
+    /// Builds an unreachable.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    pub fn build_unreachable(&self) {
+        if self.basic_block().get_terminator().is_some() {
+            return;
+        }
+
+        self.builder.build_unreachable();
+    }
+
+    ///
+    /// Builds an exception catching block sequence.
+    ///
+    /// The block is removed before the optimization if nothing unwinds to it.
+    ///
+    pub fn build_catch_block(&mut self, policy: LongReturnPolicy) {
+        self.set_basic_block(self.function().catch_block);
+        self.build_landing_pad();
+        self.build_throw_epilogue(policy);
+    }
+
+    ///
+    /// Builds a catch block sequence with a user-level `handler`.
+    ///
+    /// The landing pad result and the thrown data are bound to the payload passed to `handler`.
+    /// If the handler leaves the block unterminated, the exception is rethrown as usual.
+    ///
+    /// The block is removed before the optimization if nothing unwinds to it.
+    ///
+    pub fn build_catch_block_with_handler<H>(
+        &mut self,
+        policy: LongReturnPolicy,
+        handler: H,
+    ) -> anyhow::Result<()>
+    where
+        H: FnOnce(&mut Self, CatchPayload<'ctx>) -> anyhow::Result<()>,
+    {
+        self.set_basic_block(self.function().catch_block);
+        let landing_pad = self.build_landing_pad();
+
+        let exception = self
+            .builder
+            .build_extract_value(
+                landing_pad,
+                0,
+                self.value_name("landing_exception").as_str(),
+            )
+            .expect("Always exists")
+            .into_pointer_value();
+        let selector = self
+            .builder
+            .build_extract_value(landing_pad, 1, self.value_name("landing_selector").as_str())
+            .expect("Always exists")
+            .into_int_value();
+        let data_pointer = self.access_memory(
+            self.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Parent,
+            "landing_data_pointer",
+        );
+        let data_size = self.read_header(AddressSpace::Parent).size(self);
+        handler(
+            self,
+            CatchPayload::new(exception, selector, data_pointer, data_size),
+        )?;
+
+        if self.basic_block().get_terminator().is_none() {
+            self.build_throw_epilogue(policy);
+        }
+        Ok(())
+    }
+
+    ///
+    /// Builds the landing pad at the current position.
+    ///
+    fn build_landing_pad(&self) -> inkwell::values::StructValue<'ctx> {
+        let landing_pad_type = self.structure_type(vec![
+            self.integer_type(compiler_common::BITLENGTH_BYTE)
+                .ptr_type(AddressSpace::Stack.into())
+                .as_basic_type_enum(),
+            self.integer_type(compiler_common::BITLENGTH_X32)
+                .as_basic_type_enum(),
+        ]);
+        self.builder
+            .build_landing_pad(
+                landing_pad_type,
+                self.runtime.personality,
+                vec![self
+                    .integer_type(compiler_common::BITLENGTH_BYTE)
+                    .ptr_type(AddressSpace::Stack.into())
+                    .const_zero()
+                    .as_basic_value_enum()],
+                self.value_name("landing").as_str(),
+            )
+            .into_struct_value()
+    }
+
+    ///
+    /// Builds an error throwing block sequence.
+    ///
+    /// The block is removed before the optimization if nothing branches to it.
+    ///
+    pub fn build_throw_block(&mut self, policy: LongReturnPolicy) {
+        self.set_basic_block(self.function().throw_block);
+        self.build_throw_epilogue(policy);
+    }
+
+    ///
+    /// Removes the throw and catch blocks which are not referenced, along with the blocks only
+    /// reachable from them.
+    ///
+    /// Such blocks are appended to every function, but many small functions never throw, so their
+    /// exception handling sequences are dropped if unused. The blocks may be referenced by the
+    /// code emitted after them, so they are only removed once the translation is complete, and
+    /// the function throw and catch blocks must not be used afterwards.
+    ///
+    /// Returns whether any block has been removed.
+    ///
+    fn remove_unreferenced_exception_blocks(&mut self) -> bool {
+        let mut protected = Vec::with_capacity(self.functions.len() * 2);
+        let mut candidates = Vec::with_capacity(self.functions.len() * 2);
+        for function in self.functions.values() {
+            protected.push(function.entry_block);
+            protected.push(function.return_block);
+            candidates.push(function.throw_block);
+            candidates.push(function.catch_block);
+        }
+
+        let mut removed = Vec::new();
+        while let Some(block) = candidates.pop() {
+            if removed.contains(&block)
+                || protected.contains(&block)
+                || block.get_first_use().is_some()
+            {
+                continue;
+            }
+
+            if let Some(terminator) = block.get_terminator() {
+                for index in 0..terminator.get_num_operands() {
+                    if let Some(successor) = terminator
+                        .get_operand(index)
+                        .and_then(|operand| operand.right())
+                    {
+                        candidates.push(successor);
+                    }
+                }
+            }
+            self.cold_blocks.retain(|cold_block| *cold_block != block);
+            unsafe {
+                block
+                    .delete()
+                    .expect("The function block is always attached");
+            }
+            removed.push(block);
+        }
+        !removed.is_empty()
+    }
+
+    ///
+    /// Builds the exception rethrowing sequence shared by the throw and catch blocks.
+    ///
+    /// With `LongReturnPolicy::Return`, the long return flag is checked first, and the function
+    /// returns normally if it is set.
+    ///
+    fn build_throw_epilogue(&mut self, policy: LongReturnPolicy) {
+        if let LongReturnPolicy::Return = policy {
+            let long_return_flag_pointer = self.access_memory_reserved(
+                self.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_LONG_RETURN * compiler_common::SIZE_FIELD)
+                        as u64,
+                ),
+                AddressSpace::Heap,
+                "long_return_flag_pointer",
+            );
+            let long_return_flag = self.build_load(long_return_flag_pointer, "long_return_flag");
+            let is_long_return_flag_set = self.builder.build_int_compare(
+                inkwell::IntPredicate::EQ,
+                long_return_flag.into_int_value(),
+                self.field_const(1),
+                self.value_name("is_long_return_flag_set").as_str(),
+            );
+            self.build_if(is_long_return_flag_set, "long_return", |context| {
+                context.build_unconditional_branch(context.function().return_block);
+                Ok(())
+            })
+            .expect("Always valid");
+        }
+
+        if self.is_shared_throw_enabled {
+            let function = self.shared_throw_function();
+            self.build_call(function, &[], Self::FUNCTION_SHARED_THROW);
+        } else {
+            self.build_call(
+                self.runtime.cxa_throw,
+                self.cxa_throw_arguments().as_slice(),
+                compiler_common::LLVM_FUNCTION_CXA_THROW,
+            );
+        }
+        self.build_unreachable();
+    }
+
+    ///
+    /// Returns the shared throw function, declaring and defining it on the first request.
+    ///
+    /// The function is cold and never inlined, so the throw sequence is emitted once per module.
+    ///
+    fn shared_throw_function(&self) -> inkwell::values::FunctionValue<'ctx> {
+        if let Some(function) = self.module.get_function(Self::FUNCTION_SHARED_THROW) {
+            return function;
+        }
+
+        let function = self.module.add_function(
+            Self::FUNCTION_SHARED_THROW,
+            self.llvm.void_type().fn_type(&[], false),
+            Some(inkwell::module::Linkage::Private),
+        );
+        for attribute in ["cold", "noinline", "noreturn"] {
+            function.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                self.llvm.create_enum_attribute(
+                    inkwell::attributes::Attribute::get_named_enum_kind_id(attribute),
+                    0,
+                ),
+            );
+        }
+
+        let builder = self.llvm.create_builder();
+        let entry_block = self
+            .llvm
+            .append_basic_block(function, self.value_name("entry").as_str());
+        builder.position_at_end(entry_block);
+        builder.build_call(
+            self.runtime.cxa_throw,
+            self.cxa_throw_arguments().as_slice(),
+            self.value_name(compiler_common::LLVM_FUNCTION_CXA_THROW)
+                .as_str(),
+        );
+        builder.build_unreachable();
+        function
+    }
+
+    ///
+    /// Returns the exception throwing function arguments, which are unused on this target.
+    ///
+    fn cxa_throw_arguments(&self) -> Vec<inkwell::values::BasicValueEnum<'ctx>> {
+        vec![
+            self.integer_type(compiler_common::BITLENGTH_BYTE)
+                .ptr_type(AddressSpace::Stack.into())
+                .const_null()
+                .as_basic_value_enum();
+            3
+        ]
+    }
+
+    ///
+    /// Returns the pointer to the deploy code variable of the immutable `key`, declaring it on
+    /// first use.
+    ///
+    /// The immutables are buffered until the construction succeeds, see
+    /// `build_immutables_flush`.
+    ///
+    pub fn immutable_pointer(&mut self, key: &str) -> inkwell::values::PointerValue<'ctx> {
+        self.immutables.insert(key.to_owned());
+        self.get_global_field(format!("{}{}", Self::GLOBAL_IMMUTABLE_PREFIX, key).as_str())
+    }
+
+    ///
+    /// Writes the immutables buffered by the deploy code to the storage, where the runtime code
+    /// loads them from.
+    ///
+    /// Must be built on the constructor return path once the deploy code has been translated,
+    /// so every immutable is known, and they are only written if the construction succeeds.
+    ///
+    pub fn build_immutables_flush(&mut self) -> anyhow::Result<()> {
+        for key in self.immutables.clone().into_iter() {
+            let pointer = self.immutable_pointer(key.as_str());
+            let value = self
+                .build_load(pointer, "immutable_flush_value")
+                .into_int_value();
+            let position =
+                self.field_const_hex(compiler_common::keccak256(key.as_bytes()).as_str())?;
+            self.build_storage_store(value, position, self.field_const(0), "immutable_flush")?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Builds the contract exit of `kind`, passing `size` bytes of the heap at `offset` to the
+    /// parent if the kind has data.
+    ///
+    /// The successful exits return to the caller via the long return, and the failing ones
+    /// throw. The `offset` and `size` are ignored by the kinds without data.
+    ///
+    /// In the deploy code, the returned data is the runtime code to deploy, but the runtime code
+    /// is deployed by the system on this target. So the deploy code return discards the data and
+    /// only signals the successful construction, returning empty data. The long return reaches
+    /// the constructor return block, where the immutables are written to the storage.
+    ///
+    pub fn build_exit(
+        &mut self,
+        kind: ExitKind,
+        offset: inkwell::values::IntValue<'ctx>,
+        size: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let is_deploy_return = kind == ExitKind::Return && self.code_type == Some(CodeType::Deploy);
+        if kind.has_data() && !is_deploy_return {
+            let source = self.access_memory(
+                offset,
+                AddressSpace::Heap,
+                format!("{}_source_pointer", kind).as_str(),
+            );
+            let destination = self.access_memory(
+                self.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+                ),
+                AddressSpace::Parent,
+                format!("{}_destination_pointer", kind).as_str(),
+            );
+
+            self.write_header_size(size, AddressSpace::Parent);
+            self.build_memcpy(
+                IntrinsicFunction::MemoryCopyToParent,
+                destination,
+                source,
+                size,
+                format!("{}_memcpy_to_parent", kind).as_str(),
+            );
+        } else {
+            self.write_header_size(self.field_const(0), AddressSpace::Parent);
+        }
+
+        if kind.is_successful() {
+            self.build_long_return()
+        } else {
+            self.build_unconditional_branch(self.try_function()?.throw_block);
+            Ok(())
+        }
+    }
+
+    ///
+    /// Builds the long return sequence.
+    ///
+    /// The upper level functions return normally, whereas the nested ones throw with the long
+    /// return flag set.
+    ///
+    pub(crate) fn build_long_return(&mut self) -> anyhow::Result<()> {
+        let function = self.try_function()?;
+        let (return_block, throw_block) = (function.return_block, function.throw_block);
+
+        let current_name = function.name.as_str();
+        if current_name == compiler_common::LLVM_FUNCTION_ENTRY
+            || current_name == compiler_common::LLVM_FUNCTION_CONSTRUCTOR
+            || current_name == compiler_common::LLVM_FUNCTION_SELECTOR
+        {
+            self.build_unconditional_branch(return_block);
+        } else {
+            let long_return_flag_pointer = self.access_memory_reserved(
+                self.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_LONG_RETURN * compiler_common::SIZE_FIELD)
+                        as u64,
+                ),
+                AddressSpace::Heap,
+                "long_return_flag_pointer",
+            );
+            self.build_store(long_return_flag_pointer, self.field_const(1));
+            self.build_unconditional_branch(throw_block);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Builds a debug trace of `value` marked with the `tag` hash.
+    ///
+    /// Emits nothing if the debug tracer is unset.
+    ///
+    /// The contract tracer input overwrites the child memory header and the first two data
+    /// words, which hold the return data of the last external call, so they are saved before
+    /// the trace and restored after it. The tracer contract must return no data.
+    ///
+    /// # Errors
+    /// If the target does not provide the intrinsics used by the tracer, or the tracer contract
+    /// address is invalid.
+    ///
+    pub fn build_debug_trace(
+        &mut self,
+        value: inkwell::values::IntValue<'ctx>,
+        tag: &str,
+    ) -> anyhow::Result<()> {
+        let debug_tracer = match self.debug_tracer {
+            Some(ref debug_tracer) => debug_tracer.to_owned(),
+            None => return Ok(()),
+        };
+
+        let tag_hash = compiler_common::keccak256(tag.as_bytes());
+        let tag_hash = self.field_const_hex(tag_hash.as_str())?;
+
+        match debug_tracer {
+            DebugTracer::Event => {
+                let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::Event)?;
+                let event_initializer = self.builder.build_int_add(
+                    self.field_const(1),
+                    self.field_const(
+                        (compiler_common::SIZE_FIELD << compiler_common::BITLENGTH_X32) as u64,
+                    ),
+                    self.value_name("debug_trace_event_initializer").as_str(),
+                );
+                self.build_call(
+                    intrinsic,
+                    &[
+                        event_initializer.as_basic_value_enum(),
+                        tag_hash.as_basic_value_enum(),
+                        self.field_const(1).as_basic_value_enum(),
+                    ],
+                    "debug_trace_event_init_with_tag",
+                );
+                self.build_call(
+                    intrinsic,
+                    &[
+                        value.as_basic_value_enum(),
+                        self.field_const(0).as_basic_value_enum(),
+                        self.field_const(0).as_basic_value_enum(),
+                    ],
+                    "debug_trace_event_value",
+                );
+            }
+            DebugTracer::Contract(address) => {
+                let address = self.field_const_hex(address.as_str())?;
+                let far_call = self.try_get_intrinsic_function(IntrinsicFunction::FarCall)?;
+
+                let child_offset_data = self.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+                );
+                let child_pointer_tag =
+                    self.access_memory(child_offset_data, AddressSpace::Child, "debug_trace_tag");
+                let child_offset_value = self.builder.build_int_add(
+                    child_offset_data,
+                    self.field_const(compiler_common::SIZE_FIELD as u64),
+                    self.value_name("debug_trace_child_offset_value").as_str(),
+                );
+                let child_pointer_value = self.access_memory(
+                    child_offset_value,
+                    AddressSpace::Child,
+                    "debug_trace_value",
+                );
+
+                let saved_header = self.read_header(AddressSpace::Child);
+                let saved_tag_word = self.build_load(child_pointer_tag, "debug_trace_saved_tag");
+                let saved_value_word =
+                    self.build_load(child_pointer_value, "debug_trace_saved_value");
+
+                self.with_child_context("debug_trace", |context| {
+                    context.write_header_size(
+                        context.field_const((compiler_common::SIZE_FIELD * 2) as u64),
+                        AddressSpace::Child,
+                    );
+                    context.build_store(child_pointer_tag, tag_hash);
+                    context.build_store(child_pointer_value, value);
+
+                    let call_definition = CallDefinition::new(address).encode(context);
+                    context.build_call(
+                        far_call,
+                        &[call_definition.as_basic_value_enum()],
+                        "debug_trace_call_external",
+                    );
+                    Ok(())
+                })?;
+
+                self.write_header(saved_header, AddressSpace::Child);
+                self.build_store(child_pointer_tag, saved_tag_word);
+                self.build_store(child_pointer_value, saved_value_word);
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Builds a storage store intrinsic call writing `value` to the slot `position`.
+    ///
+    /// If the storage journal is enabled, the old value of the slot is loaded before the store,
+    /// and the `sstore.slot`, `sstore.old`, and `sstore.new` debug traces are emitted after it.
+    ///
+    /// # Errors
+    /// If the target does not provide the storage intrinsics, or the debug trace fails.
+    ///
+    pub fn build_storage_store(
+        &mut self,
+        value: inkwell::values::IntValue<'ctx>,
+        position: inkwell::values::IntValue<'ctx>,
+        is_external_storage: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<()> {
+        let old_value = if self.is_storage_journal_enabled {
+            let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?;
+            let old_value = self
+                .build_call(
+                    intrinsic,
+                    &[
+                        position.as_basic_value_enum(),
+                        is_external_storage.as_basic_value_enum(),
+                    ],
+                    "storage_journal_old_value",
+                )
+                .expect("Contract storage always returns a value")
+                .into_int_value();
+            Some(old_value)
+        } else {
+            None
+        };
+
+        let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::StorageStore)?;
+        self.build_call(
+            intrinsic,
+            &[
+                value.as_basic_value_enum(),
+                position.as_basic_value_enum(),
+                is_external_storage.as_basic_value_enum(),
+            ],
+            name,
+        );
+
+        if let Some(old_value) = old_value {
+            for (value, tag) in [
+                (position, "sstore.slot"),
+                (old_value, "sstore.old"),
+                (value, "sstore.new"),
+            ] {
+                self.build_debug_trace(value, tag)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the pointer to the module-level field variable `name`, declaring it on first use.
+    ///
+    /// The variable is zero-initialized and lives in the stack address space.
+    ///
+    pub fn get_global_field(&self, name: &str) -> inkwell::values::PointerValue<'ctx> {
+        let global = match self.module.get_global(name) {
+            Some(global) => global,
+            None => {
+                let global = self.module.add_global(
+                    self.field_type(),
+                    Some(AddressSpace::Stack.into()),
+                    name,
+                );
+                global.set_linkage(inkwell::module::Linkage::Private);
+                global.set_initializer(&self.field_const(0));
+                global.set_alignment(compiler_common::SIZE_FIELD as u32);
+                global
+            }
+        };
+
+        global.as_pointer_value()
+    }
+
+    ///
+    /// Saves the return data size of the last external call.
+    ///
+    /// Must be called by every lowering which changes the child memory state.
+    ///
+    pub fn write_return_data_size(&self, size: inkwell::values::IntValue<'ctx>) {
+        let pointer = self.get_global_field(Self::GLOBAL_RETURN_DATA_SIZE);
+        self.build_store(pointer, size);
+    }
+
+    ///
+    /// Saves the return data size from the child memory header after an external call.
+    ///
+    pub fn write_return_data_size_from_child(&self) {
+        let size = self.read_header(AddressSpace::Child).size(self);
+        self.write_return_data_size(size);
+    }
+
+    ///
+    /// Returns the return data size of the last external call.
+    ///
+    pub fn read_return_data_size(&self) -> inkwell::values::IntValue<'ctx> {
+        let pointer = self.get_global_field(Self::GLOBAL_RETURN_DATA_SIZE);
+        self.build_load(pointer, "return_data_size")
+            .into_int_value()
+    }
+
+    ///
+    /// Hashes `input_size` bytes already written to the child memory data section.
+    ///
+    /// The hashing backend is selected by the target capabilities.
+    ///
+    /// # Errors
+    /// If the target does not provide the intrinsic of the selected backend.
+    ///
+    pub fn build_keccak256_child(
+        &self,
+        input_size: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>> {
+        let child_offset_data = self.field_const(
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+        );
+
+        match self.capabilities.keccak256 {
+            Keccak256::Precompile(ref address) => {
+                let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::StaticCall)?;
+                let call_definition =
+                    CallDefinition::new(self.field_const_hex(address.as_str())?).encode(self);
+                self.build_call(
+                    intrinsic,
+                    &[call_definition.as_basic_value_enum()],
+                    format!("{}_call_external", name).as_str(),
+                );
+
+                let child_pointer_data =
+                    self.access_memory(child_offset_data, AddressSpace::Child, name);
+                Ok(self.build_load(child_pointer_data, name))
+            }
+            Keccak256::Intrinsic => {
+                let intrinsic = self.try_get_intrinsic_function(IntrinsicFunction::Keccak256)?;
+                self.build_call(
+                    intrinsic,
+                    &[
+                        child_offset_data.as_basic_value_enum(),
+                        input_size.as_basic_value_enum(),
+                    ],
+                    name,
+                )
+                .ok_or_else(|| anyhow::anyhow!("The keccak256 intrinsic must return a value"))
+            }
+        }
+    }
+
+    ///
+    /// Reads the header from the specified memory.
+    ///
+    pub fn read_header(&self, address_space: AddressSpace) -> HeaderCodec<'ctx> {
+        let header_pointer = self.access_memory_reserved(
+            self.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
+            ),
+            address_space,
+            "header_pointer",
+        );
+        let value = self
+            .build_load(header_pointer, "header_value")
+            .into_int_value();
+        HeaderCodec::from_value(value)
+    }
+
+    ///
+    /// Writes the header to the specified memory.
+    ///
+    pub fn write_header(&self, header: HeaderCodec<'ctx>, address_space: AddressSpace) {
+        let header_pointer = self.access_memory_reserved(
+            self.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
+            ),
+            address_space,
+            "header_pointer",
+        );
+        self.build_store(header_pointer, header.value());
+    }
+
+    ///
+    /// Writes the header only containing the data `size` to the specified memory.
+    ///
+    pub fn write_header_size(
+        &self,
+        size: inkwell::values::IntValue<'ctx>,
+        address_space: AddressSpace,
+    ) {
+        let header = HeaderCodec::new(self).set_size(self, size);
+        self.write_header(header, address_space);
+    }
+
+    ///
+    /// Loads `length` big-endian bytes from `pointer` into the lowest bytes of a field value.
+    ///
+    pub fn build_load_be_bytes(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        length: usize,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        Self::check_bytes_length(length)?;
+
+        let word = self
+            .build_load(pointer, "load_be_bytes_word")
+            .into_int_value();
+        if length == compiler_common::SIZE_FIELD {
+            return Ok(word);
+        }
+
+        Ok(self.builder.build_right_shift(
+            word,
+            self.field_const(
+                (compiler_common::BITLENGTH_BYTE * (compiler_common::SIZE_FIELD - length)) as u64,
+            ),
+            false,
+            self.value_name("load_be_bytes_result").as_str(),
+        ))
+    }
+
+    ///
+    /// Stores the lowest `length` bytes of `value` to `pointer` in the big-endian order.
+    ///
+    /// The memory bytes following the stored ones are preserved.
+    ///
+    pub fn build_store_be_bytes(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+        value: inkwell::values::IntValue<'ctx>,
+        length: usize,
+    ) -> anyhow::Result<()> {
+        Self::check_bytes_length(length)?;
+
+        if length == compiler_common::SIZE_FIELD {
+            self.build_store(pointer, value);
+            return Ok(());
+        }
+
+        let original = self
+            .build_load(pointer, "store_be_bytes_original")
+            .into_int_value();
+        let original_mask = self
+            .field_type()
+            .const_all_ones()
+            .const_rshr(self.field_const((compiler_common::BITLENGTH_BYTE * length) as u64));
+        let original_masked = self.builder.build_and(
+            original,
+            original_mask,
+            self.value_name("store_be_bytes_original_masked").as_str(),
+        );
+
+        let value_shifted = self.build_left_align_bytes(value, length);
+        let result = self.builder.build_or(
+            original_masked,
+            value_shifted,
+            self.value_name("store_be_bytes_result").as_str(),
+        );
+        self.build_store(pointer, result);
+
+        Ok(())
+    }
+
+    ///
+    /// Shifts the lowest `length` bytes of `value` to the highest ones.
+    ///
+    pub fn build_left_align_bytes(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+        length: usize,
+    ) -> inkwell::values::IntValue<'ctx> {
+        if length >= compiler_common::SIZE_FIELD {
+            return value;
+        }
+
+        self.builder.build_left_shift(
+            value,
+            self.field_const(
+                (compiler_common::BITLENGTH_BYTE * (compiler_common::SIZE_FIELD - length)) as u64,
+            ),
+            self.value_name("left_aligned_bytes").as_str(),
+        )
+    }
+
+    ///
+    /// Checks that `length` bytes fit into a field value.
+    ///
+    fn check_bytes_length(length: usize) -> anyhow::Result<()> {
+        if length == 0 || length > compiler_common::SIZE_FIELD {
+            anyhow::bail!("Invalid big-endian bytes length {}", length);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Writes the error data to the parent memory.
+    ///
+    /// The error is the bare hash of the compile-time `message`. For the messages built at
+    /// runtime, see `write_error_dynamic`.
+    ///
+    pub fn write_error(&self, message: &'static str) {
+        self.write_header_size(
+            self.field_const(compiler_common::SIZE_X32 as u64),
+            AddressSpace::Parent,
+        );
+
+        let error_hash = compiler_common::keccak256(message.as_bytes());
+        let error_code = self
+            .field_const_hex(error_hash.as_str())
+            .expect("Always valid");
+        let error_code_shifted = self.build_left_align_bytes(error_code, compiler_common::SIZE_X32);
+        let parent_error_code_pointer = self.access_memory(
+            self.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Parent,
+            "parent_error_code_pointer",
+        );
+        self.build_store(parent_error_code_pointer, error_code_shifted);
+    }
+
+    ///
+    /// Panics with `PanicCode::ArithmeticOverflow` if `is_overflow` is true.
+    ///
+    pub fn build_overflow_check(&self, is_overflow: inkwell::values::IntValue<'ctx>, name: &str) {
+        let overflow_block = self.append_basic_block(format!("{}_overflow", name).as_str());
+        let join_block = self.append_basic_block(format!("{}_non_overflow", name).as_str());
+        self.build_conditional_branch(is_overflow, overflow_block, join_block);
+
+        self.set_basic_block(overflow_block);
+        self.build_panic(PanicCode::ArithmeticOverflow);
+
+        self.set_basic_block(join_block);
+    }
+
+    ///
+    /// Writes the `Panic(uint256)` error with the `code` to the parent memory and branches to
+    /// the throw block.
+    ///
+    pub fn build_panic(&self, code: PanicCode) {
+        let data_offset =
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64;
+
+        let selector_pointer = self.access_memory(
+            self.field_const(data_offset),
+            AddressSpace::Parent,
+            "panic_selector_pointer",
+        );
+        let selector = self.build_left_align_bytes(
+            self.field_const(PanicCode::SELECTOR),
+            compiler_common::SIZE_X32,
+        );
+        self.build_store(selector_pointer, selector);
+
+        let code_pointer = self.access_memory(
+            self.field_const(data_offset + compiler_common::SIZE_X32 as u64),
+            AddressSpace::Parent,
+            "panic_code_pointer",
+        );
+        self.build_store(code_pointer, self.field_const(code.code()));
+
+        self.write_header_size(
+            self.field_const((compiler_common::SIZE_X32 + compiler_common::SIZE_FIELD) as u64),
+            AddressSpace::Parent,
+        );
+        self.build_unconditional_branch(self.function().throw_block);
+    }
+
+    ///
+    /// Writes the ABI-encoded error with the `selector` and the heap message at `offset` of
+    /// `length` bytes to the parent memory.
+    ///
+    /// The encoding is emitted once as a shared runtime function.
+    ///
+    pub fn write_error_dynamic(
+        &mut self,
+        selector: inkwell::values::IntValue<'ctx>,
+        offset: inkwell::values::IntValue<'ctx>,
+        length: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let function =
+            self.get_or_build_runtime_function(ErrorEncoder::NAME, ErrorEncoder::build)?;
+
+        self.build_call(
+            function,
+            &[
+                selector.as_basic_value_enum(),
+                offset.as_basic_value_enum(),
+                length.as_basic_value_enum(),
+            ],
+            ErrorEncoder::NAME,
+        );
+        Ok(())
+    }
+
+    ///
+    /// Reverts with the child return data if the contract call `status` flag is zero.
+    ///
+    /// The check is emitted once as a shared runtime function.
+    ///
+    pub fn build_revert_on_failure(
+        &mut self,
+        status: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let function =
+            self.get_or_build_runtime_function(RevertOnFailure::NAME, RevertOnFailure::build)?;
+
+        self.build_invoke(
+            function,
+            &[status.as_basic_value_enum()],
+            RevertOnFailure::NAME,
+        );
+        Ok(())
+    }
+
+    ///
+    /// Locks the nonreentrant mutex storage `slot`, throwing if it is already locked.
+    ///
+    pub fn build_nonreentrant_enter(
+        &mut self,
+        slot: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let function = self.get_or_build_runtime_function(
+            NonreentrantGuard::NAME_ENTER,
+            NonreentrantGuard::build,
+        )?;
+        self.build_invoke(
+            function,
+            &[slot.as_basic_value_enum()],
+            NonreentrantGuard::NAME_ENTER,
+        );
+        Ok(())
+    }
+
+    ///
+    /// Unlocks the nonreentrant mutex storage `slot`.
+    ///
+    pub fn build_nonreentrant_exit(
+        &mut self,
+        slot: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let function = self.get_or_build_runtime_function(
+            NonreentrantGuard::NAME_EXIT,
+            NonreentrantGuard::build,
+        )?;
+        self.build_call(
+            function,
+            &[slot.as_basic_value_enum()],
+            NonreentrantGuard::NAME_EXIT,
+        );
+        Ok(())
+    }
+
+    ///
+    /// Approves the `spender` to take the token `amount` from the current contract.
+    ///
+    /// Throws if the `token` contract rejects the approval.
+    ///
+    pub fn build_paymaster_approve(
+        &mut self,
+        token: inkwell::values::IntValue<'ctx>,
+        spender: inkwell::values::IntValue<'ctx>,
+        amount: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        let function =
+            self.get_or_build_runtime_function(PaymasterFlow::NAME_APPROVE, PaymasterFlow::build)?;
+        self.build_invoke(
+            function,
+            &[
+                token.as_basic_value_enum(),
+                spender.as_basic_value_enum(),
+                amount.as_basic_value_enum(),
+            ],
+            PaymasterFlow::NAME_APPROVE,
+        );
+        Ok(())
+    }
+
+    ///
+    /// Calls the standard library `function` with the field `arguments`, returning the result.
+    ///
+    /// The function is declared and defined in the current module on the first request.
+    ///
+    pub fn build_stdlib_call(
+        &mut self,
+        function: StdlibFunction,
+        arguments: &[inkwell::values::IntValue<'ctx>],
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        if arguments.len() != function.input_size() {
+            anyhow::bail!(
+                "The standard library function `{}` expects {} arguments, found {}",
+                function.name(),
+                function.input_size(),
+                arguments.len()
+            );
+        }
+
+        let value = self.get_or_build_runtime_function(function.name(), |context| {
+            let mut item = function;
+            WriteLLVM::<D>::declare(&mut item, context)?;
+            WriteLLVM::<D>::into_llvm(item, context)
+        })?;
+        let arguments: Vec<inkwell::values::BasicValueEnum<'ctx>> = arguments
+            .iter()
+            .map(|argument| argument.as_basic_value_enum())
+            .collect();
+        let result = self
+            .build_invoke(value, arguments.as_slice(), function.name())
+            .expect("Always returns a value")
+            .into_int_value();
+        Ok(result)
+    }
+
+    ///
+    /// Compares `length` bytes at the `lhs` and `rhs` memory pointers, returning 1 if they are
+    /// equal and 0 otherwise.
+    ///
+    /// The pointers may be in different address spaces, e.g. to compare the return data with the
+    /// expected value on the heap. The comparison is emitted once per pair of address spaces as
+    /// a shared runtime function.
+    ///
+    pub fn build_memcmp(
+        &mut self,
+        lhs: inkwell::values::PointerValue<'ctx>,
+        rhs: inkwell::values::PointerValue<'ctx>,
+        length: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let memory_compare = MemoryCompare::new(
+            AddressSpace::try_from(lhs.get_type().get_address_space())?,
+            AddressSpace::try_from(rhs.get_type().get_address_space())?,
+        );
+        let name = memory_compare.name();
+
+        let function = self.get_or_build_runtime_function(name.as_str(), |context| {
+            memory_compare.build(context)
+        })?;
+        let lhs_offset = self.builder.build_ptr_to_int(
+            lhs,
+            self.field_type(),
+            self.value_name("memcmp_lhs_offset").as_str(),
+        );
+        let rhs_offset = self.builder.build_ptr_to_int(
+            rhs,
+            self.field_type(),
+            self.value_name("memcmp_rhs_offset").as_str(),
+        );
+        let result = self
+            .build_invoke(
+                function,
+                &[
+                    lhs_offset.as_basic_value_enum(),
+                    rhs_offset.as_basic_value_enum(),
+                    length.as_basic_value_enum(),
+                ],
+                name.as_str(),
+            )
+            .expect("Always returns a value")
+            .into_int_value();
+        Ok(result)
+    }
+
+    ///
+    /// Copies the length-prefixed bytes at `source_offset` in the `source` memory to
+    /// `destination_offset` in the `destination` memory, zero-padding the data.
+    ///
+    /// Returns the size of the written region, including the length word and padding.
+    ///
+    pub fn build_bytes_copy(
+        &mut self,
+        source: AddressSpace,
+        source_offset: inkwell::values::IntValue<'ctx>,
+        destination: AddressSpace,
+        destination_offset: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let bytes_copy = BytesCopy::new(source, destination)?;
+        let name = bytes_copy.name();
+
+        let function =
+            self.get_or_build_runtime_function(name.as_str(), |context| bytes_copy.build(context))?;
+        let size = self
+            .build_invoke(
+                function,
+                &[
+                    source_offset.as_basic_value_enum(),
+                    destination_offset.as_basic_value_enum(),
+                ],
+                name.as_str(),
+            )
+            .expect("Always returns the size")
+            .into_int_value();
+        Ok(size)
+    }
+
+    ///
+    /// Returns a field type constant.
+    ///
+    pub fn field_const(&self, value: u64) -> inkwell::values::IntValue<'ctx> {
+        self.field_type().const_int(value, false)
+    }
+
+    ///
+    /// Returns a field type constant from the 64-bit words, the least significant word first.
+    ///
+    pub fn field_const_words(
+        &self,
+        words: [u64; compiler_common::BITLENGTH_FIELD / 64],
+    ) -> inkwell::values::IntValue<'ctx> {
+        self.field_type().const_int_arbitrary_precision(&words)
+    }
+
+    ///
+    /// Returns a field type constant from a big unsigned integer.
+    ///
+    /// # Errors
+    /// If the value does not fit into the field type.
+    ///
+    pub fn field_const_uint(
+        &self,
+        value: &num::BigUint,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        if value.bits() > compiler_common::BITLENGTH_FIELD as u64 {
+            anyhow::bail!(
+                "The constant {} does not fit into {} bits",
+                value,
+                compiler_common::BITLENGTH_FIELD
+            );
+        }
+
+        let mut words = [0u64; compiler_common::BITLENGTH_FIELD / 64];
+        for (word, digit) in words.iter_mut().zip(value.iter_u64_digits()) {
+            *word = digit;
+        }
+        Ok(self.field_const_words(words))
+    }
+
+    ///
+    /// Returns a field type constant from a decimal string.
+    ///
+    /// The constants are interned in the context constant pool.
+    ///
+    pub fn field_const_dec(&self, value: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+            anyhow::bail!("Invalid decimal constant `{}`", value);
+        }
+
+        self.constant_pool.get_or_try_insert(value, || {
+            self.field_type()
+                .const_int_from_string(value, inkwell::types::StringRadix::Decimal)
+                .ok_or_else(|| anyhow::anyhow!("Invalid decimal constant `{}`", value))
+        })
+    }
+
+    ///
+    /// Returns a field type constant from a hexadecimal string, with or without the `0x` prefix.
+    ///
+    /// The constants are interned in the context constant pool.
+    ///
+    pub fn field_const_hex(&self, value: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            anyhow::bail!("Invalid hexadecimal constant `{}`", value);
+        }
+
+        // The hexadecimal keys are prefixed, so they never collide with the decimal ones.
+        let key = format!("0x{}", digits);
+        self.constant_pool.get_or_try_insert(key.as_str(), || {
+            self.field_type()
+                .const_int_from_string(digits, inkwell::types::StringRadix::Hexadecimal)
+                .ok_or_else(|| anyhow::anyhow!("Invalid hexadecimal constant `{}`", value))
+        })
+    }
+
+    ///
+    /// Returns a field type constant from a `0x`-prefixed hexadecimal or a decimal string.
+    ///
+    /// # Errors
+    /// If the string without the prefix contains hexadecimal digits, since it is ambiguous
+    /// whether it is a hexadecimal value with the prefix omitted or a malformed decimal one.
+    ///
+    pub fn field_const_auto(&self, value: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        if value.starts_with("0x") {
+            return self.field_const_hex(value);
+        }
+        if !value.is_empty()
+            && value.bytes().all(|byte| byte.is_ascii_hexdigit())
+            && !value.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            anyhow::bail!(
+                "The constant `{}` is ambiguous: use the `0x` prefix for hexadecimal values",
+                value
+            );
+        }
+        self.field_const_dec(value)
+    }
+
+    ///
+    /// Returns the void type.
+    ///
+    pub fn void_type(&self) -> inkwell::types::VoidType<'ctx> {
+        self.llvm.void_type()
+    }
+
+    ///
+    /// Returns the integer type of the specified bitlength.
+    ///
+    pub fn integer_type(&self, bitlength: usize) -> inkwell::types::IntType<'ctx> {
+        self.llvm.custom_width_int_type(bitlength as u32)
+    }
+
+    ///
+    /// Returns the integer type of the specified bitlength, validating it.
+    ///
+    /// The bitlength must be either 1 or a multiple of the byte bitlength not exceeding the field.
+    ///
+    pub fn int_type_checked(
+        &self,
+        bitlength: usize,
+    ) -> anyhow::Result<inkwell::types::IntType<'ctx>> {
+        if bitlength == 0
+            || bitlength > compiler_common::BITLENGTH_FIELD
+            || (bitlength != compiler_common::BITLENGTH_BOOLEAN
+                && bitlength % compiler_common::BITLENGTH_BYTE != 0)
+        {
+            anyhow::bail!("Invalid integer type bitlength {}", bitlength);
+        }
+
+        Ok(self.integer_type(bitlength))
+    }
+
+    ///
+    /// Extends `value` to the field type.
+    ///
+    /// Field values are returned as is.
+    ///
+    pub fn extend_to_field(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+        is_signed: bool,
+    ) -> inkwell::values::IntValue<'ctx> {
+        if value.get_type().get_bit_width() == compiler_common::BITLENGTH_FIELD as u32 {
+            return value;
+        }
+
+        if is_signed {
+            self.builder.build_int_s_extend(
+                value,
+                self.field_type(),
+                self.value_name("extended_to_field_signed").as_str(),
+            )
+        } else {
+            self.builder.build_int_z_extend(
+                value,
+                self.field_type(),
+                self.value_name("extended_to_field").as_str(),
+            )
+        }
+    }
+
+    ///
+    /// Truncates the field `value` to the integer type of the specified bitlength.
+    ///
+    pub fn truncate_from_field(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+        bitlength: usize,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let r#type = self.int_type_checked(bitlength)?;
+        if value.get_type().get_bit_width() == r#type.get_bit_width() {
+            return Ok(value);
+        }
+
+        Ok(self.builder.build_int_truncate(
+            value,
+            r#type,
+            self.value_name("truncated_from_field").as_str(),
+        ))
+    }
+
+    ///
+    /// Cleans up the upper bits of the field `value` holding an integer of the specified bitlength.
+    ///
+    /// The unsigned values are masked, and the signed ones are sign-extended. The truncation and
+    /// extension pair is used instead of an explicit mask, so LLVM folds chained cleanups.
+    ///
+    pub fn cleanup_field(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+        bitlength: usize,
+        is_signed: bool,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let truncated = self.truncate_from_field(value, bitlength)?;
+        Ok(self.extend_to_field(truncated, is_signed))
+    }
+
+    ///
+    /// Converts `value` to the boolean type.
+    ///
+    /// The boolean values are returned as is, and the extended ones are unwrapped, so no
+    /// redundant comparisons are emitted for chained logical operations.
+    ///
+    pub fn as_bool(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx> {
+        if value.get_type().get_bit_width() == compiler_common::BITLENGTH_BOOLEAN as u32 {
+            return value;
+        }
+
+        if let Some(original) = Self::unwrap_bool_extension(value) {
+            return original;
+        }
+
+        self.builder.build_int_compare(
+            inkwell::IntPredicate::NE,
+            value,
+            value.get_type().const_zero(),
+            self.value_name("as_bool").as_str(),
+        )
+    }
+
+    ///
+    /// Converts `value` to the field type holding either 0 or 1.
+    ///
+    /// The values which are already the extended booleans are returned as is.
+    ///
+    pub fn as_field_bool(
+        &self,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> inkwell::values::IntValue<'ctx> {
+        if value.get_type().get_bit_width() == compiler_common::BITLENGTH_FIELD as u32
+            && Self::unwrap_bool_extension(value).is_some()
+        {
+            return value;
+        }
+
+        let value = self.as_bool(value);
+        self.builder.build_int_z_extend(
+            value,
+            self.field_type(),
+            self.value_name("as_field_bool").as_str(),
+        )
+    }
+
+    ///
+    /// Returns the original boolean if `value` is its zero extension.
+    ///
+    pub fn unwrap_bool_extension(
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> Option<inkwell::values::IntValue<'ctx>> {
+        let instruction = value.as_instruction()?;
+        if instruction.get_opcode() != inkwell::values::InstructionOpcode::ZExt {
+            return None;
+        }
+
+        match instruction
+            .get_operand(0)
+            .and_then(|operand| operand.left())
+        {
+            Some(inkwell::values::BasicValueEnum::IntValue(original))
+                if original.get_type().get_bit_width()
+                    == compiler_common::BITLENGTH_BOOLEAN as u32 =>
+            {
+                Some(original)
+            }
+            _ => None,
+        }
     }
 
     ///
@@ -912,19 +4985,117 @@ where
     ///
     /// Returns the memory pointer to `address_space` at `offset` bytes.
     ///
+    /// In the memory safety mode, the heap accesses are checked against the reserved region.
+    ///
     pub fn access_memory(
         &self,
         offset: inkwell::values::IntValue<'ctx>,
         address_space: AddressSpace,
         name: &str,
+    ) -> inkwell::values::PointerValue<'ctx> {
+        if self.is_memory_safety_enabled && address_space == AddressSpace::Heap {
+            self.build_reserved_region_check(offset, name);
+        }
+
+        self.access_memory_reserved(offset, address_space, name)
+    }
+
+    ///
+    /// Returns a pointer to the memory without the reserved region check.
+    ///
+    /// Only used for the crate-owned reserved slots, e.g. the header and long return flag.
+    ///
+    pub(crate) fn access_memory_reserved(
+        &self,
+        offset: inkwell::values::IntValue<'ctx>,
+        address_space: AddressSpace,
+        name: &str,
     ) -> inkwell::values::PointerValue<'ctx> {
         self.builder.build_int_to_ptr(
             offset,
             self.field_type().ptr_type(address_space.into()),
-            name,
+            self.value_name(name).as_str(),
         )
     }
 
+    ///
+    /// Builds a check that throws if the heap `offset` is within the reserved region.
+    ///
+    /// The check is omitted for the constant offsets known to be outside of the region.
+    ///
+    fn build_reserved_region_check(&self, offset: inkwell::values::IntValue<'ctx>, name: &str) {
+        let reserved_size = Self::HEAP_RESERVED_SIZE as u64;
+        if matches!(offset.get_zero_extended_constant(), Some(constant) if constant >= reserved_size)
+        {
+            return;
+        }
+
+        let is_safe = self.builder.build_int_compare(
+            inkwell::IntPredicate::UGE,
+            offset,
+            offset.get_type().const_int(reserved_size, false),
+            self.value_name(format!("{}_is_safe", name).as_str())
+                .as_str(),
+        );
+        let safe_block = self.append_basic_block(format!("{}_safe", name).as_str());
+        self.build_conditional_branch(is_safe, safe_block, self.function().throw_block);
+        self.set_basic_block(safe_block);
+    }
+
+    ///
+    /// Invalidates the contract context value with the `identifier` in the module, so that its
+    /// getters reload it at every use.
+    ///
+    /// The cached values are loaded in the function entry blocks, where they are not affected
+    /// by the setters in the function or its callees. So the values set anywhere in the module
+    /// must be invalidated before any of their getters are translated, e.g. by the frontends
+    /// scanning the setters in advance. The setters invalidate their values as well.
+    ///
+    /// # Errors
+    /// If the value has already been cached by a getter.
+    ///
+    pub fn invalidate_context_value(&mut self, identifier: u64) -> anyhow::Result<()> {
+        if self.cached_context_values.contains(&identifier) {
+            anyhow::bail!(
+                "The context value {} is invalidated after it has been cached",
+                identifier
+            );
+        }
+        self.invalidated_context_values.insert(identifier);
+        Ok(())
+    }
+
+    ///
+    /// Whether the contract context value with the `identifier` has been invalidated in the
+    /// module.
+    ///
+    pub fn is_context_value_invalidated(&self, identifier: u64) -> bool {
+        self.invalidated_context_values.contains(&identifier)
+    }
+
+    ///
+    /// Caches the contract context `value` with the `identifier` in the current function.
+    ///
+    /// # Errors
+    /// If the value has been invalidated, or the current function has no EVM data.
+    ///
+    pub fn cache_context_value(
+        &mut self,
+        identifier: u64,
+        value: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        if self.is_context_value_invalidated(identifier) {
+            anyhow::bail!("The context value {} has been invalidated", identifier);
+        }
+        self.try_function_mut()?
+            .evm_data
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("The function has no EVM data"))?
+            .cache_context_value(identifier, value);
+        self.cached_context_values.insert(identifier);
+        Ok(())
+    }
+
     ///
     /// Returns a contract context value.
     ///
@@ -967,3 +5138,768 @@ where
             .expect("The EVM data must have been initialized")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::RwLock;
+
+    use inkwell::attributes::AttributeLoc;
+    use inkwell::types::BasicType;
+    use inkwell::values::BasicValue;
+
+    use crate::context::address_space::AddressSpace;
+    use crate::context::argument::Argument;
+    use crate::context::cache::Cache;
+    use crate::context::error::Error;
+    use crate::context::exit_kind::ExitKind;
+    use crate::context::function::constructor::Constructor;
+    use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+    use crate::context::gas_report::GasReport;
+    use crate::context::long_return_policy::LongReturnPolicy;
+    use crate::context::naming_policy::NamingPolicy;
+    use crate::context::operands::Operands;
+    use crate::context::panic_code::PanicCode;
+    use crate::context::privileged_operation::PrivilegedOperation;
+    use crate::dump_flag::DumpFlag;
+    use crate::Dependency;
+    use crate::WriteLLVM;
+
+    use super::Context;
+
+    ///
+    /// The test dependency manager, which rejects every request.
+    ///
+    struct TestDependency {}
+
+    impl Dependency for TestDependency {
+        fn resolve_library(_project: Arc<RwLock<Self>>, path: &str) -> anyhow::Result<String> {
+            anyhow::bail!("The library `{}` cannot be resolved in tests", path)
+        }
+    }
+
+    ///
+    /// Creates the target machine of the test contexts.
+    ///
+    fn target_machine() -> inkwell::targets::TargetMachine {
+        inkwell::targets::Target::initialize_syncvm(
+            &inkwell::targets::InitializationConfig::default(),
+        );
+        inkwell::targets::Target::from_name("syncvm")
+            .expect("Always exists")
+            .create_target_machine(
+                &inkwell::targets::TargetTriple::create("syncvm"),
+                "",
+                "",
+                inkwell::OptimizationLevel::None,
+                inkwell::targets::RelocMode::Default,
+                inkwell::targets::CodeModel::Default,
+            )
+            .expect("Always valid")
+    }
+
+    ///
+    /// Creates the test context with the optimizations disabled.
+    ///
+    fn test_context(llvm: &inkwell::context::Context) -> Context<TestDependency> {
+        Context::<TestDependency>::new(
+            llvm,
+            &target_machine(),
+            inkwell::OptimizationLevel::None,
+            inkwell::OptimizationLevel::None,
+            "test",
+            None,
+            vec![],
+        )
+    }
+
+    ///
+    /// Declares the external `test` function without arguments and return values, and sets it
+    /// as the current one, with the insertion point in its entry block.
+    ///
+    fn with_test_function(context: &mut Context<TestDependency>) {
+        let function_type = context.function_type(0, vec![]);
+        context
+            .add_function(
+                "test",
+                function_type,
+                Some(inkwell::module::Linkage::External),
+            )
+            .expect("Always valid");
+        let function = context
+            .get_function("test")
+            .cloned()
+            .expect("Always exists");
+        context.set_function(function);
+        context.set_basic_block(context.function().entry_block);
+    }
+
+    ///
+    /// Builds and verifies a function with the throw or catch block built with `policy`.
+    ///
+    /// Returns whether the exception block branches to the return block.
+    ///
+    fn exception_block_returns(is_catch: bool, policy: LongReturnPolicy) -> bool {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+        context.build_return(None);
+
+        if is_catch {
+            context.build_catch_block(policy);
+        } else {
+            context.build_throw_block(policy);
+        }
+        let exception_block = if is_catch {
+            context.function().catch_block
+        } else {
+            context.function().throw_block
+        };
+        let exception_terminator = exception_block
+            .get_terminator()
+            .expect("Always terminated")
+            .get_opcode();
+        match policy {
+            LongReturnPolicy::Propagate => assert_eq!(
+                exception_terminator,
+                inkwell::values::InstructionOpcode::Unreachable
+            ),
+            LongReturnPolicy::Return => {
+                assert_eq!(exception_terminator, inkwell::values::InstructionOpcode::Br)
+            }
+        }
+
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+        context.verify().expect("Always valid");
+
+        context.function().return_block.get_first_use().is_some()
+    }
+
+    #[test]
+    fn throw_block_propagate() {
+        assert!(!exception_block_returns(false, LongReturnPolicy::Propagate));
+    }
+
+    #[test]
+    fn throw_block_return() {
+        assert!(exception_block_returns(false, LongReturnPolicy::Return));
+    }
+
+    #[test]
+    fn catch_block_propagate() {
+        assert!(!exception_block_returns(true, LongReturnPolicy::Propagate));
+    }
+
+    #[test]
+    fn catch_block_return() {
+        assert!(exception_block_returns(true, LongReturnPolicy::Return));
+    }
+
+    #[test]
+    fn pointer_alignments_multi_return() {
+        let llvm = inkwell::context::Context::create();
+        let field_type = llvm.custom_width_int_type(compiler_common::BITLENGTH_FIELD as u32);
+        let return_type = llvm
+            .struct_type(vec![field_type.as_basic_type_enum(); 2].as_slice(), false)
+            .ptr_type(AddressSpace::Stack.into());
+        let function_type = return_type.fn_type(
+            &[
+                return_type.as_basic_type_enum(),
+                field_type.as_basic_type_enum(),
+                field_type
+                    .ptr_type(AddressSpace::Heap.into())
+                    .as_basic_type_enum(),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            Context::<TestDependency>::pointer_alignments(function_type),
+            vec![
+                (AttributeLoc::Param(0), compiler_common::SIZE_FIELD as u32),
+                (AttributeLoc::Param(2), 1),
+                (AttributeLoc::Return, compiler_common::SIZE_FIELD as u32),
+            ]
+        );
+    }
+
+    #[test]
+    fn pointer_alignments_single_return() {
+        let llvm = inkwell::context::Context::create();
+        let field_type = llvm.custom_width_int_type(compiler_common::BITLENGTH_FIELD as u32);
+        let function_type = field_type.fn_type(&[field_type.as_basic_type_enum(); 2], false);
+
+        assert!(Context::<TestDependency>::pointer_alignments(function_type).is_empty());
+    }
+
+    #[test]
+    fn pointer_alignments_no_return() {
+        let llvm = inkwell::context::Context::create();
+        let function_type = llvm.void_type().fn_type(
+            &[
+                llvm.i8_type()
+                    .ptr_type(AddressSpace::Parent.into())
+                    .as_basic_type_enum(),
+                llvm.i8_type()
+                    .ptr_type(AddressSpace::Stack.into())
+                    .as_basic_type_enum(),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            Context::<TestDependency>::pointer_alignments(function_type),
+            vec![
+                (AttributeLoc::Param(0), 1),
+                (AttributeLoc::Param(1), compiler_common::SIZE_FIELD as u32),
+            ]
+        );
+    }
+
+    ///
+    /// Creates the test context with the `caller` and `callee` functions declared and their inputs
+    /// recorded.
+    ///
+    /// The input of the `callee` is its `version`, and the `caller` is external, so the module
+    /// is not empty after the optimization.
+    ///
+    fn cached_context(
+        llvm: &inkwell::context::Context,
+        version: u64,
+        is_system_mode: bool,
+    ) -> Context<TestDependency> {
+        let mut context = test_context(llvm);
+        context.set_system_mode(is_system_mode);
+        let function_type = context.function_type(0, vec![]);
+        for (name, linkage) in [
+            ("caller", inkwell::module::Linkage::External),
+            ("callee", inkwell::module::Linkage::Private),
+        ] {
+            context
+                .add_function(name, function_type, Some(linkage))
+                .expect("Always valid");
+        }
+        context.set_function_input("caller", b"caller");
+        context.set_function_input("callee", format!("callee {}", version).as_bytes());
+        context
+    }
+
+    ///
+    /// Translates the declared functions, optimizes the module, and returns the captured cache.
+    ///
+    /// The `caller` invokes the `callee`, which writes its `version` to the return data size.
+    ///
+    fn build_cached_module(context: &mut Context<TestDependency>, version: u64) -> Cache {
+        for name in ["caller", "callee"] {
+            let function = context.get_function(name).cloned().expect("Always exists");
+            context.set_function(function);
+            context.set_basic_block(context.function().entry_block);
+            if name == "caller" {
+                let callee = context.get_function("callee").expect("Always exists").value;
+                context.build_invoke(callee, &[], "callee_call");
+            } else {
+                context.write_return_data_size(context.field_const(version));
+            }
+            context.build_unconditional_branch(context.function().return_block);
+
+            context.build_throw_block(LongReturnPolicy::Propagate);
+            context.build_catch_block(LongReturnPolicy::Propagate);
+            context.set_basic_block(context.function().return_block);
+            context.build_return(None);
+        }
+        context.verify().expect("Always valid");
+        context.optimize();
+        context.cache().cloned().expect("Always captured")
+    }
+
+    #[test]
+    fn cache_restores_unchanged_module() {
+        let llvm = inkwell::context::Context::create();
+        let mut cached = cached_context(&llvm, 1, false);
+        let cache = build_cached_module(&mut cached, 1);
+
+        let mut context = cached_context(&llvm, 1, false);
+        assert!(context.restore_from_cache(&cache).expect("Always valid"));
+        context.verify().expect("Always valid");
+        assert!(context.functions.is_empty());
+        assert!(context.module().get_function("caller").is_some());
+        assert_eq!(
+            context.module().print_to_string().to_string(),
+            cached.module().print_to_string().to_string()
+        );
+    }
+
+    #[test]
+    fn cache_misses_changed_callee() {
+        let llvm = inkwell::context::Context::create();
+        let cache = build_cached_module(&mut cached_context(&llvm, 1, false), 1);
+
+        let mut context = cached_context(&llvm, 2, false);
+        assert!(!context.restore_from_cache(&cache).expect("Always valid"));
+        assert!(context.get_function("callee").is_some());
+
+        let rebuilt = build_cached_module(&mut context, 2);
+        assert_ne!(rebuilt.key, cache.key);
+        assert_ne!(rebuilt.bitcode, cache.bitcode);
+    }
+
+    #[test]
+    fn cache_misses_changed_settings() {
+        let llvm = inkwell::context::Context::create();
+        let cache = build_cached_module(&mut cached_context(&llvm, 1, false), 1);
+
+        let mut context = cached_context(&llvm, 1, true);
+        assert!(!context.restore_from_cache(&cache).expect("Always valid"));
+        assert!(context.get_function("caller").is_some());
+    }
+
+    #[test]
+    fn cache_misses_stale_entry() {
+        let llvm = inkwell::context::Context::create();
+        let cache = build_cached_module(&mut cached_context(&llvm, 1, false), 1);
+
+        let mut context = cached_context(&llvm, 1, false);
+        context.set_function_input("added", b"added");
+        assert!(!context.restore_from_cache(&cache).expect("Always valid"));
+
+        let mut context = cached_context(&llvm, 1, false);
+        let corrupted = Cache::new(cache.key, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(context.restore_from_cache(&corrupted).is_err());
+    }
+
+    #[test]
+    fn storage_writes_own_and_external_kept() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+        let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageStore);
+        for (value, is_external) in [(1, 0), (2, 1)] {
+            context.build_call(
+                intrinsic,
+                &[
+                    context.field_const(value).as_basic_value_enum(),
+                    context.field_const(42).as_basic_value_enum(),
+                    context.field_const(is_external).as_basic_value_enum(),
+                ],
+                "storage_store",
+            );
+        }
+        context.build_unconditional_branch(context.function().return_block);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+
+        assert!(!context.combine_storage_writes());
+        assert!(context.eliminated_writes.is_empty());
+
+        let entry_block = context.function().entry_block;
+        let mut stores = 0;
+        let mut instruction = entry_block.get_first_instruction();
+        while let Some(current) = instruction {
+            instruction = current.get_next_instruction();
+            if GasReport::callee_name(current).as_deref()
+                == Some(IntrinsicFunction::StorageStore.name())
+            {
+                stores += 1;
+            }
+        }
+        assert_eq!(stores, 2);
+    }
+
+    #[test]
+    fn function_nothrow_requires_body() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+        assert!(context.set_function_nothrow("test").is_err());
+
+        context.build_unconditional_branch(context.function().return_block);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+
+        assert!(context.set_function_nothrow("test").is_ok());
+        assert!(context.function().is_nothrow);
+    }
+
+    ///
+    /// The test deploy code, which sets an immutable and returns `size` bytes of the runtime
+    /// code.
+    ///
+    struct TestDeployCode {
+        /// The returned runtime code size.
+        size: u64,
+    }
+
+    impl WriteLLVM<TestDependency> for TestDeployCode {
+        fn into_llvm(self, context: &mut Context<TestDependency>) -> anyhow::Result<()> {
+            let value = context.field_const(42);
+            crate::evm::immutable::store(context, "test".to_owned(), value)?;
+            context.build_exit(
+                ExitKind::Return,
+                context.field_const(0),
+                context.field_const(self.size),
+            )
+        }
+    }
+
+    #[test]
+    fn deploy_return_flushes_immutables() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        let mut constructor = Constructor::new(TestDeployCode { size: 64 });
+        constructor.declare(&mut context).expect("Always valid");
+        constructor.into_llvm(&mut context).expect("Always valid");
+        context.verify().expect("Always valid");
+
+        let constructor = context
+            .get_function(compiler_common::LLVM_FUNCTION_CONSTRUCTOR)
+            .expect("Always exists");
+        let mut stores = 0;
+        let mut instruction = constructor.return_block.get_first_instruction();
+        while let Some(current) = instruction {
+            instruction = current.get_next_instruction();
+            if GasReport::callee_name(current).as_deref()
+                == Some(IntrinsicFunction::StorageStore.name())
+            {
+                stores += 1;
+            }
+        }
+        assert_eq!(stores, 2);
+    }
+
+    #[test]
+    fn system_intrinsics_required() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+        context.set_system_mode(true);
+
+        let zero = context.field_const(0);
+        let error =
+            crate::evm::contract::mimic_call(&mut context, zero, zero, zero, zero, zero, zero)
+                .expect_err("Always unsupported");
+        assert_eq!(
+            error.downcast_ref::<Error>(),
+            Some(&Error::UnsupportedSystemIntrinsic(
+                PrivilegedOperation::MimicCall
+            ))
+        );
+    }
+
+    #[test]
+    fn stripped_names_unnamed() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        context.set_naming_policy(NamingPolicy::Stripped);
+        with_test_function(&mut context);
+
+        let size = context.field_const(64);
+        let offset = context.build_heap_alloc(size);
+        context.build_store_multiple(offset, &[offset, offset]);
+        let address = context.build_address_mask(offset);
+        let flag = context.as_field_bool(address);
+        let truncated = context
+            .truncate_from_field(flag, compiler_common::BITLENGTH_X64)
+            .expect("Always valid");
+        let extended = context.extend_to_field(truncated, false);
+        let pointer = context.access_memory(offset, AddressSpace::Heap, "pointer");
+        context
+            .build_store_be_bytes(pointer, extended, 4)
+            .expect("Always valid");
+        context.build_return(None);
+
+        context.build_throw_block(LongReturnPolicy::Return);
+        context.build_catch_block(LongReturnPolicy::Return);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+        context.verify().expect("Always valid");
+
+        let ir = context.module().print_to_string().to_string();
+        for line in ir.lines() {
+            let is_named_block = !line.starts_with(char::is_whitespace)
+                && line
+                    .split_whitespace()
+                    .next()
+                    .map(|token| {
+                        token.ends_with(':') && !token.starts_with(|c: char| c.is_ascii_digit())
+                    })
+                    .unwrap_or_default();
+            assert!(!is_named_block, "Named block in `{}`", line);
+
+            let is_named_value = line
+                .match_indices('%')
+                .any(|(index, _)| !line[index + 1..].starts_with(|c: char| c.is_ascii_digit()));
+            assert!(!is_named_value, "Named value in `{}`", line);
+        }
+    }
+
+    ///
+    /// The divider of the guarded division tests.
+    ///
+    #[derive(Debug, Clone, Copy)]
+    enum TestDivider {
+        /// The zero constant.
+        Zero,
+        /// The minus one constant.
+        MinusOne,
+        /// The value loaded from the heap.
+        Dynamic,
+    }
+
+    ///
+    /// The division or remainder lowering.
+    ///
+    type DivisionLowering =
+        for<'ctx> fn(
+            &mut Context<'ctx, TestDependency>,
+            Operands<'ctx>,
+        ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>;
+
+    ///
+    /// Translates the `lowering` of a dynamic dividend by `divider` and verifies the function.
+    ///
+    /// Returns the result constant, if it has been folded, and the number of the selects.
+    ///
+    fn guarded_division(
+        lowering: DivisionLowering,
+        divider: TestDivider,
+    ) -> (Option<num::BigUint>, usize) {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+
+        let dividend_pointer = context.access_memory(
+            context.field_const(0),
+            AddressSpace::Heap,
+            "dividend_pointer",
+        );
+        let dividend = context
+            .build_load(dividend_pointer, "dividend")
+            .into_int_value();
+        let divider = match divider {
+            TestDivider::Zero => context.field_const(0),
+            TestDivider::MinusOne => context.field_type().const_all_ones(),
+            TestDivider::Dynamic => {
+                let divider_pointer = context.access_memory(
+                    context.field_const(compiler_common::SIZE_FIELD as u64),
+                    AddressSpace::Heap,
+                    "divider_pointer",
+                );
+                context
+                    .build_load(divider_pointer, "divider")
+                    .into_int_value()
+            }
+        };
+        let result = lowering(&mut context, [dividend, divider].into())
+            .expect("Always valid")
+            .expect("Always exists")
+            .into_int_value();
+        context.build_store(dividend_pointer, result);
+        context.build_unconditional_branch(context.function().return_block);
+
+        context.build_throw_block(LongReturnPolicy::Propagate);
+        context.build_catch_block(LongReturnPolicy::Propagate);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+        context.verify().expect("Always valid");
+
+        let mut selects = 0;
+        for block in context.function().value.get_basic_blocks() {
+            let mut instruction = block.get_first_instruction();
+            while let Some(current) = instruction {
+                if current.get_opcode() == inkwell::values::InstructionOpcode::Select {
+                    selects += 1;
+                }
+                instruction = current.get_next_instruction();
+            }
+        }
+        (Argument::constant_of(result.as_basic_value_enum()), selects)
+    }
+
+    #[test]
+    fn guarded_division_zero() {
+        for lowering in [
+            crate::evm::arithmetic::division as DivisionLowering,
+            crate::evm::arithmetic::remainder,
+            crate::evm::arithmetic::division_signed,
+            crate::evm::arithmetic::remainder_signed,
+        ] {
+            assert_eq!(
+                guarded_division(lowering, TestDivider::Zero),
+                (Some(num::BigUint::from(0u8)), 0)
+            );
+        }
+    }
+
+    #[test]
+    fn guarded_division_minus_one() {
+        for lowering in [
+            crate::evm::arithmetic::division as DivisionLowering,
+            crate::evm::arithmetic::remainder,
+            crate::evm::arithmetic::division_signed,
+        ] {
+            assert_eq!(guarded_division(lowering, TestDivider::MinusOne), (None, 0));
+        }
+        assert_eq!(
+            guarded_division(
+                crate::evm::arithmetic::remainder_signed,
+                TestDivider::MinusOne
+            ),
+            (Some(num::BigUint::from(0u8)), 0)
+        );
+    }
+
+    #[test]
+    fn guarded_division_dynamic() {
+        for lowering in [
+            crate::evm::arithmetic::division as DivisionLowering,
+            crate::evm::arithmetic::remainder,
+        ] {
+            assert_eq!(guarded_division(lowering, TestDivider::Dynamic), (None, 2));
+        }
+        for lowering in [
+            crate::evm::arithmetic::division_signed as DivisionLowering,
+            crate::evm::arithmetic::remainder_signed,
+        ] {
+            assert_eq!(guarded_division(lowering, TestDivider::Dynamic), (None, 3));
+        }
+    }
+
+    #[test]
+    fn field_const_parsing() {
+        let llvm = inkwell::context::Context::create();
+        let context = test_context(&llvm);
+
+        let decimal = context.field_const_dec("100").expect("Always valid");
+        let hexadecimal = context.field_const_hex("0x100").expect("Always valid");
+        assert_eq!(decimal.get_zero_extended_constant(), Some(100));
+        assert_eq!(hexadecimal.get_zero_extended_constant(), Some(256));
+        assert_eq!(
+            context.field_const_hex("100").expect("Always valid"),
+            hexadecimal
+        );
+        assert_eq!(
+            context.field_const_auto("100").expect("Always valid"),
+            decimal
+        );
+        assert_eq!(
+            context.field_const_auto("0x100").expect("Always valid"),
+            hexadecimal
+        );
+
+        assert!(context.field_const_auto("1f").is_err());
+        assert!(context.field_const_dec("1f").is_err());
+        assert!(context.field_const_dec("0x100").is_err());
+        assert!(context.field_const_hex("0x").is_err());
+        assert!(context.field_const_hex("1g").is_err());
+    }
+
+    #[test]
+    fn dumps_collected_in_artifact() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = Context::<TestDependency>::new(
+            &llvm,
+            &target_machine(),
+            inkwell::OptimizationLevel::None,
+            inkwell::OptimizationLevel::None,
+            "test",
+            None,
+            vec![DumpFlag::VerifiedLLVM, DumpFlag::SizeReport],
+        );
+        with_test_function(&mut context);
+        context.build_return(None);
+        context.build_throw_block(LongReturnPolicy::Propagate);
+        context.build_catch_block(LongReturnPolicy::Propagate);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+
+        context.verify().expect("Always valid");
+        context.optimize();
+
+        let dumps = context.artifact().dumps;
+        assert_eq!(
+            dumps
+                .iter()
+                .map(|dump| dump.flag)
+                .collect::<Vec<DumpFlag>>(),
+            vec![DumpFlag::VerifiedLLVM, DumpFlag::SizeReport]
+        );
+        assert!(dumps[0].text.contains("define void @test"));
+    }
+
+    #[test]
+    fn panic_codes() {
+        assert_eq!(
+            Context::<TestDependency>::selector("Panic(uint256)") as u64,
+            PanicCode::SELECTOR
+        );
+        for (code, value) in [
+            (PanicCode::Assert, 0x01),
+            (PanicCode::ArithmeticOverflow, 0x11),
+            (PanicCode::DivisionByZero, 0x12),
+            (PanicCode::EnumConversion, 0x21),
+            (PanicCode::StorageBytesEncoding, 0x22),
+            (PanicCode::EmptyArrayPop, 0x31),
+            (PanicCode::IndexOutOfBounds, 0x32),
+            (PanicCode::AllocationTooLarge, 0x41),
+            (PanicCode::ZeroInitializedFunction, 0x51),
+        ] {
+            assert_eq!(code.code(), value);
+        }
+        assert_eq!(PanicCode::Assert.to_string(), "0x01");
+    }
+
+    #[test]
+    fn panic_branches_to_throw_block() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+        with_test_function(&mut context);
+        context.build_panic(PanicCode::IndexOutOfBounds);
+
+        context.build_throw_block(LongReturnPolicy::Propagate);
+        context.build_catch_block(LongReturnPolicy::Propagate);
+        context.set_basic_block(context.function().return_block);
+        context.build_return(None);
+        context.verify().expect("Always valid");
+
+        let terminator = context
+            .function()
+            .entry_block
+            .get_terminator()
+            .expect("Always terminated");
+        assert_eq!(
+            terminator
+                .get_operand(0)
+                .and_then(|operand| operand.right()),
+            Some(context.function().throw_block)
+        );
+    }
+
+    #[test]
+    fn selector_table() {
+        let llvm = inkwell::context::Context::create();
+        let mut context = test_context(&llvm);
+
+        let transfer = Context::<TestDependency>::selector("transfer(address,uint256)");
+        assert_eq!(transfer, 0xa9059cbb);
+        context
+            .register_selector(transfer, "transfer")
+            .expect("Always valid");
+        context
+            .register_selector(transfer, "transfer")
+            .expect("Always valid");
+        context
+            .register_selector(0x01, "first")
+            .expect("Always valid");
+        assert!(context.register_selector(transfer, "other").is_err());
+
+        let selectors = context.artifact().selectors;
+        assert_eq!(
+            selectors.into_iter().collect::<Vec<(String, String)>>(),
+            vec![
+                ("0x00000001".to_owned(), "first".to_owned()),
+                ("0xa9059cbb".to_owned(), "transfer".to_owned()),
+            ]
+        );
+    }
+}
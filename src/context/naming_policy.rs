@@ -0,0 +1,22 @@
+//!
+//! The LLVM value naming policy.
+//!
+
+///
+/// The LLVM value naming policy.
+///
+/// Applied to the names of the values and basic blocks created by the context helpers.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamingPolicy {
+    /// The descriptive names are kept, which is useful for the IR dumps.
+    Verbose,
+    /// The names are dropped, so LLVM numbers the values, which reduces the IR size.
+    Stripped,
+}
+
+impl Default for NamingPolicy {
+    fn default() -> Self {
+        Self::Verbose
+    }
+}
@@ -0,0 +1,147 @@
+//!
+//! The LLVM instruction operands.
+//!
+
+use crate::context::argument::Argument;
+
+///
+/// The LLVM instruction operands.
+///
+/// The uniform input of the EVM instruction translators, so that frontends can pass their
+/// arguments without per-instruction glue code.
+///
+#[derive(Debug, Clone, Default)]
+pub struct Operands<'ctx> {
+    /// The operands with their metadata.
+    inner: Vec<Argument<'ctx>>,
+}
+
+impl<'ctx> Operands<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(inner: Vec<Argument<'ctx>>) -> Self {
+        Self { inner }
+    }
+
+    ///
+    /// Returns the number of operands.
+    ///
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    ///
+    /// Whether there are no operands.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    ///
+    /// Checks that there are exactly `expected` operands.
+    ///
+    pub fn check_count(&self, expected: usize) -> anyhow::Result<()> {
+        if self.inner.len() != expected {
+            anyhow::bail!("Expected {} operands, found {}", expected, self.inner.len());
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the operand at `index`.
+    ///
+    pub fn argument(&self, index: usize) -> anyhow::Result<&Argument<'ctx>> {
+        self.inner
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("Operand {} is missing", index))
+    }
+
+    ///
+    /// Returns the LLVM value of the operand at `index`.
+    ///
+    pub fn value(&self, index: usize) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>> {
+        self.argument(index).map(Argument::to_llvm)
+    }
+
+    ///
+    /// Returns the LLVM value of the operand at `index` as an integer.
+    ///
+    pub fn int(&self, index: usize) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        match self.value(index)? {
+            inkwell::values::BasicValueEnum::IntValue(value) => Ok(value),
+            value => anyhow::bail!("Operand {} is not an integer: {:?}", index, value),
+        }
+    }
+
+    ///
+    /// Returns the LLVM values of all operands as integers.
+    ///
+    pub fn ints(&self) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>> {
+        (0..self.inner.len()).map(|index| self.int(index)).collect()
+    }
+
+    ///
+    /// Returns the compile-time constant value of the operand at `index`, if it is known.
+    ///
+    pub fn constant(&self, index: usize) -> Option<&num::BigUint> {
+        self.inner
+            .get(index)
+            .and_then(|argument| argument.constant.as_ref())
+    }
+
+    ///
+    /// Returns the compile-time constant value of the operand at `index`, if it is known and fits
+    /// into 64 bits.
+    ///
+    pub fn constant_u64(&self, index: usize) -> Option<u64> {
+        self.inner.get(index).and_then(Argument::constant_u64)
+    }
+
+    ///
+    /// Returns the original AST value of the operand at `index`, if it is known.
+    ///
+    pub fn original(&self, index: usize) -> Option<&str> {
+        self.inner
+            .get(index)
+            .and_then(|argument| argument.original.as_deref())
+    }
+
+    ///
+    /// Takes the original AST value of the operand at `index`, if it is known.
+    ///
+    pub fn take_original(&mut self, index: usize) -> Option<String> {
+        self.inner
+            .get_mut(index)
+            .and_then(|argument| argument.original.take())
+    }
+}
+
+impl<'ctx> From<Vec<Argument<'ctx>>> for Operands<'ctx> {
+    fn from(inner: Vec<Argument<'ctx>>) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<'ctx> From<Vec<inkwell::values::BasicValueEnum<'ctx>>> for Operands<'ctx> {
+    fn from(values: Vec<inkwell::values::BasicValueEnum<'ctx>>) -> Self {
+        Self::new(values.into_iter().map(Argument::new).collect())
+    }
+}
+
+impl<'ctx, const N: usize> From<[inkwell::values::BasicValueEnum<'ctx>; N]> for Operands<'ctx> {
+    fn from(values: [inkwell::values::BasicValueEnum<'ctx>; N]) -> Self {
+        Self::new(values.into_iter().map(Argument::new).collect())
+    }
+}
+
+impl<'ctx, const N: usize> From<[inkwell::values::IntValue<'ctx>; N]> for Operands<'ctx> {
+    fn from(values: [inkwell::values::IntValue<'ctx>; N]) -> Self {
+        Self::new(
+            values
+                .into_iter()
+                .map(|value| Argument::new(value.into()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,191 @@
+//!
+//! The LLVM optimizing tools.
+//!
+
+pub mod settings;
+
+use self::settings::Settings;
+
+///
+/// The LLVM optimizing tools.
+///
+#[derive(Debug)]
+pub struct Optimizer<'ctx> {
+    /// The middle-end optimization level.
+    level_middle: inkwell::OptimizationLevel,
+    /// The back-end optimization level.
+    level_back: inkwell::OptimizationLevel,
+    /// The optimizer settings.
+    settings: Settings,
+    /// The module optimization pass manager.
+    pass_manager_module: inkwell::passes::PassManager<inkwell::module::Module<'ctx>>,
+    /// The function optimization pass manager.
+    pass_manager_function: inkwell::passes::PassManager<inkwell::values::FunctionValue<'ctx>>,
+    /// The size-optimizing pass managers, only created if the size budget is set.
+    pass_managers_size: Option<(
+        inkwell::passes::PassManager<inkwell::module::Module<'ctx>>,
+        inkwell::passes::PassManager<inkwell::values::FunctionValue<'ctx>>,
+    )>,
+}
+
+impl<'ctx> Optimizer<'ctx> {
+    /// The LLVM size level used by the size-optimizing re-run, which is `-Oz`.
+    const SIZE_LEVEL: u32 = 2;
+    /// The inlining threshold used by the size-optimizing re-run.
+    const SIZE_INLINER_THRESHOLD: u32 = 5;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        module: &inkwell::module::Module<'ctx>,
+        level_middle: inkwell::OptimizationLevel,
+        level_back: inkwell::OptimizationLevel,
+    ) -> Self {
+        Self::new_with_settings(module, level_middle, level_back, Settings::default())
+    }
+
+    ///
+    /// A shortcut constructor with the tuning settings.
+    ///
+    pub fn new_with_settings(
+        module: &inkwell::module::Module<'ctx>,
+        level_middle: inkwell::OptimizationLevel,
+        level_back: inkwell::OptimizationLevel,
+        settings: Settings,
+    ) -> Self {
+        let (pass_manager_module, pass_manager_function) =
+            Self::create_pass_managers(module, level_middle, settings.inliner_threshold, 0);
+        let pass_managers_size = settings.size_budget.map(|_| {
+            Self::create_pass_managers(
+                module,
+                level_middle,
+                Some(Self::SIZE_INLINER_THRESHOLD),
+                Self::SIZE_LEVEL,
+            )
+        });
+
+        Self {
+            level_middle,
+            level_back,
+            settings,
+            pass_manager_module,
+            pass_manager_function,
+            pass_managers_size,
+        }
+    }
+
+    ///
+    /// Returns the middle-end optimization level.
+    ///
+    pub fn level_middle(&self) -> inkwell::OptimizationLevel {
+        self.level_middle
+    }
+
+    ///
+    /// Returns the back-end optimization level.
+    ///
+    pub fn level_back(&self) -> inkwell::OptimizationLevel {
+        self.level_back
+    }
+
+    ///
+    /// Returns the optimizer settings.
+    ///
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    ///
+    /// Runs the optimizations on `module`.
+    ///
+    /// Only returns `true` if any of the passes modified the module.
+    ///
+    pub fn run_on_module(&self, module: &inkwell::module::Module<'ctx>) -> bool {
+        self.pass_manager_module.run_on(module)
+    }
+
+    ///
+    /// Runs the optimizations on `function`.
+    ///
+    /// Only returns `true` if any of the passes modified the function.
+    ///
+    pub fn run_on_function(&self, function: inkwell::values::FunctionValue<'ctx>) -> bool {
+        self.pass_manager_function.run_on(&function)
+    }
+
+    ///
+    /// Removes the unreferenced private functions and globals from `module`.
+    ///
+    /// Only returns `true` if anything has been removed.
+    ///
+    pub fn run_dead_function_elimination(&self, module: &inkwell::module::Module<'ctx>) -> bool {
+        let pass_manager = inkwell::passes::PassManager::create(());
+        pass_manager.add_global_dce_pass();
+        pass_manager.run_on(module)
+    }
+
+    ///
+    /// Runs the size-optimizing pipeline on `module`.
+    ///
+    /// Only returns `true` if the size budget is set and any of the passes modified the module.
+    ///
+    pub fn run_on_module_for_size(&self, module: &inkwell::module::Module<'ctx>) -> bool {
+        self.pass_managers_size
+            .as_ref()
+            .map(|(pass_manager_module, _)| pass_manager_module.run_on(module))
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Runs the size-optimizing pipeline on `function`.
+    ///
+    /// Only returns `true` if the size budget is set and any of the passes modified the function.
+    ///
+    pub fn run_on_function_for_size(&self, function: inkwell::values::FunctionValue<'ctx>) -> bool {
+        self.pass_managers_size
+            .as_ref()
+            .map(|(_, pass_manager_function)| pass_manager_function.run_on(&function))
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Creates the module and function pass managers.
+    ///
+    fn create_pass_managers(
+        module: &inkwell::module::Module<'ctx>,
+        level_middle: inkwell::OptimizationLevel,
+        inliner_threshold: Option<u32>,
+        size_level: u32,
+    ) -> (
+        inkwell::passes::PassManager<inkwell::module::Module<'ctx>>,
+        inkwell::passes::PassManager<inkwell::values::FunctionValue<'ctx>>,
+    ) {
+        let internalize = matches!(level_middle, inkwell::OptimizationLevel::Aggressive);
+        let run_inliner = matches!(level_middle, inkwell::OptimizationLevel::Aggressive);
+
+        let pass_manager_builder = inkwell::passes::PassManagerBuilder::create();
+        pass_manager_builder.set_optimization_level(level_middle);
+        pass_manager_builder.set_size_level(size_level);
+        pass_manager_builder.set_disable_unroll_loops(matches!(
+            level_middle,
+            inkwell::OptimizationLevel::Aggressive
+        ));
+        if let Some(inliner_threshold) = inliner_threshold {
+            pass_manager_builder.set_inliner_with_threshold(inliner_threshold);
+        }
+
+        let pass_manager_module = inkwell::passes::PassManager::create(());
+        pass_manager_builder.populate_lto_pass_manager(
+            &pass_manager_module,
+            internalize,
+            run_inliner,
+        );
+        pass_manager_builder.populate_module_pass_manager(&pass_manager_module);
+
+        let pass_manager_function = inkwell::passes::PassManager::create(module);
+        pass_manager_builder.populate_function_pass_manager(&pass_manager_function);
+
+        (pass_manager_module, pass_manager_function)
+    }
+}
@@ -0,0 +1,37 @@
+//!
+//! The LLVM optimizer settings.
+//!
+
+///
+/// The LLVM optimizer settings.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The inlining threshold. If unset, the LLVM default for the optimization level is used.
+    pub inliner_threshold: Option<u32>,
+    /// The estimated module size in bytes, exceeding which triggers the size-optimizing re-run.
+    pub size_budget: Option<usize>,
+    /// Whether to eliminate the storage writes overwritten later in the same basic block.
+    pub is_storage_combining_enabled: bool,
+}
+
+impl Settings {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(inliner_threshold: Option<u32>, size_budget: Option<usize>) -> Self {
+        Self {
+            inliner_threshold,
+            size_budget,
+            is_storage_combining_enabled: false,
+        }
+    }
+
+    ///
+    /// Enables or disables the storage write combining.
+    ///
+    pub fn with_storage_combining(mut self, value: bool) -> Self {
+        self.is_storage_combining_enabled = value;
+        self
+    }
+}
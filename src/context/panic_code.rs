@@ -0,0 +1,58 @@
+//!
+//! The Solidity panic codes.
+//!
+
+///
+/// The Solidity panic codes.
+///
+/// The codes are encoded as the `Panic(uint256)` error argument.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PanicCode {
+    /// The `assert` condition is false.
+    Assert,
+    /// An arithmetic operation overflowed or underflowed.
+    ArithmeticOverflow,
+    /// A division or modulo by zero.
+    DivisionByZero,
+    /// A value too big or negative converted into an enum.
+    EnumConversion,
+    /// An incorrectly encoded storage byte array.
+    StorageBytesEncoding,
+    /// The `pop` called on an empty array.
+    EmptyArrayPop,
+    /// An array, slice, or fixed bytes index is out of bounds.
+    IndexOutOfBounds,
+    /// Too much memory allocated, or an array too large created.
+    AllocationTooLarge,
+    /// A zero-initialized variable of an internal function type called.
+    ZeroInitializedFunction,
+}
+
+impl PanicCode {
+    /// The `Panic(uint256)` selector.
+    pub const SELECTOR: u64 = 0x4e487b71;
+
+    ///
+    /// Returns the numeric panic code.
+    ///
+    pub fn code(&self) -> u64 {
+        match self {
+            Self::Assert => 0x01,
+            Self::ArithmeticOverflow => 0x11,
+            Self::DivisionByZero => 0x12,
+            Self::EnumConversion => 0x21,
+            Self::StorageBytesEncoding => 0x22,
+            Self::EmptyArrayPop => 0x31,
+            Self::IndexOutOfBounds => 0x32,
+            Self::AllocationTooLarge => 0x41,
+            Self::ZeroInitializedFunction => 0x51,
+        }
+    }
+}
+
+impl std::fmt::Display for PanicCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:02x}", self.code())
+    }
+}
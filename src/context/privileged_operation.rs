@@ -0,0 +1,34 @@
+//!
+//! The privileged operation.
+//!
+
+///
+/// The privileged operation.
+///
+/// Such operations are only emitted if the system mode is enabled.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrivilegedOperation {
+    /// The contract call on behalf of another account.
+    MimicCall,
+    /// The contract context value setter.
+    SetContextValue,
+    /// The switch to the storage of another account.
+    SetStorage,
+    /// The storage load from another account.
+    ExternalStorageLoad,
+    /// The storage store to another account.
+    ExternalStorageStore,
+}
+
+impl std::fmt::Display for PrivilegedOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MimicCall => write!(f, "mimic call"),
+            Self::SetContextValue => write!(f, "context value setter"),
+            Self::SetStorage => write!(f, "storage switch"),
+            Self::ExternalStorageLoad => write!(f, "external storage load"),
+            Self::ExternalStorageStore => write!(f, "external storage store"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//!
+//! The instruction restricted to a code type.
+//!
+
+use crate::context::code_type::CodeType;
+
+///
+/// The instruction restricted to a code type.
+///
+/// Such instructions are rejected outside of the allowed code type, since they would only
+/// produce meaningless code there.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RestrictedInstruction {
+    /// The `setimmutable` instruction, which initializes the immutables in the constructor.
+    SetImmutable,
+    /// The `loadimmutable` instruction, which reads the immutables initialized by the constructor.
+    LoadImmutable,
+    /// The `codecopy` of the deploy code, which reads the constructor arguments appended thereto.
+    CodeCopy,
+    /// The `codesize` of the deploy code, which includes the constructor arguments.
+    CodeSize,
+}
+
+impl RestrictedInstruction {
+    ///
+    /// Returns the only code type the instruction is allowed in.
+    ///
+    pub fn allowed_code_type(&self) -> CodeType {
+        match self {
+            Self::SetImmutable => CodeType::Deploy,
+            Self::LoadImmutable => CodeType::Runtime,
+            Self::CodeCopy => CodeType::Deploy,
+            Self::CodeSize => CodeType::Deploy,
+        }
+    }
+}
+
+impl std::fmt::Display for RestrictedInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SetImmutable => write!(f, "setimmutable"),
+            Self::LoadImmutable => write!(f, "loadimmutable"),
+            Self::CodeCopy => write!(f, "codecopy"),
+            Self::CodeSize => write!(f, "codesize"),
+        }
+    }
+}
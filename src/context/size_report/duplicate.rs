@@ -0,0 +1,43 @@
+//!
+//! The code size report duplicate function entry.
+//!
+
+use serde::Serialize;
+
+///
+/// The code size report duplicate function entry.
+///
+/// Describes a function body found in several modules.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Duplicate {
+    /// The function body hash.
+    pub hash: String,
+    /// The distinct names of the function instances.
+    pub names: Vec<String>,
+    /// The number of the function instances.
+    pub instances: usize,
+    /// The estimated bytecode size of a single instance in bytes.
+    pub estimated_size: usize,
+}
+
+impl Duplicate {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(hash: String, names: Vec<String>, instances: usize, estimated_size: usize) -> Self {
+        Self {
+            hash,
+            names,
+            instances,
+            estimated_size,
+        }
+    }
+
+    ///
+    /// Returns the estimated size in bytes, which could be saved by sharing a single instance.
+    ///
+    pub fn wasted_size(&self) -> usize {
+        self.estimated_size * (self.instances - 1)
+    }
+}
@@ -0,0 +1,34 @@
+//!
+//! The code size report function entry.
+//!
+
+use serde::Serialize;
+
+///
+/// The code size report function entry.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Function {
+    /// The function name.
+    pub name: String,
+    /// The number of LLVM IR instructions.
+    pub instructions: usize,
+    /// The estimated bytecode size in bytes.
+    pub estimated_size: usize,
+    /// The body hash, which is equal for the identical functions in different modules.
+    pub hash: String,
+}
+
+impl Function {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, instructions: usize, estimated_size: usize, hash: String) -> Self {
+        Self {
+            name,
+            instructions,
+            estimated_size,
+            hash,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//!
+//! The code size report.
+//!
+
+pub mod duplicate;
+pub mod function;
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::context::function::statistics::Statistics as FunctionStatistics;
+
+use self::duplicate::Duplicate;
+use self::function::Function;
+
+///
+/// The code size report.
+///
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SizeReport {
+    /// The defined function entries, sorted by the estimated size in descending order.
+    pub functions: Vec<Function>,
+    /// The total number of LLVM IR instructions.
+    pub instructions: usize,
+    /// The total estimated bytecode size in bytes.
+    pub estimated_size: usize,
+    /// The unreferenced functions removed before the optimization.
+    pub removed_functions: Vec<String>,
+    /// The number of the emitted assembly instructions, if the assembly has been post-processed.
+    pub assembly_instructions: Option<usize>,
+}
+
+impl SizeReport {
+    /// The estimated bytecode size of a single instruction.
+    pub const INSTRUCTION_SIZE_ESTIMATE: usize = 8;
+
+    ///
+    /// Walks the `module` and counts the instructions of each defined function.
+    ///
+    /// Should be run after the optimizations to get meaningful numbers.
+    ///
+    pub fn new(module: &inkwell::module::Module) -> Self {
+        let mut functions = Vec::new();
+
+        let mut function = module.get_first_function();
+        while let Some(value) = function {
+            if value.count_basic_blocks() > 0 {
+                let instructions = FunctionStatistics::new(value).instructions;
+                functions.push(Function::new(
+                    value.get_name().to_string_lossy().to_string(),
+                    instructions,
+                    instructions * Self::INSTRUCTION_SIZE_ESTIMATE,
+                    Self::hash_body(value),
+                ));
+            }
+            function = value.get_next_function();
+        }
+        functions.sort_by(|a, b| b.estimated_size.cmp(&a.estimated_size));
+
+        let instructions = functions.iter().map(|function| function.instructions).sum();
+        let estimated_size = functions
+            .iter()
+            .map(|function| function.estimated_size)
+            .sum();
+
+        Self {
+            functions,
+            instructions,
+            estimated_size,
+            removed_functions: Vec::new(),
+            assembly_instructions: None,
+        }
+    }
+
+    ///
+    /// Sets the unreferenced functions removed before the optimization.
+    ///
+    pub fn with_removed_functions(mut self, removed_functions: Vec<String>) -> Self {
+        self.removed_functions = removed_functions;
+        self
+    }
+
+    ///
+    /// Sets the number of the emitted assembly instructions.
+    ///
+    pub fn with_assembly_instructions(mut self, assembly_instructions: Option<usize>) -> Self {
+        self.assembly_instructions = assembly_instructions;
+        self
+    }
+
+    ///
+    /// Returns the function entry with the specified `name`.
+    ///
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|function| function.name.as_str() == name)
+    }
+
+    ///
+    /// Finds the functions duplicated across the `reports` of different modules, e.g. the runtime
+    /// helpers embedded into each dependency of a factory contract.
+    ///
+    /// The duplicates are sorted by the wasted size in descending order.
+    ///
+    pub fn duplicates(reports: &[&SizeReport]) -> Vec<Duplicate> {
+        let mut groups: BTreeMap<&str, Vec<&Function>> = BTreeMap::new();
+        for report in reports.iter() {
+            for function in report.functions.iter() {
+                groups
+                    .entry(function.hash.as_str())
+                    .or_default()
+                    .push(function);
+            }
+        }
+
+        let mut duplicates: Vec<Duplicate> = groups
+            .into_iter()
+            .filter(|(_, functions)| functions.len() > 1)
+            .map(|(hash, functions)| {
+                let mut names: Vec<String> = functions
+                    .iter()
+                    .map(|function| function.name.clone())
+                    .collect();
+                names.sort();
+                names.dedup();
+                Duplicate::new(
+                    hash.to_owned(),
+                    names,
+                    functions.len(),
+                    functions[0].estimated_size,
+                )
+            })
+            .collect();
+        duplicates.sort_by(|a, b| b.wasted_size().cmp(&a.wasted_size()));
+        duplicates
+    }
+
+    ///
+    /// Hashes the `function` IR without its signature line, which contains the name.
+    ///
+    fn hash_body(function: inkwell::values::FunctionValue) -> String {
+        let ir = function.print_to_string().to_string();
+        let body = ir
+            .split_once('\n')
+            .map(|(_, body)| body)
+            .unwrap_or_default();
+        format!("{:x}", md5::compute(body.as_bytes()))
+    }
+}
+
+impl std::fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for function in self.functions.iter() {
+            writeln!(
+                f,
+                "{:>8} {:>10} {}",
+                function.instructions, function.estimated_size, function.name
+            )?;
+        }
+        for name in self.removed_functions.iter() {
+            writeln!(f, "{:>8} {:>10} {}", "removed", 0, name)?;
+        }
+        if let Some(assembly_instructions) = self.assembly_instructions {
+            writeln!(f, "{:>8} {:>10} assembly", assembly_instructions, "")?;
+        }
+        write!(
+            f,
+            "{:>8} {:>10} total",
+            self.instructions, self.estimated_size
+        )
+    }
+}
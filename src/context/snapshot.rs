@@ -0,0 +1,65 @@
+//!
+//! The LLVM context state snapshot.
+//!
+
+use std::collections::HashMap;
+
+use crate::context::function::Function;
+use crate::context::r#loop::Loop;
+
+///
+/// The LLVM context state snapshot.
+///
+/// Created by `Context::snapshot` and consumed by `Context::restore`.
+///
+#[derive(Debug, Clone)]
+pub struct Snapshot<'ctx> {
+    /// The current function.
+    pub(crate) function: Option<Function<'ctx>>,
+    /// The insertion basic block.
+    pub(crate) block: Option<inkwell::basic_block::BasicBlock<'ctx>>,
+    /// The loop stack.
+    pub(crate) loop_stack: Vec<Loop<'ctx>>,
+    /// The declared functions.
+    pub(crate) functions: HashMap<String, Function<'ctx>>,
+    /// The module functions.
+    pub(crate) module_functions: Vec<inkwell::values::FunctionValue<'ctx>>,
+    /// The module basic blocks with their last instructions.
+    pub(crate) blocks: Vec<(
+        inkwell::basic_block::BasicBlock<'ctx>,
+        Option<inkwell::values::InstructionValue<'ctx>>,
+    )>,
+}
+
+impl<'ctx> Snapshot<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub(crate) fn new(
+        function: Option<Function<'ctx>>,
+        block: Option<inkwell::basic_block::BasicBlock<'ctx>>,
+        loop_stack: Vec<Loop<'ctx>>,
+        functions: HashMap<String, Function<'ctx>>,
+        module: &inkwell::module::Module<'ctx>,
+    ) -> Self {
+        let mut module_functions = Vec::new();
+        let mut blocks = Vec::new();
+        let mut function_value = module.get_first_function();
+        while let Some(current) = function_value {
+            function_value = current.get_next_function();
+            module_functions.push(current);
+            for block in current.get_basic_blocks() {
+                blocks.push((block, block.get_last_instruction()));
+            }
+        }
+
+        Self {
+            function,
+            block,
+            loop_stack,
+            functions,
+            module_functions,
+            blocks,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//!
+//! The optimization statistics.
+//!
+
+pub mod phase;
+
+use serde::Serialize;
+
+use self::phase::Phase;
+
+///
+/// The optimization statistics.
+///
+/// The equivalent of the LLVM `-time-passes` and `-stats` options, collected per pipeline phase.
+///
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Statistics {
+    /// The pipeline phases in the execution order.
+    pub phases: Vec<Phase>,
+    /// The number of defined functions before the optimization.
+    pub functions_before: usize,
+    /// The number of defined functions after the optimization.
+    pub functions_after: usize,
+    /// The number of LLVM IR instructions before the optimization.
+    pub instructions_before: usize,
+    /// The number of LLVM IR instructions after the optimization.
+    pub instructions_after: usize,
+}
+
+impl Statistics {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(functions_before: usize, instructions_before: usize) -> Self {
+        Self {
+            phases: Vec::new(),
+            functions_before,
+            functions_after: functions_before,
+            instructions_before,
+            instructions_after: instructions_before,
+        }
+    }
+
+    ///
+    /// Records the phase started at `start`, which is finished now.
+    ///
+    pub fn push_phase(&mut self, name: &str, start: std::time::Instant, is_modified: bool) {
+        self.phases
+            .push(Phase::new(name.to_owned(), start.elapsed(), is_modified));
+    }
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for phase in self.phases.iter() {
+            writeln!(f, "{}", phase)?;
+        }
+        writeln!(
+            f,
+            "functions: {} -> {}",
+            self.functions_before, self.functions_after
+        )?;
+        write!(
+            f,
+            "instructions: {} -> {}",
+            self.instructions_before, self.instructions_after
+        )
+    }
+}
@@ -0,0 +1,43 @@
+//!
+//! The optimization pipeline phase statistics.
+//!
+
+use serde::Serialize;
+
+///
+/// The optimization pipeline phase statistics.
+///
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase {
+    /// The phase name.
+    pub name: String,
+    /// The phase duration in microseconds.
+    pub duration_micros: u64,
+    /// Whether the phase has modified the module.
+    pub is_modified: bool,
+}
+
+impl Phase {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, duration: std::time::Duration, is_modified: bool) -> Self {
+        Self {
+            name,
+            duration_micros: duration.as_micros() as u64,
+            is_modified,
+        }
+    }
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>12.3} ms {:>8} {}",
+            self.duration_micros as f64 / 1000.0,
+            if self.is_modified { "modified" } else { "" },
+            self.name
+        )
+    }
+}
@@ -0,0 +1,66 @@
+//!
+//! The storage access hint.
+//!
+
+///
+/// The storage access hint.
+///
+/// Is attached to the storage access instructions as metadata, which is reported in the gas
+/// report and reserved for the backend scheduler.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageHint {
+    /// The slot is likely accessed for the first time in the transaction.
+    Cold,
+    /// The slot has likely been accessed earlier in the transaction.
+    Warm,
+}
+
+impl StorageHint {
+    /// The metadata kind name.
+    pub const METADATA_KIND: &'static str = "syncvm.storage.hint";
+
+    ///
+    /// Returns the metadata string.
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cold => "cold",
+            Self::Warm => "warm",
+        }
+    }
+
+    ///
+    /// Parses the metadata string.
+    ///
+    pub fn from_metadata(value: &str) -> Option<Self> {
+        match value {
+            "cold" => Some(Self::Cold),
+            "warm" => Some(Self::Warm),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Reads the hint attached to the `instruction`, if any.
+    ///
+    pub fn read(
+        llvm: inkwell::context::ContextRef,
+        instruction: inkwell::values::InstructionValue,
+    ) -> Option<Self> {
+        let metadata = instruction.get_metadata(llvm.get_kind_id(Self::METADATA_KIND))?;
+        match metadata.get_node_values().first() {
+            Some(inkwell::values::BasicMetadataValueEnum::MetadataValue(value)) => value
+                .get_string_value()
+                .and_then(|value| value.to_str().ok())
+                .and_then(Self::from_metadata),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for StorageHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! The LLVM submodule.
+//!
+
+use crate::context::function::runtime::Runtime;
+
+///
+/// The LLVM submodule.
+///
+/// A separate module sharing the LLVM context, e.g. for the deploy and runtime code split.
+/// Each submodule has its own runtime function declarations, as LLVM values cannot be shared
+/// between modules.
+///
+#[derive(Debug)]
+pub struct Submodule<'ctx> {
+    /// The LLVM module.
+    pub module: inkwell::module::Module<'ctx>,
+    /// The runtime functions declared in the module.
+    pub runtime: Runtime<'ctx>,
+}
+
+impl<'ctx> Submodule<'ctx> {
+    ///
+    /// Creates an empty submodule with the same target settings as the `parent` module.
+    ///
+    pub fn new(
+        llvm: &'ctx inkwell::context::Context,
+        parent: &inkwell::module::Module<'ctx>,
+        name: &str,
+    ) -> Self {
+        let module = llvm.create_module(name);
+        module.set_triple(&parent.get_triple());
+        module.set_data_layout(&parent.get_data_layout());
+
+        let runtime = Runtime::new(llvm, &module);
+
+        Self { module, runtime }
+    }
+}
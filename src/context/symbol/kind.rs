@@ -0,0 +1,23 @@
+//!
+//! The linker symbol kind.
+//!
+
+///
+/// The linker symbol kind.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// The library address, resolved by `Dependency::resolve_library`.
+    LibraryAddress,
+    /// The arbitrary constant, e.g. a size or feature flag, resolved by `Dependency::resolve_symbol`.
+    Constant,
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LibraryAddress => write!(f, "library address"),
+            Self::Constant => write!(f, "constant"),
+        }
+    }
+}
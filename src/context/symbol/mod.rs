@@ -0,0 +1,33 @@
+//!
+//! The linker symbol.
+//!
+
+pub mod kind;
+
+use self::kind::Kind;
+
+///
+/// The linker symbol.
+///
+/// Represents a named constant, which may be unknown at compile time. Such symbols are resolved
+/// by the dependency manager, or left to the linker.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The symbol kind.
+    pub kind: Kind,
+    /// The hexadecimal value, if known at compile time.
+    pub value: Option<String>,
+}
+
+impl Symbol {
+    /// The link-time symbol global variable name prefix.
+    pub const GLOBAL_PREFIX: &'static str = "__linker_symbol_";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(kind: Kind, value: Option<String>) -> Self {
+        Self { kind, value }
+    }
+}
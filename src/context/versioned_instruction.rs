@@ -0,0 +1,55 @@
+//!
+//! The instruction introduced by an EVM version.
+//!
+
+use crate::context::evm_version::EvmVersion;
+
+///
+/// The instruction introduced by an EVM version.
+///
+/// Such instructions are rejected if the selected EVM version predates them.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionedInstruction {
+    /// The `push0` instruction.
+    Push0,
+    /// The `mcopy` instruction.
+    MemoryCopy,
+    /// The `tload` instruction.
+    TransientLoad,
+    /// The `tstore` instruction.
+    TransientStore,
+    /// The `blobhash` instruction.
+    BlobHash,
+    /// The `blobbasefee` instruction.
+    BlobBaseFee,
+}
+
+impl VersionedInstruction {
+    ///
+    /// Returns the EVM version the instruction has been introduced in.
+    ///
+    pub fn introduced_in(&self) -> EvmVersion {
+        match self {
+            Self::Push0 => EvmVersion::Shanghai,
+            Self::MemoryCopy => EvmVersion::Cancun,
+            Self::TransientLoad => EvmVersion::Cancun,
+            Self::TransientStore => EvmVersion::Cancun,
+            Self::BlobHash => EvmVersion::Cancun,
+            Self::BlobBaseFee => EvmVersion::Cancun,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionedInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Push0 => write!(f, "push0"),
+            Self::MemoryCopy => write!(f, "mcopy"),
+            Self::TransientLoad => write!(f, "tload"),
+            Self::TransientStore => write!(f, "tstore"),
+            Self::BlobHash => write!(f, "blobhash"),
+            Self::BlobBaseFee => write!(f, "blobbasefee"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//!
+//! The LLVM generator warning.
+//!
+
+pub mod span;
+
+use serde::Serialize;
+
+use self::span::Span;
+
+///
+/// The LLVM generator warning.
+///
+/// Is accumulated by `Context::warn` and returned with the artifact.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Warning {
+    /// The warning code, e.g. `selfdestruct-unsupported`.
+    pub code: String,
+    /// The warning message.
+    pub message: String,
+    /// The source code location, if known.
+    pub span: Option<Span>,
+}
+
+impl Warning {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(code: String, message: String, span: Option<Span>) -> Self {
+        Self {
+            code,
+            message,
+            span,
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.span {
+            Some(ref span) => write!(f, "{}: [{}] {}", span, self.code, self.message),
+            None => write!(f, "[{}] {}", self.code, self.message),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//!
+//! The LLVM generator warning source code location.
+//!
+
+use serde::Serialize;
+
+///
+/// The LLVM generator warning source code location.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Span {
+    /// The source file path, if known.
+    pub path: Option<String>,
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The column number, starting from 1.
+    pub column: usize,
+}
+
+impl Span {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(path: Option<String>, line: usize, column: usize) -> Self {
+        Self { path, line, column }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path {
+            Some(ref path) => write!(f, "{}:{}:{}", path, self.line, self.column),
+            None => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//!
+//! The project dependency data.
+//!
+
+pub mod output;
+pub mod request;
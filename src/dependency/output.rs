@@ -0,0 +1,56 @@
+//!
+//! The project dependency compilation output.
+//!
+
+///
+/// The project dependency compilation output.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// The bytecode hash.
+    pub hash: String,
+    /// The bytecode, if provided by the dependency manager.
+    pub bytecode: Option<Vec<u8>>,
+    /// The JSON ABI, if provided by the dependency manager.
+    pub abi: Option<String>,
+    /// The LLVM bitcode, if requested and provided by the dependency manager.
+    pub bitcode: Option<Vec<u8>>,
+}
+
+impl Output {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(hash: String) -> Self {
+        Self {
+            hash,
+            bytecode: None,
+            abi: None,
+            bitcode: None,
+        }
+    }
+
+    ///
+    /// Sets the bytecode.
+    ///
+    pub fn with_bytecode(mut self, bytecode: Vec<u8>) -> Self {
+        self.bytecode = Some(bytecode);
+        self
+    }
+
+    ///
+    /// Sets the JSON ABI.
+    ///
+    pub fn with_abi(mut self, abi: String) -> Self {
+        self.abi = Some(abi);
+        self
+    }
+
+    ///
+    /// Sets the LLVM bitcode.
+    ///
+    pub fn with_bitcode(mut self, bitcode: Vec<u8>) -> Self {
+        self.bitcode = Some(bitcode);
+        self
+    }
+}
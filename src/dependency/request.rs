@@ -0,0 +1,81 @@
+//!
+//! The project dependency compilation request.
+//!
+
+use crate::context::code_type::CodeType;
+use crate::dump_flag::DumpFlag;
+
+///
+/// The project dependency compilation request.
+///
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// The dependency full name.
+    pub name: String,
+    /// The dependent contract full name.
+    pub parent_name: String,
+    /// The required code type of the dependency.
+    pub code_type: CodeType,
+    /// The dependency source language, if known.
+    pub language: Option<String>,
+    /// The dependency contract path within its source file, if known.
+    pub path: Option<String>,
+    /// The middle-end optimization level.
+    pub optimization_level_middle: inkwell::OptimizationLevel,
+    /// The back-end optimization level.
+    pub optimization_level_back: inkwell::OptimizationLevel,
+    /// The IR dump flags.
+    pub dump_flags: Vec<DumpFlag>,
+    /// Whether the LLVM bitcode is requested for the cross-object linking.
+    pub is_bitcode_requested: bool,
+}
+
+impl Request {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        name: String,
+        parent_name: String,
+        code_type: CodeType,
+        optimization_level_middle: inkwell::OptimizationLevel,
+        optimization_level_back: inkwell::OptimizationLevel,
+        dump_flags: Vec<DumpFlag>,
+    ) -> Self {
+        Self {
+            name,
+            parent_name,
+            code_type,
+            language: None,
+            path: None,
+            optimization_level_middle,
+            optimization_level_back,
+            dump_flags,
+            is_bitcode_requested: false,
+        }
+    }
+
+    ///
+    /// Sets the dependency source language.
+    ///
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    ///
+    /// Sets the dependency contract path within its source file.
+    ///
+    pub fn with_path(mut self, path: String) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    ///
+    /// Requests the LLVM bitcode along with the bytecode hash.
+    ///
+    pub fn with_bitcode_requested(mut self) -> Self {
+        self.is_bitcode_requested = true;
+        self
+    }
+}
@@ -0,0 +1,119 @@
+//!
+//! The compiler tester dump flag.
+//!
+
+pub mod set;
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+///
+/// The intermediate representation dump flags.
+///
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum DumpFlag {
+    /// Whether to dump the Yul code.
+    Yul,
+    /// Whether to dump the Ethereal IR code.
+    EthIR,
+    /// Whether to dump the EVM code.
+    EVM,
+    /// Whether to dump the Vyper LLL IR code.
+    LLL,
+    /// Whether to dump the LLVM IR code.
+    LLVM,
+    /// Whether to dump the assembly code.
+    Assembly,
+    /// Whether to dump the code size report.
+    SizeReport,
+    /// Whether to dump the LLVM IR code after a successful verification.
+    VerifiedLLVM,
+    /// Whether to trace the dependency compilation and library resolution.
+    Dependencies,
+    /// Whether to dump the optimization timings and statistics.
+    Statistics,
+}
+
+impl DumpFlag {
+    /// All the dump flags.
+    pub const ALL: [Self; 10] = [
+        Self::Yul,
+        Self::EthIR,
+        Self::EVM,
+        Self::LLL,
+        Self::LLVM,
+        Self::Assembly,
+        Self::SizeReport,
+        Self::VerifiedLLVM,
+        Self::Dependencies,
+        Self::Statistics,
+    ];
+
+    ///
+    /// Returns the flag bit in the dump flag set.
+    ///
+    pub fn bit(self) -> u32 {
+        1 << (self as u32)
+    }
+
+    ///
+    /// A shortcut constructor for vector.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        yul: bool,
+        ethir: bool,
+        evm: bool,
+        lll: bool,
+        llvm: bool,
+        assembly: bool,
+        size_report: bool,
+    ) -> Vec<Self> {
+        let mut vector = Vec::with_capacity(7);
+        if yul {
+            vector.push(Self::Yul);
+        }
+        if ethir {
+            vector.push(Self::EthIR);
+        }
+        if evm {
+            vector.push(Self::EVM);
+        }
+        if lll {
+            vector.push(Self::LLL);
+        }
+        if llvm {
+            vector.push(Self::LLVM);
+        }
+        if assembly {
+            vector.push(Self::Assembly);
+        }
+        if size_report {
+            vector.push(Self::SizeReport);
+        }
+        vector
+    }
+}
+
+impl FromStr for DumpFlag {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "yul" => Ok(Self::Yul),
+            "ethir" => Ok(Self::EthIR),
+            "evm" => Ok(Self::EVM),
+            "lll" => Ok(Self::LLL),
+            "llvm" => Ok(Self::LLVM),
+            "asm" | "assembly" => Ok(Self::Assembly),
+            "size" | "size-report" => Ok(Self::SizeReport),
+            "verified-llvm" => Ok(Self::VerifiedLLVM),
+            "deps" | "dependencies" => Ok(Self::Dependencies),
+            "stats" | "statistics" => Ok(Self::Statistics),
+            value => anyhow::bail!("Unknown dump flag `{}`", value),
+        }
+    }
+}
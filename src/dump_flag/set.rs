@@ -0,0 +1,145 @@
+//!
+//! The dump flag set.
+//!
+
+use std::str::FromStr;
+
+use crate::dump_flag::DumpFlag;
+
+///
+/// The dump flag set.
+///
+/// Stored as a bit set, so the membership check is constant-time.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Set {
+    /// The flag bits.
+    bits: u32,
+}
+
+impl Set {
+    ///
+    /// Parses the flags from an iterator of names, e.g. `["yul", "llvm", "asm"]`.
+    ///
+    pub fn parse<'a, I>(names: I) -> anyhow::Result<Self>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut set = Self::default();
+        for name in names.into_iter().filter(|name| !name.trim().is_empty()) {
+            set.insert(DumpFlag::from_str(name)?);
+        }
+        Ok(set)
+    }
+
+    ///
+    /// Inserts the flag.
+    ///
+    pub fn insert(&mut self, flag: DumpFlag) {
+        self.bits |= flag.bit();
+    }
+
+    ///
+    /// Removes the flag.
+    ///
+    pub fn remove(&mut self, flag: DumpFlag) {
+        self.bits &= !flag.bit();
+    }
+
+    ///
+    /// Checks whether the flag is set.
+    ///
+    pub fn contains(&self, flag: DumpFlag) -> bool {
+        self.bits & flag.bit() != 0
+    }
+
+    ///
+    /// Checks whether no flags are set.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    ///
+    /// Returns the set flags in the declaration order.
+    ///
+    pub fn to_vec(&self) -> Vec<DumpFlag> {
+        DumpFlag::ALL
+            .into_iter()
+            .filter(|flag| self.contains(*flag))
+            .collect()
+    }
+}
+
+impl FromIterator<DumpFlag> for Set {
+    fn from_iter<I: IntoIterator<Item = DumpFlag>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for flag in iter {
+            set.insert(flag);
+        }
+        set
+    }
+}
+
+impl FromStr for Set {
+    type Err = anyhow::Error;
+
+    ///
+    /// Parses a comma-separated list, e.g. `yul,llvm,asm`.
+    ///
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value.split(','))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::dump_flag::DumpFlag;
+
+    use super::Set;
+
+    #[test]
+    fn flag_aliases() {
+        for (name, flag) in [
+            ("yul", DumpFlag::Yul),
+            ("EthIR", DumpFlag::EthIR),
+            ("evm", DumpFlag::EVM),
+            ("lll", DumpFlag::LLL),
+            (" llvm ", DumpFlag::LLVM),
+            ("asm", DumpFlag::Assembly),
+            ("assembly", DumpFlag::Assembly),
+            ("size", DumpFlag::SizeReport),
+            ("size-report", DumpFlag::SizeReport),
+            ("verified-llvm", DumpFlag::VerifiedLLVM),
+            ("deps", DumpFlag::Dependencies),
+            ("dependencies", DumpFlag::Dependencies),
+            ("stats", DumpFlag::Statistics),
+            ("statistics", DumpFlag::Statistics),
+        ] {
+            assert_eq!(DumpFlag::from_str(name).expect("Always valid"), flag);
+        }
+        assert!(DumpFlag::from_str("bytecode").is_err());
+    }
+
+    #[test]
+    fn list_parsed() {
+        let set = Set::from_str("yul,llvm,,asm,llvm").expect("Always valid");
+        assert_eq!(
+            set.to_vec(),
+            vec![DumpFlag::Yul, DumpFlag::LLVM, DumpFlag::Assembly]
+        );
+        assert!(!set.contains(DumpFlag::EVM));
+        assert!(Set::from_str("").expect("Always valid").is_empty());
+        assert!(Set::from_str("yul,unknown").is_err());
+    }
+
+    #[test]
+    fn flag_removed() {
+        let mut set = DumpFlag::ALL.into_iter().collect::<Set>();
+        set.remove(DumpFlag::Yul);
+        assert!(!set.contains(DumpFlag::Yul));
+        assert_eq!(set.to_vec().len(), DumpFlag::ALL.len() - 1);
+    }
+}
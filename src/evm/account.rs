@@ -0,0 +1,160 @@
+//!
+//! Translates the account abstraction entry points.
+//!
+
+use crate::context::account_method::AccountMethod;
+use crate::context::address_space::AddressSpace;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// Translates the account abstraction lifecycle `method` entry point.
+///
+/// The system methods throw unless the `caller` is the bootloader. The `body` is translated after
+/// the guard, and the method returns the magic value if it is expected by the bootloader, or
+/// empty data otherwise. If the `body` terminates the block, nothing is returned implicitly.
+///
+pub fn entry_point<'ctx, D, F>(
+    context: &mut Context<'ctx, D>,
+    method: AccountMethod,
+    caller: inkwell::values::IntValue<'ctx>,
+    body: F,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+    F: FnOnce(&mut Context<'ctx, D>) -> anyhow::Result<()>,
+{
+    if method.is_bootloader_only() {
+        check_bootloader(context, caller)?;
+    }
+
+    body(context)?;
+    if context.basic_block().get_terminator().is_some() {
+        return Ok(None);
+    }
+
+    context.with_evm_op("RETURN", |context| {
+        let return_size = if method.has_magic() {
+            let magic = Context::<D>::selector(method.signature().as_str()) as u64;
+            let magic = context
+                .build_left_align_bytes(context.field_const(magic), compiler_common::SIZE_X32);
+            let pointer = context.access_memory(
+                context.field_const(0),
+                AddressSpace::Heap,
+                "account_magic_pointer",
+            );
+            context.build_store(pointer, magic);
+            compiler_common::SIZE_FIELD
+        } else {
+            0
+        };
+
+        let operands = [
+            context.field_const(0),
+            context.field_const(return_size as u64),
+        ];
+        crate::evm::r#return::r#return(context, operands.into())
+    })
+}
+
+///
+/// Throws unless the `caller` is the bootloader.
+///
+pub fn check_bootloader<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    caller: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    context.with_evm_op("ACCOUNT_BOOTLOADER_CHECK", |context| {
+        let bootloader = context.field_const_hex(AccountMethod::BOOTLOADER_ADDRESS)?;
+        let caller = context.build_address_mask(caller);
+        let is_caller_not_bootloader = context.builder().build_int_compare(
+            inkwell::IntPredicate::NE,
+            caller,
+            bootloader,
+            context
+                .value_name("account_is_caller_not_bootloader")
+                .as_str(),
+        );
+        context.build_if(
+            is_caller_not_bootloader,
+            "account_caller_not_bootloader",
+            |context| {
+                context.write_error(AccountMethod::ERROR_NOT_BOOTLOADER);
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            },
+        )
+    })
+}
+
+///
+/// Throws unless the signature at the calldata `offset` has the expected `length`, and is within
+/// the calldata bounds.
+///
+pub fn check_signature<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    offset: inkwell::values::IntValue<'ctx>,
+    length: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    context.with_evm_op("ACCOUNT_SIGNATURE_CHECK", |context| {
+        let is_length_invalid = context.builder().build_int_compare(
+            inkwell::IntPredicate::NE,
+            length,
+            context.field_const(AccountMethod::SIGNATURE_LENGTH as u64),
+            context
+                .value_name("account_signature_is_length_invalid")
+                .as_str(),
+        );
+
+        let end = context.builder().build_int_add(
+            offset,
+            length,
+            context.value_name("account_signature_end").as_str(),
+        );
+        let is_end_overflow = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            end,
+            offset,
+            context
+                .value_name("account_signature_is_end_overflow")
+                .as_str(),
+        );
+        let calldata_size = context.read_header(AddressSpace::Parent).size(context);
+        let is_out_of_bounds = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            end,
+            calldata_size,
+            context
+                .value_name("account_signature_is_out_of_bounds")
+                .as_str(),
+        );
+
+        let is_window_invalid = context.builder().build_or(
+            is_end_overflow,
+            is_out_of_bounds,
+            context
+                .value_name("account_signature_is_window_invalid")
+                .as_str(),
+        );
+        let is_signature_invalid = context.builder().build_or(
+            is_length_invalid,
+            is_window_invalid,
+            context.value_name("account_signature_is_invalid").as_str(),
+        );
+        context.build_if(
+            is_signature_invalid,
+            "account_signature_invalid",
+            |context| {
+                context.write_error(AccountMethod::ERROR_INVALID_SIGNATURE);
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            },
+        )
+    })
+}
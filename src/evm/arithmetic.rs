@@ -3,268 +3,423 @@
 //!
 
 use inkwell::values::BasicValue;
+use num::One;
+use num::Zero;
 
+use crate::context::argument::Argument;
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
 ///
 /// Translates the arithmetic addition.
 ///
+/// Panics on the unsigned overflow if the overflow checks are enabled.
+///
 pub fn addition<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_int_add(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "addition_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("ADD", |context| {
+        operands.check_count(2)?;
+        let operand_1 = operands.int(0)?;
+        let operand_2 = operands.int(1)?;
+
+        let result = context.builder().build_int_add(
+            operand_1,
+            operand_2,
+            context.value_name("addition_result").as_str(),
+        );
+        if context.are_overflow_checks_enabled() {
+            let is_overflow = context.builder().build_int_compare(
+                inkwell::IntPredicate::ULT,
+                result,
+                operand_1,
+                context.value_name("addition_is_overflow").as_str(),
+            );
+            context.build_overflow_check(is_overflow, "addition");
+        }
+
+        Ok(Some(result.as_basic_value_enum()))
+    })
 }
 
 ///
 /// Translates the arithmetic subtraction.
 ///
+/// Panics on the unsigned underflow if the overflow checks are enabled.
+///
 pub fn subtraction<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_int_sub(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "subtraction_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("SUB", |context| {
+        operands.check_count(2)?;
+        let operand_1 = operands.int(0)?;
+        let operand_2 = operands.int(1)?;
+
+        if context.are_overflow_checks_enabled() {
+            let is_overflow = context.builder().build_int_compare(
+                inkwell::IntPredicate::ULT,
+                operand_1,
+                operand_2,
+                context.value_name("subtraction_is_overflow").as_str(),
+            );
+            context.build_overflow_check(is_overflow, "subtraction");
+        }
+
+        Ok(Some(
+            context
+                .builder()
+                .build_int_sub(
+                    operand_1,
+                    operand_2,
+                    context.value_name("subtraction_result").as_str(),
+                )
+                .as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
 /// Translates the arithmetic multiplication.
 ///
+/// Panics on the unsigned overflow if the overflow checks are enabled, which is detected by
+/// dividing the result back.
+///
 pub fn multiplication<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_int_mul(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "multiplication_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("MUL", |context| {
+        operands.check_count(2)?;
+        let operand_1 = operands.int(0)?;
+        let operand_2 = operands.int(1)?;
+
+        let result = context.builder().build_int_mul(
+            operand_1,
+            operand_2,
+            context.value_name("multiplication_result").as_str(),
+        );
+        if context.are_overflow_checks_enabled() {
+            let is_operand_2_zero = context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                operand_2,
+                context.field_const(0),
+                context
+                    .value_name("multiplication_is_operand_2_zero")
+                    .as_str(),
+            );
+            let divider = context.builder().build_select(
+                is_operand_2_zero,
+                context.field_const(1),
+                operand_2,
+                context.value_name("multiplication_divider").as_str(),
+            );
+            let quotient = context.builder().build_int_unsigned_div(
+                result,
+                divider.into_int_value(),
+                context.value_name("multiplication_quotient").as_str(),
+            );
+            let is_quotient_different = context.builder().build_int_compare(
+                inkwell::IntPredicate::NE,
+                quotient,
+                operand_1,
+                context
+                    .value_name("multiplication_is_quotient_different")
+                    .as_str(),
+            );
+            let is_overflow = context.builder().build_and(
+                is_quotient_different,
+                context.builder().build_not(
+                    is_operand_2_zero,
+                    context
+                        .value_name("multiplication_is_operand_2_non_zero")
+                        .as_str(),
+                ),
+                context.value_name("multiplication_is_overflow").as_str(),
+            );
+            context.build_overflow_check(is_overflow, "multiplication");
+        }
+
+        Ok(Some(result.as_basic_value_enum()))
+    })
 }
 
 ///
 /// Translates the arithmetic division.
 ///
+/// Returns zero if the divider is zero.
+///
 pub fn division<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let zero_block = context.append_basic_block("division_zero");
-    let non_zero_block = context.append_basic_block("division_non_zero");
-    let join_block = context.append_basic_block("division_join");
+    context.with_evm_op("DIV", |context| {
+        operands.check_count(2)?;
 
-    let result_pointer = context.build_alloca(context.field_type(), "division_result_pointer");
-    let condition = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        arguments[1].into_int_value(),
-        context.field_const(0),
-        "division_is_divider_zero",
-    );
-    context.build_conditional_branch(condition, zero_block, non_zero_block);
-
-    context.set_basic_block(non_zero_block);
-    let result = context.builder().build_int_unsigned_div(
-        arguments[0].into_int_value(),
-        arguments[1].into_int_value(),
-        "division_result_non_zero",
-    );
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(zero_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "division_result");
-
-    Ok(Some(result))
+        let result = build_guarded(
+            context,
+            operands.int(0)?,
+            operands.int(1)?,
+            Operation::Division,
+        );
+        Ok(Some(result.as_basic_value_enum()))
+    })
 }
 
 ///
 /// Translates the arithmetic remainder.
 ///
+/// Returns zero if the modulo is zero.
+///
 pub fn remainder<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let zero_block = context.append_basic_block("remainder_zero");
-    let non_zero_block = context.append_basic_block("remainder_non_zero");
-    let join_block = context.append_basic_block("remainder_join");
+    context.with_evm_op("MOD", |context| {
+        operands.check_count(2)?;
 
-    let result_pointer = context.build_alloca(context.field_type(), "remainder_result_pointer");
-    let condition = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        arguments[1].into_int_value(),
-        context.field_const(0),
-        "remainder_is_modulo_zero",
-    );
-    context.build_conditional_branch(condition, zero_block, non_zero_block);
-
-    context.set_basic_block(non_zero_block);
-    let result = context.builder().build_int_unsigned_rem(
-        arguments[0].into_int_value(),
-        arguments[1].into_int_value(),
-        "remainder_result_non_zero",
-    );
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(zero_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "remainder_result");
-
-    Ok(Some(result))
+        let result = build_guarded(
+            context,
+            operands.int(0)?,
+            operands.int(1)?,
+            Operation::Remainder,
+        );
+        Ok(Some(result.as_basic_value_enum()))
+    })
 }
 
 ///
 /// Translates the signed arithmetic division.
 ///
+/// Returns zero if the divider is zero, and the dividend if the division overflows.
+///
 pub fn division_signed<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let zero_block = context.append_basic_block("division_signed_zero");
-    let non_zero_block = context.append_basic_block("division_signed_non_zero");
-    let overflow_block = context.append_basic_block("division_signed_overflow");
-    let non_overflow_block = context.append_basic_block("division_signed_non_overflow");
-    let join_block = context.append_basic_block("division_signed_join");
-
-    let result_pointer =
-        context.build_alloca(context.field_type(), "division_signed_result_pointer");
-    let condition_is_divider_zero = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        arguments[1].into_int_value(),
-        context.field_const(0),
-        "division_signed_is_divider_zero",
-    );
-    context.build_conditional_branch(condition_is_divider_zero, zero_block, non_zero_block);
+    context.with_evm_op("SDIV", |context| {
+        operands.check_count(2)?;
 
-    context.set_basic_block(non_zero_block);
-    let condition_is_divided_int_min = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        arguments[0].into_int_value(),
-        context.field_const_str("8000000000000000000000000000000000000000000000000000000000000000"),
-        "division_signed_is_divided_int_min",
-    );
-    let condition_is_divider_minus_one = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        arguments[1].into_int_value(),
-        context.field_type().const_all_ones(),
-        "division_signed_is_divider_minus_one",
-    );
-    let condition_is_overflow = context.builder().build_and(
-        condition_is_divided_int_min,
-        condition_is_divider_minus_one,
-        "division_signed_is_overflow",
-    );
-    context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
+        let result = build_guarded(
+            context,
+            operands.int(0)?,
+            operands.int(1)?,
+            Operation::DivisionSigned,
+        );
+        Ok(Some(result.as_basic_value_enum()))
+    })
+}
 
-    context.set_basic_block(overflow_block);
-    context.build_store(result_pointer, arguments[0]);
-    context.build_unconditional_branch(join_block);
+///
+/// Translates the signed arithmetic remainder.
+///
+/// Returns zero if the modulo is zero or minus one.
+///
+pub fn remainder_signed<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SMOD", |context| {
+        operands.check_count(2)?;
 
-    context.set_basic_block(non_overflow_block);
-    let result = context.builder().build_int_signed_div(
-        arguments[0].into_int_value(),
-        arguments[1].into_int_value(),
-        "division_signed_result_non_zero",
-    );
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
+        let result = build_guarded(
+            context,
+            operands.int(0)?,
+            operands.int(1)?,
+            Operation::RemainderSigned,
+        );
+        Ok(Some(result.as_basic_value_enum()))
+    })
+}
 
-    context.set_basic_block(zero_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
+///
+/// The guarded division operation.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    /// The unsigned division.
+    Division,
+    /// The unsigned remainder.
+    Remainder,
+    /// The signed division.
+    DivisionSigned,
+    /// The signed remainder.
+    RemainderSigned,
+}
 
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "division_signed_result");
+impl Operation {
+    ///
+    /// Returns the LLVM value name prefix.
+    ///
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Division => "division",
+            Self::Remainder => "remainder",
+            Self::DivisionSigned => "division_signed",
+            Self::RemainderSigned => "remainder_signed",
+        }
+    }
 
-    Ok(Some(result))
+    ///
+    /// Whether the operation is signed and must handle the minus one divider separately.
+    ///
+    fn is_signed(&self) -> bool {
+        matches!(self, Self::DivisionSigned | Self::RemainderSigned)
+    }
 }
 
 ///
-/// Translates the signed arithmetic remainder.
+/// Builds the division or remainder with the EVM semantics instead of the LLVM undefined behavior.
 ///
-pub fn remainder_signed<'ctx, D>(
-    context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
-) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+/// The zero divider yields zero. The signed division by minus one is a negation, which wraps the
+/// minimal value around, and the signed remainder by minus one is zero.
+///
+/// The guards are branchless selects, and they are omitted if the divider is a constant.
+///
+fn build_guarded<'ctx, D>(
+    context: &Context<'ctx, D>,
+    dividend: inkwell::values::IntValue<'ctx>,
+    divider: inkwell::values::IntValue<'ctx>,
+    operation: Operation,
+) -> inkwell::values::IntValue<'ctx>
 where
     D: Dependency,
 {
-    let zero_block = context.append_basic_block("remainder_signed_zero");
-    let non_zero_block = context.append_basic_block("remainder_signed_non_zero");
-    let join_block = context.append_basic_block("remainder_signed_join");
+    let name = operation.name();
+    let build_operation = |divider: inkwell::values::IntValue<'ctx>| {
+        let name = format!("{}_result", name);
+        match operation {
+            Operation::Division => context.builder().build_int_unsigned_div(
+                dividend,
+                divider,
+                context.value_name(name.as_str()).as_str(),
+            ),
+            Operation::Remainder => context.builder().build_int_unsigned_rem(
+                dividend,
+                divider,
+                context.value_name(name.as_str()).as_str(),
+            ),
+            Operation::DivisionSigned => context.builder().build_int_signed_div(
+                dividend,
+                divider,
+                context.value_name(name.as_str()).as_str(),
+            ),
+            Operation::RemainderSigned => context.builder().build_int_signed_rem(
+                dividend,
+                divider,
+                context.value_name(name.as_str()).as_str(),
+            ),
+        }
+    };
+    let build_minus_one_result = || match operation {
+        Operation::DivisionSigned => context.builder().build_int_neg(
+            dividend,
+            context
+                .value_name(format!("{}_negated", name).as_str())
+                .as_str(),
+        ),
+        _ => context.field_const(0),
+    };
+
+    if let Some(constant) = Argument::constant_of(divider.as_basic_value_enum()) {
+        let is_zero = constant.is_zero();
+        let is_minus_one =
+            constant == (num::BigUint::one() << compiler_common::BITLENGTH_FIELD) - 1u32;
+        return if is_zero {
+            context.field_const(0)
+        } else if is_minus_one && operation.is_signed() {
+            build_minus_one_result()
+        } else {
+            build_operation(divider)
+        };
+    }
 
-    let result_pointer =
-        context.build_alloca(context.field_type(), "remainder_signed_result_pointer");
-    let condition = context.builder().build_int_compare(
+    let is_divider_zero = context.builder().build_int_compare(
         inkwell::IntPredicate::EQ,
-        arguments[1].into_int_value(),
+        divider,
         context.field_const(0),
-        "remainder_signed_is_modulo_zero",
-    );
-    context.build_conditional_branch(condition, zero_block, non_zero_block);
-
-    context.set_basic_block(non_zero_block);
-    let result = context.builder().build_int_signed_rem(
-        arguments[0].into_int_value(),
-        arguments[1].into_int_value(),
-        "remainder_signed_result_non_zero",
+        context
+            .value_name(format!("{}_is_divider_zero", name).as_str())
+            .as_str(),
     );
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
+    let mut is_divider_special = is_divider_zero;
+    let mut is_divider_minus_one = None;
+    if operation.is_signed() {
+        let is_minus_one = context.builder().build_int_compare(
+            inkwell::IntPredicate::EQ,
+            divider,
+            context.field_type().const_all_ones(),
+            context
+                .value_name(format!("{}_is_divider_minus_one", name).as_str())
+                .as_str(),
+        );
+        is_divider_special = context.builder().build_or(
+            is_divider_zero,
+            is_minus_one,
+            context
+                .value_name(format!("{}_is_divider_special", name).as_str())
+                .as_str(),
+        );
+        is_divider_minus_one = Some(is_minus_one);
+    }
 
-    context.set_basic_block(zero_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "remainder_signed_result");
-
-    Ok(Some(result))
+    let divider_safe = context
+        .builder()
+        .build_select(
+            is_divider_special,
+            context.field_const(1),
+            divider,
+            context
+                .value_name(format!("{}_divider_safe", name).as_str())
+                .as_str(),
+        )
+        .into_int_value();
+    let mut result = build_operation(divider_safe);
+    if let Some(is_divider_minus_one) = is_divider_minus_one {
+        result = context
+            .builder()
+            .build_select(
+                is_divider_minus_one,
+                build_minus_one_result(),
+                result,
+                context
+                    .value_name(format!("{}_result_minus_one_checked", name).as_str())
+                    .as_str(),
+            )
+            .into_int_value();
+    }
+    context
+        .builder()
+        .build_select(
+            is_divider_zero,
+            context.field_const(0),
+            result,
+            context
+                .value_name(format!("{}_result_zero_checked", name).as_str())
+                .as_str(),
+        )
+        .into_int_value()
 }
@@ -4,6 +4,7 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -12,21 +13,25 @@ use crate::Dependency;
 ///
 pub fn or<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_or(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "or_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("OR", |context| {
+        operands.check_count(2)?;
+
+        Ok(Some(
+            context
+                .builder()
+                .build_or(
+                    operands.int(0)?,
+                    operands.int(1)?,
+                    context.value_name("or_result").as_str(),
+                )
+                .as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
@@ -34,21 +39,25 @@ where
 ///
 pub fn xor<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_xor(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "xor_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("XOR", |context| {
+        operands.check_count(2)?;
+
+        Ok(Some(
+            context
+                .builder()
+                .build_xor(
+                    operands.int(0)?,
+                    operands.int(1)?,
+                    context.value_name("xor_result").as_str(),
+                )
+                .as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
@@ -56,21 +65,25 @@ where
 ///
 pub fn and<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(Some(
-        context
-            .builder()
-            .build_and(
-                arguments[0].into_int_value(),
-                arguments[1].into_int_value(),
-                "and_result",
-            )
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("AND", |context| {
+        operands.check_count(2)?;
+
+        Ok(Some(
+            context
+                .builder()
+                .build_and(
+                    operands.int(0)?,
+                    operands.int(1)?,
+                    context.value_name("and_result").as_str(),
+                )
+                .as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
@@ -78,40 +91,59 @@ where
 ///
 pub fn shift_left<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let overflow_block = context.append_basic_block("shift_left_overflow");
-    let non_overflow_block = context.append_basic_block("shift_left_non_overflow");
-    let join_block = context.append_basic_block("shift_left_join");
-
-    let result_pointer = context.build_alloca(context.field_type(), "shift_left_result_pointer");
-    let condition_is_overflow = context.builder().build_int_compare(
-        inkwell::IntPredicate::UGT,
-        arguments[0].into_int_value(),
-        context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
-        "shift_left_is_overflow",
-    );
-    context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
-
-    context.set_basic_block(overflow_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(non_overflow_block);
-    let value = context.builder().build_left_shift(
-        arguments[1].into_int_value(),
-        arguments[0].into_int_value(),
-        "shift_left_non_overflow_result",
-    );
-    context.build_store(result_pointer, value);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let value = context.build_load(result_pointer, "shift_left_result");
-    Ok(Some(value))
+    context.with_evm_op("SHL", |context| {
+        operands.check_count(2)?;
+
+        if let Some(shift) = operands.constant(0) {
+            if *shift >= num::BigUint::from(compiler_common::BITLENGTH_FIELD) {
+                return Ok(Some(context.field_const(0).as_basic_value_enum()));
+            }
+            let value = context.builder().build_left_shift(
+                operands.int(1)?,
+                operands.int(0)?,
+                context.value_name("shift_left_constant_result").as_str(),
+            );
+            return Ok(Some(value.as_basic_value_enum()));
+        }
+
+        let overflow_block = context.append_basic_block("shift_left_overflow");
+        let non_overflow_block = context.append_basic_block("shift_left_non_overflow");
+        let join_block = context.append_basic_block("shift_left_join");
+
+        let result_pointer =
+            context.build_alloca(context.field_type(), "shift_left_result_pointer");
+        let condition_is_overflow = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            operands.int(0)?,
+            context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
+            context.value_name("shift_left_is_overflow").as_str(),
+        );
+        context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
+
+        context.set_basic_block(overflow_block);
+        context.build_store(result_pointer, context.field_const(0));
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(non_overflow_block);
+        let value = context.builder().build_left_shift(
+            operands.int(1)?,
+            operands.int(0)?,
+            context
+                .value_name("shift_left_non_overflow_result")
+                .as_str(),
+        );
+        context.build_store(result_pointer, value);
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(join_block);
+        let value = context.build_load(result_pointer, "shift_left_result");
+        Ok(Some(value))
+    })
 }
 
 ///
@@ -119,41 +151,61 @@ where
 ///
 pub fn shift_right<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let overflow_block = context.append_basic_block("shift_right_overflow");
-    let non_overflow_block = context.append_basic_block("shift_right_non_overflow");
-    let join_block = context.append_basic_block("shift_right_join");
-
-    let result_pointer = context.build_alloca(context.field_type(), "shift_right_result_pointer");
-    let condition_is_overflow = context.builder().build_int_compare(
-        inkwell::IntPredicate::UGT,
-        arguments[0].into_int_value(),
-        context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
-        "shift_right_is_overflow",
-    );
-    context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
-
-    context.set_basic_block(overflow_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(non_overflow_block);
-    let value = context.builder().build_right_shift(
-        arguments[1].into_int_value(),
-        arguments[0].into_int_value(),
-        false,
-        "shift_right_non_overflow_result",
-    );
-    context.build_store(result_pointer, value);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let value = context.build_load(result_pointer, "shift_right_result");
-    Ok(Some(value))
+    context.with_evm_op("SHR", |context| {
+        operands.check_count(2)?;
+
+        if let Some(shift) = operands.constant(0) {
+            if *shift >= num::BigUint::from(compiler_common::BITLENGTH_FIELD) {
+                return Ok(Some(context.field_const(0).as_basic_value_enum()));
+            }
+            let value = context.builder().build_right_shift(
+                operands.int(1)?,
+                operands.int(0)?,
+                false,
+                context.value_name("shift_right_constant_result").as_str(),
+            );
+            return Ok(Some(value.as_basic_value_enum()));
+        }
+
+        let overflow_block = context.append_basic_block("shift_right_overflow");
+        let non_overflow_block = context.append_basic_block("shift_right_non_overflow");
+        let join_block = context.append_basic_block("shift_right_join");
+
+        let result_pointer =
+            context.build_alloca(context.field_type(), "shift_right_result_pointer");
+        let condition_is_overflow = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            operands.int(0)?,
+            context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
+            context.value_name("shift_right_is_overflow").as_str(),
+        );
+        context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
+
+        context.set_basic_block(overflow_block);
+        context.build_store(result_pointer, context.field_const(0));
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(non_overflow_block);
+        let value = context.builder().build_right_shift(
+            operands.int(1)?,
+            operands.int(0)?,
+            false,
+            context
+                .value_name("shift_right_non_overflow_result")
+                .as_str(),
+        );
+        context.build_store(result_pointer, value);
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(join_block);
+        let value = context.build_load(result_pointer, "shift_right_result");
+        Ok(Some(value))
+    })
 }
 
 ///
@@ -161,70 +213,105 @@ where
 ///
 pub fn shift_right_arithmetic<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let overflow_block = context.append_basic_block("shift_right_arithmetic_overflow");
-    let overflow_positive_block =
-        context.append_basic_block("shift_right_arithmetic_overflow_positive");
-    let overflow_negative_block =
-        context.append_basic_block("shift_right_arithmetic_overflow_negative");
-    let non_overflow_block = context.append_basic_block("shift_right_arithmetic_non_overflow");
-    let join_block = context.append_basic_block("shift_right_arithmetic_join");
-
-    let result_pointer = context.build_alloca(
-        context.field_type(),
-        "shift_right_arithmetic_result_pointer",
-    );
-    let condition_is_overflow = context.builder().build_int_compare(
-        inkwell::IntPredicate::UGT,
-        arguments[0].into_int_value(),
-        context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
-        "shift_right_arithmetic_is_overflow",
-    );
-    context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
-
-    context.set_basic_block(overflow_block);
-    let sign_bit = context.builder().build_right_shift(
-        arguments[1].into_int_value(),
-        context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
-        false,
-        "shift_right_arithmetic_sign_bit",
-    );
-    let condition_is_negative = context.builder().build_int_truncate_or_bit_cast(
-        sign_bit,
-        context.integer_type(compiler_common::BITLENGTH_BOOLEAN),
-        "shift_right_arithmetic_sign_bit_truncated",
-    );
-    context.build_conditional_branch(
-        condition_is_negative,
-        overflow_negative_block,
-        overflow_positive_block,
-    );
-
-    context.set_basic_block(overflow_positive_block);
-    context.build_store(result_pointer, context.field_const(0));
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(overflow_negative_block);
-    context.build_store(result_pointer, context.field_type().const_all_ones());
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(non_overflow_block);
-    let value = context.builder().build_right_shift(
-        arguments[1].into_int_value(),
-        arguments[0].into_int_value(),
-        true,
-        "shift_right_arithmetic_non_overflow_result",
-    );
-    context.build_store(result_pointer, value);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let value = context.build_load(result_pointer, "shift_right_arithmetic_result");
-    Ok(Some(value))
+    context.with_evm_op("SAR", |context| {
+        operands.check_count(2)?;
+
+        if let Some(shift) = operands.constant(0) {
+            if *shift >= num::BigUint::from(compiler_common::BITLENGTH_FIELD) {
+                let value = context.builder().build_right_shift(
+                    operands.int(1)?,
+                    context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
+                    true,
+                    context
+                        .value_name("shift_right_arithmetic_constant_overflow_result")
+                        .as_str(),
+                );
+                return Ok(Some(value.as_basic_value_enum()));
+            }
+            let value = context.builder().build_right_shift(
+                operands.int(1)?,
+                operands.int(0)?,
+                true,
+                context
+                    .value_name("shift_right_arithmetic_constant_result")
+                    .as_str(),
+            );
+            return Ok(Some(value.as_basic_value_enum()));
+        }
+
+        let overflow_block = context.append_basic_block("shift_right_arithmetic_overflow");
+        let overflow_positive_block =
+            context.append_basic_block("shift_right_arithmetic_overflow_positive");
+        let overflow_negative_block =
+            context.append_basic_block("shift_right_arithmetic_overflow_negative");
+        let non_overflow_block = context.append_basic_block("shift_right_arithmetic_non_overflow");
+        let join_block = context.append_basic_block("shift_right_arithmetic_join");
+
+        let result_pointer = context.build_alloca(
+            context.field_type(),
+            "shift_right_arithmetic_result_pointer",
+        );
+        let condition_is_overflow = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            operands.int(0)?,
+            context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
+            context
+                .value_name("shift_right_arithmetic_is_overflow")
+                .as_str(),
+        );
+        context.build_conditional_branch(condition_is_overflow, overflow_block, non_overflow_block);
+
+        context.set_basic_block(overflow_block);
+        let sign_bit = context.builder().build_right_shift(
+            operands.int(1)?,
+            context.field_const((compiler_common::BITLENGTH_FIELD - 1) as u64),
+            false,
+            context
+                .value_name("shift_right_arithmetic_sign_bit")
+                .as_str(),
+        );
+        let condition_is_negative = context.builder().build_int_truncate_or_bit_cast(
+            sign_bit,
+            context.integer_type(compiler_common::BITLENGTH_BOOLEAN),
+            context
+                .value_name("shift_right_arithmetic_sign_bit_truncated")
+                .as_str(),
+        );
+        context.build_conditional_branch(
+            condition_is_negative,
+            overflow_negative_block,
+            overflow_positive_block,
+        );
+
+        context.set_basic_block(overflow_positive_block);
+        context.build_store(result_pointer, context.field_const(0));
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(overflow_negative_block);
+        context.build_store(result_pointer, context.field_type().const_all_ones());
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(non_overflow_block);
+        let value = context.builder().build_right_shift(
+            operands.int(1)?,
+            operands.int(0)?,
+            true,
+            context
+                .value_name("shift_right_arithmetic_non_overflow_result")
+                .as_str(),
+        );
+        context.build_store(result_pointer, value);
+        context.build_unconditional_branch(join_block);
+
+        context.set_basic_block(join_block);
+        let value = context.build_load(result_pointer, "shift_right_arithmetic_result");
+        Ok(Some(value))
+    })
 }
 
 ///
@@ -232,30 +319,35 @@ where
 ///
 pub fn byte<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let byte_index = context.builder().build_int_sub(
-        context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
-        arguments[0].into_int_value(),
-        "byte_index",
-    );
-    let byte_bits_offset = context.builder().build_int_mul(
-        byte_index,
-        context.field_const(compiler_common::BITLENGTH_BYTE as u64),
-        "byte_bits_offset",
-    );
-    let value_shifted = context.builder().build_right_shift(
-        arguments[1].into_int_value(),
-        byte_bits_offset,
-        false,
-        "value_shifted",
-    );
-    let byte_result =
-        context
-            .builder()
-            .build_and(value_shifted, context.field_const(0xff), "byte_result");
-    Ok(Some(byte_result.as_basic_value_enum()))
+    context.with_evm_op("BYTE", |context| {
+        operands.check_count(2)?;
+
+        let byte_index = context.builder().build_int_sub(
+            context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
+            operands.int(0)?,
+            context.value_name("byte_index").as_str(),
+        );
+        let byte_bits_offset = context.builder().build_int_mul(
+            byte_index,
+            context.field_const(compiler_common::BITLENGTH_BYTE as u64),
+            context.value_name("byte_bits_offset").as_str(),
+        );
+        let value_shifted = context.builder().build_right_shift(
+            operands.int(1)?,
+            byte_bits_offset,
+            false,
+            context.value_name("value_shifted").as_str(),
+        );
+        let byte_result = context.builder().build_and(
+            value_shifted,
+            context.field_const(0xff),
+            context.value_name("byte_result").as_str(),
+        );
+        Ok(Some(byte_result.as_basic_value_enum()))
+    })
 }
@@ -1,11 +1,18 @@
 //!
 //! Translates the calldata instructions.
 //!
+//! The calldata access depends on the context calldata mode. In the lazy mode, the loads read
+//! the parent memory directly, and the copies only transfer the requested ranges. In the staged
+//! mode, the calldata is copied to the heap on the first access, and the accesses read the copy.
+//!
 
 use inkwell::values::BasicValue;
 
+use crate::context::abi_type::AbiType;
 use crate::context::address_space::AddressSpace;
+use crate::context::calldata_mode::CalldataMode;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -14,22 +21,19 @@ use crate::Dependency;
 ///
 pub fn load<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 1],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let offset_shift = compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
-    let offset = context.builder().build_int_add(
-        arguments[0].into_int_value(),
-        context.field_const(offset_shift as u64),
-        "calldata_offset",
-    );
+    context.with_evm_op("CALLDATALOAD", |context| {
+        operands.check_count(1)?;
 
-    let pointer = context.access_memory(offset, AddressSpace::Parent, "calldata_pointer");
-    let value = context.build_load(pointer, "calldata_value");
+        let pointer = pointer(context, operands.int(0)?, "calldata_pointer")?;
+        let value = context.build_load(pointer, "calldata_value");
 
-    Ok(Some(value))
+        Ok(Some(value))
+    })
 }
 
 ///
@@ -41,14 +45,11 @@ pub fn size<'ctx, D>(
 where
     D: Dependency,
 {
-    let header = context.read_header(AddressSpace::Parent);
-    let value = context.builder().build_and(
-        header,
-        context.field_const(0x00000000ffffffff),
-        "calldata_size",
-    );
+    context.with_evm_op("CALLDATASIZE", |context| {
+        let value = context.read_header(AddressSpace::Parent).size(context);
 
-    Ok(Some(value.as_basic_value_enum()))
+        Ok(Some(value.as_basic_value_enum()))
+    })
 }
 
 ///
@@ -56,38 +57,271 @@ where
 ///
 pub fn copy<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 3],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let destination = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "calldata_copy_destination_pointer",
-    );
+    context.with_evm_op("CALLDATACOPY", |context| {
+        operands.check_count(3)?;
 
-    let source_offset_shift = compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
-    let source_offset = context.builder().build_int_add(
-        arguments[1].into_int_value(),
-        context.field_const(source_offset_shift as u64),
-        "calldata_copy_source_offset",
-    );
-    let source = context.access_memory(
-        source_offset,
-        AddressSpace::Parent,
-        "calldata_copy_source_pointer",
-    );
+        let destination = context.access_memory(
+            operands.int(0)?,
+            AddressSpace::Heap,
+            "calldata_copy_destination_pointer",
+        );
 
-    let size = arguments[2].into_int_value();
+        let source = pointer(context, operands.int(1)?, "calldata_copy_source_pointer")?;
 
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyFromParent,
-        destination,
-        source,
-        size,
-        "calldata_copy_memcpy_from_child",
-    );
+        let size = operands.int(2)?;
 
-    Ok(None)
+        let intrinsic = match context.calldata_mode() {
+            CalldataMode::Staged => IntrinsicFunction::MemoryCopy,
+            CalldataMode::Lazy => IntrinsicFunction::MemoryCopyFromParent,
+        };
+        context.build_memcpy(
+            intrinsic,
+            destination,
+            source,
+            size,
+            "calldata_copy_memcpy_from_child",
+        );
+
+        Ok(None)
+    })
+}
+
+///
+/// Decodes the arguments of `head_types` following the selector directly from the calldata.
+///
+/// The fast path for the functions with only static parameters, whose arguments are loaded into
+/// values without staging the calldata on the heap. Throws with empty data if the calldata is
+/// shorter than the selector and head. The sub-word values are cleaned up according to their
+/// types.
+///
+pub fn decode_arguments<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    head_types: &[AbiType],
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("CALLDATALOAD", |context| {
+        for abi_type in head_types.iter() {
+            abi_type.validate()?;
+        }
+
+        let calldata_size = context.read_header(AddressSpace::Parent).size(context);
+        let arguments_size =
+            compiler_common::SIZE_X32 + head_types.len() * compiler_common::SIZE_FIELD;
+        let is_long_enough = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGE,
+            calldata_size,
+            context.field_const(arguments_size as u64),
+            context
+                .value_name("calldata_decode_is_long_enough")
+                .as_str(),
+        );
+        let decode_block = context.append_basic_block("calldata_decode_block");
+        let short_block = context.append_basic_block("calldata_decode_short_block");
+        context.build_conditional_branch(is_long_enough, decode_block, short_block);
+
+        context.set_basic_block(short_block);
+        context.write_header_size(context.field_const(0), AddressSpace::Parent);
+        context.build_unconditional_branch(context.try_function()?.throw_block);
+
+        context.set_basic_block(decode_block);
+
+        let mut values = Vec::with_capacity(head_types.len());
+        for (index, abi_type) in head_types.iter().enumerate() {
+            let offset = context.field_const(
+                (compiler_common::SIZE_X32 + index * compiler_common::SIZE_FIELD) as u64,
+            );
+            let pointer = pointer(
+                context,
+                offset,
+                format!("calldata_decode_pointer_{}", index).as_str(),
+            )?;
+            let value = context
+                .build_load(pointer, format!("calldata_decode_value_{}", index).as_str())
+                .into_int_value();
+            values.push(abi_type.cleanup(context, value));
+        }
+
+        Ok(values)
+    })
+}
+
+///
+/// Returns the pointer to the calldata at `offset`, according to the context calldata mode.
+///
+/// In the lazy mode, the pointer refers to the parent memory, so the frontends can read or
+/// forward the calldata ranges without copying them to the heap. In the staged mode, the pointer
+/// refers to the heap copy, and the offsets past the calldata end are clamped to the zero word
+/// following it.
+///
+pub fn pointer<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    offset: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<inkwell::values::PointerValue<'ctx>>
+where
+    D: Dependency,
+{
+    match context.calldata_mode() {
+        CalldataMode::Staged => {
+            let staged_offset = stage(context)?;
+            let calldata_size = context.read_header(AddressSpace::Parent).size(context);
+            let is_in_bounds = context.builder().build_int_compare(
+                inkwell::IntPredicate::ULT,
+                offset,
+                calldata_size,
+                context
+                    .value_name(format!("{}_is_in_bounds", name).as_str())
+                    .as_str(),
+            );
+            let offset = context
+                .builder()
+                .build_select(
+                    is_in_bounds,
+                    offset,
+                    calldata_size,
+                    context
+                        .value_name(format!("{}_clamped_offset", name).as_str())
+                        .as_str(),
+                )
+                .into_int_value();
+            let offset = context.builder().build_int_add(
+                staged_offset,
+                offset,
+                context
+                    .value_name(format!("{}_offset", name).as_str())
+                    .as_str(),
+            );
+            Ok(context.access_memory(offset, AddressSpace::Heap, name))
+        }
+        CalldataMode::Lazy => Ok(parent_pointer(context, offset, name)),
+    }
+}
+
+///
+/// Returns the heap offset of the staged calldata, copying it on the first call.
+///
+/// The calldata is copied to the heap at the free memory pointer, followed by a zero word, and
+/// the pointer is bumped by the word-aligned size. The copy is made by a shared runtime function,
+/// which is called at every access and returns the recorded offset after the first call. Must
+/// only be used once the frontend has initialized the free memory pointer.
+///
+fn stage<'ctx, D>(context: &mut Context<'ctx, D>) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let function = context.get_or_build_runtime_function(
+        Context::<D>::FUNCTION_CALLDATA_STAGE,
+        |context| {
+            context.build_runtime_function(
+                Context::<D>::FUNCTION_CALLDATA_STAGE,
+                0,
+                1,
+                |context, _parameters| {
+                    let offset_pointer =
+                        context.get_global_field(Context::<D>::GLOBAL_CALLDATA_OFFSET);
+                    let offset = context
+                        .build_load(offset_pointer, "calldata_stage_offset")
+                        .into_int_value();
+                    let is_staged = context.builder().build_int_compare(
+                        inkwell::IntPredicate::NE,
+                        offset,
+                        context.field_const(0),
+                        context.value_name("calldata_stage_is_staged").as_str(),
+                    );
+                    let copy_block = context.append_basic_block("calldata_stage_copy_block");
+                    let join_block = context.append_basic_block("calldata_stage_join_block");
+                    context.build_conditional_branch(is_staged, join_block, copy_block);
+
+                    context.set_basic_block(copy_block);
+                    let calldata_size = context.read_header(AddressSpace::Parent).size(context);
+                    let padded_size = context.builder().build_int_add(
+                        calldata_size,
+                        context.field_const((compiler_common::SIZE_FIELD * 2 - 1) as u64),
+                        context.value_name("calldata_stage_padded_size").as_str(),
+                    );
+                    let alignment_mask = context.builder().build_not(
+                        context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
+                        context.value_name("calldata_stage_alignment_mask").as_str(),
+                    );
+                    let padded_size = context.builder().build_and(
+                        padded_size,
+                        alignment_mask,
+                        context.value_name("calldata_stage_aligned_size").as_str(),
+                    );
+                    let offset = context.build_heap_alloc(padded_size);
+                    let destination = context.access_memory(
+                        offset,
+                        AddressSpace::Heap,
+                        "calldata_stage_destination_pointer",
+                    );
+                    let source = parent_pointer(
+                        context,
+                        context.field_const(0),
+                        "calldata_stage_source_pointer",
+                    );
+                    context.build_memcpy(
+                        IntrinsicFunction::MemoryCopyFromParent,
+                        destination,
+                        source,
+                        calldata_size,
+                        "calldata_stage_memcpy_from_parent",
+                    );
+                    let padding_offset = context.builder().build_int_add(
+                        offset,
+                        calldata_size,
+                        context.value_name("calldata_stage_padding_offset").as_str(),
+                    );
+                    let padding_pointer = context.access_memory(
+                        padding_offset,
+                        AddressSpace::Heap,
+                        "calldata_stage_padding_pointer",
+                    );
+                    context.build_store(padding_pointer, context.field_const(0));
+                    context.build_store(offset_pointer, offset);
+                    context.build_unconditional_branch(join_block);
+
+                    context.set_basic_block(join_block);
+                    let offset = context
+                        .build_load(offset_pointer, "calldata_stage_result")
+                        .into_int_value();
+                    Ok(Some(offset))
+                },
+            )
+        },
+    )?;
+
+    let offset = context
+        .build_call(function, &[], "calldata_stage_call")
+        .expect("Always returns a value")
+        .into_int_value();
+    Ok(offset)
+}
+
+///
+/// Returns the parent memory pointer to the calldata at `offset`.
+///
+fn parent_pointer<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    offset: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> inkwell::values::PointerValue<'ctx>
+where
+    D: Dependency,
+{
+    let offset_shift = compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
+    let offset = context.builder().build_int_add(
+        offset,
+        context.field_const(offset_shift as u64),
+        context
+            .value_name(format!("{}_offset", name).as_str())
+            .as_str(),
+    );
+    context.access_memory(offset, AddressSpace::Parent, name)
 }
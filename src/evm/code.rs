@@ -0,0 +1,106 @@
+//!
+//! Translates the deploy code instructions.
+//!
+//! The legacy assembly constructors read their arguments appended to the deploy code via
+//! `codecopy(datasize(...), ...)`. The code is not readable on this target, but the constructor
+//! arguments are passed as the calldata, so the accesses past the deploy code are mapped to the
+//! calldata.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::argument::Argument;
+use crate::context::operands::Operands;
+use crate::context::restricted_instruction::RestrictedInstruction;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// Translates the deploy code `codecopy`.
+///
+/// The source `offset` is shifted by the `deploy_code_size`, i.e. the `datasize` of the deploy
+/// code, so it points to the constructor arguments in the calldata.
+///
+/// The deploy code itself is not readable, so the constant offsets inside it are rejected, and
+/// the other ones throw at runtime.
+///
+/// Is only allowed in the deploy code.
+///
+pub fn copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+    deploy_code_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("CODECOPY", |context| {
+        context.check_code_type(RestrictedInstruction::CodeCopy)?;
+        operands.check_count(3)?;
+
+        let destination = operands.int(0)?;
+        let offset = operands.int(1)?;
+        match (
+            operands.constant(1),
+            Argument::constant_of(deploy_code_size.as_basic_value_enum()),
+        ) {
+            (Some(offset), Some(deploy_code_size)) if offset < &deploy_code_size => {
+                anyhow::bail!(
+                    "The deploy code `codecopy` offset {} is inside the deploy code of size {}, which is not readable on this target",
+                    offset,
+                    deploy_code_size
+                );
+            }
+            (Some(_), Some(_)) => {}
+            _ => {
+                let is_inside_code = context.builder().build_int_compare(
+                    inkwell::IntPredicate::ULT,
+                    offset,
+                    deploy_code_size,
+                    context.value_name("code_copy_is_inside_code").as_str());
+                context.build_if(is_inside_code, "code_copy_inside_code", |context| {
+                    context.write_header_size(context.field_const(0), AddressSpace::Parent);
+                    context.build_unconditional_branch(context.try_function()?.throw_block);
+                    Ok(())
+                })?;
+            }
+        }
+        let offset = context.builder().build_int_sub(
+            offset,
+            deploy_code_size,
+            context.value_name("code_copy_arguments_offset").as_str());
+        let size = operands.int(2)?;
+
+        crate::evm::calldata::copy(context, [destination, offset, size].into())
+    })
+}
+
+///
+/// Translates the deploy code `codesize`, which is the `deploy_code_size` with the constructor
+/// arguments size.
+///
+/// Is only allowed in the deploy code.
+///
+pub fn size<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    deploy_code_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("CODESIZE", |context| {
+        context.check_code_type(RestrictedInstruction::CodeSize)?;
+
+        let arguments_size = crate::evm::calldata::size(context)?
+            .expect("Always exists")
+            .into_int_value();
+        let value = context.builder().build_int_add(
+            deploy_code_size,
+            arguments_size,
+            context.value_name("code_size_with_arguments").as_str(),
+        );
+
+        Ok(Some(value.as_basic_value_enum()))
+    })
+}
@@ -4,6 +4,7 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -12,22 +13,140 @@ use crate::Dependency;
 ///
 pub fn compare<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
     operation: inkwell::IntPredicate,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let result = context.builder().build_int_compare(
-        operation,
-        arguments[0].into_int_value(),
-        arguments[1].into_int_value(),
-        "comparison_result",
-    );
-    let result = context.builder().build_int_z_extend_or_bit_cast(
-        result,
-        context.field_type(),
-        "comparison_result_extended",
-    );
-    Ok(Some(result.as_basic_value_enum()))
+    let op = match operation {
+        inkwell::IntPredicate::EQ => "EQ",
+        inkwell::IntPredicate::ULT => "LT",
+        inkwell::IntPredicate::UGT => "GT",
+        inkwell::IntPredicate::SLT => "SLT",
+        inkwell::IntPredicate::SGT => "SGT",
+        _ => "COMPARE",
+    };
+    context.with_evm_op(op, |context| {
+        operands.check_count(2)?;
+
+        let result = context.builder().build_int_compare(
+            operation,
+            operands.int(0)?,
+            operands.int(1)?,
+            context.value_name("comparison_result").as_str(),
+        );
+        let result = context.as_field_bool(result);
+        Ok(Some(result.as_basic_value_enum()))
+    })
+}
+
+///
+/// Translates the zero check.
+///
+/// If the operand is an extended comparison result, the comparison is rebuilt with the inverse
+/// predicate, so `iszero(eq(x, y))` becomes `ne(x, y)`, `iszero(lt(x, y))` becomes `ge(x, y)`,
+/// and so on.
+///
+pub fn is_zero<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("ISZERO", |context| {
+        operands.check_count(1)?;
+
+        let value = operands.int(0)?;
+
+        let boolean =
+            if value.get_type().get_bit_width() == compiler_common::BITLENGTH_BOOLEAN as u32 {
+                Some(value)
+            } else {
+                Context::<D>::unwrap_bool_extension(value)
+            };
+
+        let result = match boolean {
+            Some(boolean) => match fold_comparison(context, boolean) {
+                Some(result) => result,
+                None => context
+                    .builder()
+                    .build_not(boolean, context.value_name("is_zero_result").as_str()),
+            },
+            None => context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                value,
+                value.get_type().const_zero(),
+                context.value_name("is_zero_result").as_str(),
+            ),
+        };
+        let result = context.as_field_bool(result);
+        Ok(Some(result.as_basic_value_enum()))
+    })
+}
+
+///
+/// Rebuilds the `boolean` comparison with the inverse predicate.
+///
+/// Returns `None` if `boolean` is not a comparison result of integers or pointers.
+///
+fn fold_comparison<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    boolean: inkwell::values::IntValue<'ctx>,
+) -> Option<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let instruction = boolean.as_instruction()?;
+    if instruction.get_opcode() != inkwell::values::InstructionOpcode::ICmp {
+        return None;
+    }
+
+    let predicate = inverse_predicate(instruction.get_icmp_predicate()?);
+    let operand_1 = instruction.get_operand(0)?.left()?;
+    let operand_2 = instruction.get_operand(1)?.left()?;
+    match (operand_1, operand_2) {
+        (
+            inkwell::values::BasicValueEnum::IntValue(operand_1),
+            inkwell::values::BasicValueEnum::IntValue(operand_2),
+        ) => Some(context.builder().build_int_compare(
+            predicate,
+            operand_1,
+            operand_2,
+            context.value_name("is_zero_folded_comparison").as_str(),
+        )),
+        (
+            inkwell::values::BasicValueEnum::PointerValue(operand_1),
+            inkwell::values::BasicValueEnum::PointerValue(operand_2),
+        ) => Some(
+            context.builder().build_int_compare(
+                predicate,
+                operand_1,
+                operand_2,
+                context
+                    .value_name("is_zero_folded_pointer_comparison")
+                    .as_str(),
+            ),
+        ),
+        _ => None,
+    }
+}
+
+///
+/// Returns the predicate which is true whenever `predicate` is false.
+///
+fn inverse_predicate(predicate: inkwell::IntPredicate) -> inkwell::IntPredicate {
+    match predicate {
+        inkwell::IntPredicate::EQ => inkwell::IntPredicate::NE,
+        inkwell::IntPredicate::NE => inkwell::IntPredicate::EQ,
+        inkwell::IntPredicate::UGT => inkwell::IntPredicate::ULE,
+        inkwell::IntPredicate::UGE => inkwell::IntPredicate::ULT,
+        inkwell::IntPredicate::ULT => inkwell::IntPredicate::UGE,
+        inkwell::IntPredicate::ULE => inkwell::IntPredicate::UGT,
+        inkwell::IntPredicate::SGT => inkwell::IntPredicate::SLE,
+        inkwell::IntPredicate::SGE => inkwell::IntPredicate::SLT,
+        inkwell::IntPredicate::SLT => inkwell::IntPredicate::SGE,
+        inkwell::IntPredicate::SLE => inkwell::IntPredicate::SGT,
+    }
 }
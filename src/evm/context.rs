@@ -4,7 +4,14 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::capabilities::blobs::Blobs;
+use crate::context::capabilities::program_counter::ProgramCounter;
+use crate::context::error::Error;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
+use crate::context::privileged_operation::PrivilegedOperation;
+use crate::context::versioned_instruction::VersionedInstruction;
+use crate::context::warning::span::Span as WarningSpan;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -18,15 +25,233 @@ pub fn get<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::GetFromContext);
-    let value = context
+    let identifier: u64 = context_value.into();
+    let op = evm_op("CONTEXTGET", identifier);
+    context.with_evm_op(op.as_str(), |context| {
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::GetFromContext)?;
+        let value = context
+            .build_call(
+                intrinsic,
+                &[context.field_const(identifier).as_basic_value_enum()],
+                "context_get_call",
+            )
+            .expect("Contract context always returns a value");
+        Ok(Some(value))
+    })
+}
+
+///
+/// Translates the contract context getter calls of the values invariant within a contract call,
+/// e.g. the contract and caller addresses.
+///
+/// The value is loaded once in the function entry block and reused, unless it has been
+/// invalidated in the module, e.g. by a setter call. The values changing within a call, e.g. the
+/// gas left, must be translated with `get` instead. The caching is only performed in the
+/// functions with the EVM data. The load in the entry block is shared by the getters, so it is
+/// not annotated.
+///
+pub fn get_cached<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    context_value: compiler_common::ContextValue,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    let identifier: u64 = context_value.into();
+    let op = evm_op("CONTEXTGET", identifier);
+    context.with_evm_op(op.as_str(), |context| {
+        let cached = match context.try_function()?.evm_data.as_ref() {
+            Some(evm_data) if !context.is_context_value_invalidated(identifier) => {
+                Some(evm_data.context_value(identifier))
+            }
+            _ => None,
+        };
+        match cached {
+            Some(Some(value)) => return Ok(Some(value.as_basic_value_enum())),
+            Some(None) => {}
+            None => {
+                let identifier = context.field_const(identifier);
+                return get_raw(context, identifier, "context").map(Some);
+            }
+        }
+
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::GetFromContext)?;
+        let value = context
+            .build_entry_call(
+                intrinsic,
+                &[context.field_const(identifier).as_basic_value_enum()],
+                "context_get_cached_call",
+            )
+            .expect("Contract context always returns a value")
+            .into_int_value();
+        context.cache_context_value(identifier, value)?;
+        Ok(Some(value.as_basic_value_enum()))
+    })
+}
+
+///
+/// Translates the contract context setter calls.
+///
+/// Is only allowed in the system mode. Invalidates the cached value in the module, so the value
+/// must not have been cached before, see `Context::invalidate_context_value`.
+///
+/// # Errors
+/// If the target capabilities do not include the system intrinsics.
+///
+pub fn set<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    context_value: compiler_common::ContextValue,
+    value: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    let identifier: u64 = context_value.into();
+    let op = evm_op("CONTEXTSET", identifier);
+    context.with_evm_op(op.as_str(), |context| {
+        context.check_system_mode(PrivilegedOperation::SetContextValue)?;
+        context.check_system_intrinsics(PrivilegedOperation::SetContextValue)?;
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::SetToContext)?;
+
+        context.invalidate_context_value(identifier)?;
+
+        context.build_call(
+            intrinsic,
+            &[
+                context.field_const(identifier).as_basic_value_enum(),
+                value.as_basic_value_enum(),
+            ],
+            "context_set_call",
+        );
+        Ok(None)
+    })
+}
+
+///
+/// Translates the `blobhash` instruction.
+///
+/// Returns the configured constant or queries the target context, depending on the capabilities.
+///
+pub fn blob_hash<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("BLOBHASH", |context| {
+        operands.check_count(1)?;
+        let index = operands.int(0)?;
+
+        context.check_evm_version(VersionedInstruction::BlobHash)?;
+
+        match context.capabilities().blobs.clone() {
+            Blobs::Constant { hash, .. } => Ok(Some(
+                context
+                    .field_const_auto(hash.as_str())?
+                    .as_basic_value_enum(),
+            )),
+            Blobs::Context { hash, .. } => {
+                let identifier = context.builder().build_int_add(
+                    context.field_const(hash),
+                    index,
+                    context.value_name("blob_hash_context_value").as_str(),
+                );
+                Ok(Some(get_raw(context, identifier, "blob_hash")?))
+            }
+        }
+    })
+}
+
+///
+/// Translates the `blobbasefee` instruction.
+///
+/// Returns the configured constant or queries the target context, depending on the capabilities.
+///
+pub fn blob_base_fee<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("BLOBBASEFEE", |context| {
+        context.check_evm_version(VersionedInstruction::BlobBaseFee)?;
+
+        match context.capabilities().blobs.clone() {
+            Blobs::Constant { base_fee, .. } => Ok(Some(
+                context
+                    .field_const_auto(base_fee.as_str())?
+                    .as_basic_value_enum(),
+            )),
+            Blobs::Context { base_fee, .. } => {
+                let identifier = context.field_const(base_fee);
+                Ok(Some(get_raw(context, identifier, "blob_base_fee")?))
+            }
+        }
+    })
+}
+
+///
+/// Translates the `pc` instruction.
+///
+/// Depending on the capabilities, either returns the index of the current basic block, which is
+/// deterministic but unrelated to the bytecode offsets, or fails with an error at `span`.
+///
+pub fn program_counter<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    span: Option<WarningSpan>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("PC", |context| {
+        match context.capabilities().program_counter {
+            ProgramCounter::Unsupported => Err(Error::UnsupportedProgramCounter(span).into()),
+            ProgramCounter::BlockIndex => {
+                let current_block = context.basic_block();
+                let index = context
+                    .function()
+                    .value
+                    .get_basic_blocks()
+                    .into_iter()
+                    .position(|block| block == current_block)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("The current block is outside of the function")
+                    })?;
+                Ok(Some(
+                    context.field_const(index as u64).as_basic_value_enum(),
+                ))
+            }
+        }
+    })
+}
+
+///
+/// Reads the context value with the runtime `identifier`, which may be unknown to the
+/// `ContextValue` enumeration.
+///
+fn get_raw<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    identifier: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::GetFromContext)?;
+    Ok(context
         .build_call(
             intrinsic,
-            &[context
-                .field_const(context_value.into())
-                .as_basic_value_enum()],
-            "context_get_call",
+            &[identifier.as_basic_value_enum()],
+            format!("{}_context_get_call", name).as_str(),
         )
-        .expect("Contract context always returns a value");
-    Ok(Some(value))
+        .expect("Contract context always returns a value"))
+}
+
+///
+/// Returns the EVM operation name of the context value `identifier` access, used for the
+/// annotations.
+///
+fn evm_op(prefix: &str, identifier: u64) -> String {
+    format!("{}({})", prefix, identifier)
 }
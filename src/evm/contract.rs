@@ -6,7 +6,11 @@ use inkwell::values::BasicValue;
 
 use crate::context::address_space::AddressSpace;
 use crate::context::argument::Argument;
+use crate::context::call_definition::CallDefinition;
+use crate::context::call_result::CallResult;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
+use crate::context::privileged_operation::PrivilegedOperation;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -27,47 +31,395 @@ pub fn call<'ctx, D>(
 where
     D: Dependency,
 {
-    if let Some(value) = value {
-        crate::evm::check_value_zero(context, value);
-    }
+    context.with_evm_op(call_evm_op(&call_type), |context| {
+        let result = call_with_result(
+            context,
+            call_type,
+            address,
+            value,
+            input_offset,
+            input_size,
+            output_offset,
+            output_size,
+        )?;
+        Ok(Some(result.status.as_basic_value_enum()))
+    })
+}
+
+///
+/// Translates a contract call, returning the status flag, return data size, and output pointer.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn call_with_result<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    call_type: IntrinsicFunction,
+    address: inkwell::values::IntValue<'ctx>,
+    value: Option<inkwell::values::IntValue<'ctx>>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    input_size: inkwell::values::IntValue<'ctx>,
+    output_offset: inkwell::values::IntValue<'ctx>,
+    output_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<CallResult<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op(call_evm_op(&call_type), |context| {
+        let transferred_value = match value {
+            Some(value) if crate::evm::value::transfer(context, address, value)? => Some(value),
+            _ => None,
+        };
 
-    let identity_block = context.append_basic_block("contract_call_identity_block");
-    let ordinary_block = context.append_basic_block("contract_call_ordinary_block");
-    let join_block = context.append_basic_block("contract_call_join_block");
+        let address = context.build_address_mask(address);
 
-    let result_pointer = context.build_alloca(context.field_type(), "contract_call_result_pointer");
-    context.build_store(result_pointer, context.field_const(0));
+        let status = context.build_scoped_alloca(
+            context.field_type(),
+            "contract_call_result_pointer",
+            |context, result_pointer| {
+                context.build_store(result_pointer, context.field_const(0));
 
-    let is_address_identity = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
-        address,
-        context.field_const_str(compiler_common::ABI_ADDRESS_IDENTITY),
-        "contract_call_is_address_identity",
-    );
-    context.build_conditional_branch(is_address_identity, identity_block, ordinary_block);
-
-    context.set_basic_block(identity_block);
-    let result = call_identity(context, output_offset, input_offset, output_size)?;
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(ordinary_block);
-    let result = call_ordinary(
-        context,
-        call_type,
-        address,
-        input_offset,
-        input_size,
-        output_offset,
-        output_size,
-    )?;
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(join_block);
-
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "contract_call_result");
-
-    Ok(Some(result))
+                let build_identity = |context: &mut Context<'ctx, D>| -> anyhow::Result<()> {
+                    let result = call_identity(context, output_offset, input_offset, output_size)?;
+                    context.write_return_data_size(input_size);
+                    context.build_store(result_pointer, result);
+                    Ok(())
+                };
+                let build_ordinary = |context: &mut Context<'ctx, D>| -> anyhow::Result<()> {
+                    let result = call_ordinary(
+                        context,
+                        call_type,
+                        address,
+                        None,
+                        input_offset,
+                        input_size,
+                        output_offset,
+                        output_size,
+                    )?;
+                    context.build_store(result_pointer, result);
+                    Ok(())
+                };
+
+                let address_identity =
+                    context.field_const_hex(compiler_common::ABI_ADDRESS_IDENTITY)?;
+                if Argument::constant_of(address.as_basic_value_enum()).is_some() {
+                    if address == address_identity {
+                        build_identity(context)?;
+                    } else {
+                        build_ordinary(context)?;
+                    }
+                } else {
+                    let is_address_identity = context.builder().build_int_compare(
+                        inkwell::IntPredicate::EQ,
+                        address,
+                        address_identity,
+                        context
+                            .value_name("contract_call_is_address_identity")
+                            .as_str(),
+                    );
+                    context.build_if_else(
+                        is_address_identity,
+                        "contract_call_identity",
+                        build_identity,
+                        build_ordinary,
+                    )?;
+                }
+
+                Ok(context
+                    .build_load(result_pointer, "contract_call_result")
+                    .into_int_value())
+            },
+        )?;
+        if let Some(value) = transferred_value {
+            let is_value_zero = context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                value,
+                context.field_const(0),
+                context.value_name("contract_call_is_value_zero").as_str(),
+            );
+            let is_settled = context.builder().build_or(
+                context.as_bool(status),
+                is_value_zero,
+                context.value_name("contract_call_is_settled").as_str(),
+            );
+            let is_settled = context.as_field_bool(is_settled);
+            context.build_revert_on_failure(is_settled)?;
+        }
+
+        let return_data_size = context.read_return_data_size();
+        let output_pointer = context.access_memory(
+            output_offset,
+            AddressSpace::Heap,
+            "contract_call_result_output_pointer",
+        );
+
+        Ok(CallResult::new(status, return_data_size, output_pointer))
+    })
+}
+
+///
+/// Translates a contract call protected from the reentrance by the mutex storage `slot`.
+///
+/// The slot is locked before the call, which throws if it is already locked, and unlocked after.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn call_nonreentrant<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    slot: inkwell::values::IntValue<'ctx>,
+    call_type: IntrinsicFunction,
+    address: inkwell::values::IntValue<'ctx>,
+    value: Option<inkwell::values::IntValue<'ctx>>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    input_size: inkwell::values::IntValue<'ctx>,
+    output_offset: inkwell::values::IntValue<'ctx>,
+    output_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op(call_evm_op(&call_type), |context| {
+        context.build_nonreentrant_enter(slot)?;
+        let result = call(
+            context,
+            call_type,
+            address,
+            value,
+            input_offset,
+            input_size,
+            output_offset,
+            output_size,
+        )?;
+        context.build_nonreentrant_exit(slot)?;
+
+        Ok(result)
+    })
+}
+
+///
+/// Translates a contract call on behalf of the `mimic_address` account.
+///
+/// Is only allowed in the system mode.
+///
+/// # Errors
+/// If the target capabilities do not include the system intrinsics.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn mimic_call<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    address: inkwell::values::IntValue<'ctx>,
+    mimic_address: inkwell::values::IntValue<'ctx>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    input_size: inkwell::values::IntValue<'ctx>,
+    output_offset: inkwell::values::IntValue<'ctx>,
+    output_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("MIMICCALL", |context| {
+        context.check_system_mode(PrivilegedOperation::MimicCall)?;
+        context.check_system_intrinsics(PrivilegedOperation::MimicCall)?;
+
+        let address = context.build_address_mask(address);
+        let mimic_address = context.build_address_mask(mimic_address);
+
+        let result = call_ordinary(
+            context,
+            IntrinsicFunction::MimicCall,
+            address,
+            Some(mimic_address),
+            input_offset,
+            input_size,
+            output_offset,
+            output_size,
+        )?;
+
+        Ok(Some(result))
+    })
+}
+
+///
+/// Reverts with the child return data if the `success` flag is zero.
+///
+/// Is the optimized `if iszero(success) { returndatacopy(...) revert(...) }` idiom, which
+/// copies the child memory to the parent one directly, without staging through the heap.
+/// The check is emitted as a shared runtime function.
+///
+pub fn bubble_revert<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    success: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("REVERT", |context| {
+        context.build_revert_on_failure(success)?;
+
+        Ok(None)
+    })
+}
+
+///
+/// Translates a contract call, reverting with the child revert data if the call fails.
+///
+/// Is the dominant `if iszero(call(...)) { returndatacopy(...) revert(...) }` pattern, which
+/// only costs a call to the shared runtime function at each call site.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn call_or_revert<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    call_type: IntrinsicFunction,
+    address: inkwell::values::IntValue<'ctx>,
+    value: Option<inkwell::values::IntValue<'ctx>>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    input_size: inkwell::values::IntValue<'ctx>,
+    output_offset: inkwell::values::IntValue<'ctx>,
+    output_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op(call_evm_op(&call_type), |context| {
+        let result = call_with_result(
+            context,
+            call_type,
+            address,
+            value,
+            input_offset,
+            input_size,
+            output_offset,
+            output_size,
+        )?;
+        context.build_revert_on_failure(result.status)?;
+        Ok(None)
+    })
+}
+
+///
+/// Translates a contract call, forwarding its return data to the caller as is.
+///
+/// Is the optimized proxy idiom, which the frontends emit as the call with the empty output,
+/// followed by `returndatacopy(0, 0, returndatasize())` and then `revert(0, returndatasize())`
+/// on failure or `return(0, returndatasize())` on success. The child memory is copied to the
+/// parent one directly, without staging through the heap.
+///
+/// The Identity precompile does not write the child memory, so its input is forwarded instead.
+///
+pub fn call_and_forward<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    call_type: IntrinsicFunction,
+    address: inkwell::values::IntValue<'ctx>,
+    value: Option<inkwell::values::IntValue<'ctx>>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    input_size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op(call_evm_op(&call_type), |context| {
+        if let Some(value) = value {
+            // The failed call throws below, so the transferred value is always settled.
+            crate::evm::value::transfer(context, address, value)?;
+        }
+
+        let address = context.build_address_mask(address);
+        let data_offset = context.field_const(
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+        );
+
+        let build_identity = |context: &mut Context<'ctx, D>| -> anyhow::Result<()> {
+            context.write_return_data_size(input_size);
+            context.write_header_size(input_size, AddressSpace::Parent);
+            let destination = context.access_memory(
+                data_offset,
+                AddressSpace::Parent,
+                "contract_call_forward_identity_destination",
+            );
+            let source = context.access_memory(
+                input_offset,
+                AddressSpace::Heap,
+                "contract_call_forward_identity_source",
+            );
+            context.build_memcpy_non_empty(
+                IntrinsicFunction::MemoryCopyToParent,
+                destination,
+                source,
+                input_size,
+                "contract_call_forward_identity_memcpy_to_parent",
+            );
+            Ok(())
+        };
+        let build_ordinary = |context: &mut Context<'ctx, D>| -> anyhow::Result<()> {
+            let output_empty = context.field_const(0);
+            let is_call_successful = call_ordinary(
+                context,
+                call_type,
+                address,
+                None,
+                input_offset,
+                input_size,
+                output_empty,
+                output_empty,
+            )?
+            .into_int_value();
+
+            let size = context.read_return_data_size();
+            context.write_header_size(size, AddressSpace::Parent);
+            let destination = context.access_memory(
+                data_offset,
+                AddressSpace::Parent,
+                "contract_call_forward_destination",
+            );
+            let source = context.access_memory(
+                data_offset,
+                AddressSpace::Child,
+                "contract_call_forward_source",
+            );
+            context.build_memcpy_non_empty(
+                IntrinsicFunction::MemoryCopyFromChildToParent,
+                destination,
+                source,
+                size,
+                "contract_call_forward_memcpy_from_child_to_parent",
+            );
+
+            let is_call_failed = context.builder().build_not(
+                context.as_bool(is_call_successful),
+                context
+                    .value_name("contract_call_forward_is_call_failed")
+                    .as_str(),
+            );
+            context.build_if(is_call_failed, "contract_call_forward_failed", |context| {
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            })
+        };
+
+        let address_identity = context.field_const_hex(compiler_common::ABI_ADDRESS_IDENTITY)?;
+        if Argument::constant_of(address.as_basic_value_enum()).is_some() {
+            if address == address_identity {
+                build_identity(context)?;
+            } else {
+                build_ordinary(context)?;
+            }
+        } else {
+            let is_address_identity = context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                address,
+                address_identity,
+                context
+                    .value_name("contract_call_forward_is_address_identity")
+                    .as_str(),
+            );
+            context.build_if_else(
+                is_address_identity,
+                "contract_call_forward_identity",
+                build_identity,
+                build_ordinary,
+            )?;
+        }
+
+        context.build_long_return()?;
+        Ok(None)
+    })
 }
 
 ///
@@ -75,30 +427,47 @@ where
 ///
 pub fn linker_symbol<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    mut arguments: [Argument<'ctx>; 1],
+    mut operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let path = arguments[0]
-        .original
-        .take()
-        .ok_or_else(|| anyhow::anyhow!("Linker symbol literal is missing"))?;
+    context.with_evm_op("LINKERSYMBOL", |context| {
+        operands.check_count(1)?;
 
-    Ok(Some(
-        context
-            .resolve_library(path.as_str())?
-            .as_basic_value_enum(),
-    ))
+        let path = operands
+            .take_original(0)
+            .ok_or_else(|| anyhow::anyhow!("Linker symbol literal is missing"))?;
+
+        Ok(Some(
+            context.resolve_symbol(path.as_str())?.as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
-/// Generates an ordinary contract call
+/// Returns the EVM operation name of the `call_type` intrinsic, used for the annotations.
 ///
+fn call_evm_op(call_type: &IntrinsicFunction) -> &'static str {
+    match call_type {
+        IntrinsicFunction::StaticCall => "STATICCALL",
+        IntrinsicFunction::DelegateCall => "DELEGATECALL",
+        IntrinsicFunction::MimicCall => "MIMICCALL",
+        _ => "CALL",
+    }
+}
+
+///
+/// Generates an ordinary contract call.
+///
+/// The `mimic_address` is only passed to the mimic call intrinsic.
+///
+#[allow(clippy::too_many_arguments)]
 fn call_ordinary<'ctx, D>(
     context: &mut Context<'ctx, D>,
     call_type: IntrinsicFunction,
     address: inkwell::values::IntValue<'ctx>,
+    mimic_address: Option<inkwell::values::IntValue<'ctx>>,
     input_offset: inkwell::values::IntValue<'ctx>,
     input_size: inkwell::values::IntValue<'ctx>,
     output_offset: inkwell::values::IntValue<'ctx>,
@@ -107,80 +476,80 @@ fn call_ordinary<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::SwitchContext);
-    context.build_call(intrinsic, &[], "contract_call_switch_context");
-
-    let child_pointer_header = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "contract_call_child_pointer_header",
-    );
-    context.build_store(child_pointer_header, input_size);
+    context.with_child_context("contract_call", |context| {
+        context.write_header_size(input_size, AddressSpace::Child);
 
-    let destination = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "contract_call_child_input_destination",
-    );
-    let source = context.access_memory(
-        input_offset,
-        AddressSpace::Heap,
-        "contract_call_child_input_source",
-    );
+        let destination = context.access_memory(
+            context.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Child,
+            "contract_call_child_input_destination",
+        );
+        let source = context.access_memory(
+            input_offset,
+            AddressSpace::Heap,
+            "contract_call_child_input_source",
+        );
 
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToChild,
-        destination,
-        source,
-        input_size,
-        "contract_call_memcpy_to_child",
-    );
+        context.build_memcpy_non_empty(
+            IntrinsicFunction::MemoryCopyToChild,
+            destination,
+            source,
+            input_size,
+            "contract_call_memcpy_to_child",
+        );
 
-    let intrinsic = context.get_intrinsic_function(call_type);
-    let call_definition = context.builder().build_left_shift(
-        address,
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "",
-    );
-    let is_call_successful = context
-        .build_call(
-            intrinsic,
-            &[call_definition.as_basic_value_enum()],
-            "contract_call_external",
-        )
-        .expect("IntrinsicFunction always returns a flag");
-
-    let source = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "contract_call_output_source",
-    );
-    let destination = context.access_memory(
-        output_offset,
-        AddressSpace::Heap,
-        "contract_call_output_pointer",
-    );
+        let intrinsic = context.try_get_intrinsic_function(call_type)?;
+        let call_definition = CallDefinition::new(address).encode(context);
+        let mut call_arguments = vec![call_definition.as_basic_value_enum()];
+        if let Some(mimic_address) = mimic_address {
+            call_arguments.push(mimic_address.as_basic_value_enum());
+        }
+        let is_call_successful = context
+            .build_call(
+                intrinsic,
+                call_arguments.as_slice(),
+                "contract_call_external",
+            )
+            .expect("IntrinsicFunction always returns a flag");
+        context.write_return_data_size_from_child();
 
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyFromChild,
-        destination,
-        source,
-        output_size,
-        "contract_call_memcpy_from_child",
-    );
+        let source = context.access_memory(
+            context.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Child,
+            "contract_call_output_source",
+        );
+        let destination = context.access_memory(
+            output_offset,
+            AddressSpace::Heap,
+            "contract_call_output_pointer",
+        );
+
+        context.build_memcpy_non_empty(
+            IntrinsicFunction::MemoryCopyFromChild,
+            destination,
+            source,
+            output_size,
+            "contract_call_memcpy_from_child",
+        );
 
-    Ok(is_call_successful)
+        Ok(is_call_successful)
+    })
 }
 
+/// The maximal constant size in words copied without the memory copy intrinsic.
+const IDENTITY_WORDWISE_COPY_LIMIT: u64 = 4;
+
 ///
 /// Generates a memcopy call for the Identity precompile.
 ///
+/// The copy is omitted for the constant zero size, and is done via direct loads and stores for
+/// small constant sizes which are multiples of the field size. Otherwise, the memory copy
+/// intrinsic is called, unless the regions are the same or the size is zero at runtime.
+///
 fn call_identity<'ctx, D>(
     context: &mut Context<'ctx, D>,
     destination: inkwell::values::IntValue<'ctx>,
@@ -190,20 +559,86 @@ fn call_identity<'ctx, D>(
 where
     D: Dependency,
 {
-    let destination = context.access_memory(
-        destination,
-        AddressSpace::Heap,
-        "contract_call_identity_destination",
-    );
-    let source = context.access_memory(source, AddressSpace::Heap, "contract_call_identity_source");
+    if let Some(size) = size.get_zero_extended_constant() {
+        let size_field = compiler_common::SIZE_FIELD as u64;
+        if size == 0 {
+            return Ok(context.field_const(1).as_basic_value_enum());
+        }
+
+        if size % size_field == 0 && size / size_field <= IDENTITY_WORDWISE_COPY_LIMIT {
+            let mut values = Vec::with_capacity((size / size_field) as usize);
+            for index in 0..size / size_field {
+                let offset = context.builder().build_int_add(
+                    source,
+                    context.field_const(index * size_field),
+                    context
+                        .value_name("contract_call_identity_source_offset")
+                        .as_str(),
+                );
+                let pointer = context.access_memory(
+                    offset,
+                    AddressSpace::Heap,
+                    "contract_call_identity_source_pointer",
+                );
+                values.push(
+                    context
+                        .build_load(pointer, "contract_call_identity_value")
+                        .into_int_value(),
+                );
+            }
+            context.build_store_multiple(destination, values.as_slice());
 
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopy,
+            return Ok(context.field_const(1).as_basic_value_enum());
+        }
+    }
+
+    let is_same_region = context.builder().build_int_compare(
+        inkwell::IntPredicate::EQ,
         destination,
         source,
+        context
+            .value_name("contract_call_identity_is_same_region")
+            .as_str(),
+    );
+    let is_size_zero = context.builder().build_int_compare(
+        inkwell::IntPredicate::EQ,
         size,
-        "contract_call_memcpy_to_child",
+        context.field_const(0),
+        context
+            .value_name("contract_call_identity_is_size_zero")
+            .as_str(),
     );
+    let is_copy_skipped = context.builder().build_or(
+        is_same_region,
+        is_size_zero,
+        context
+            .value_name("contract_call_identity_is_copy_skipped")
+            .as_str(),
+    );
+    let is_copy_needed = context.builder().build_not(
+        is_copy_skipped,
+        context
+            .value_name("contract_call_identity_is_copy_needed")
+            .as_str(),
+    );
+
+    context.build_if(is_copy_needed, "contract_call_identity_copy", |context| {
+        let destination = context.access_memory(
+            destination,
+            AddressSpace::Heap,
+            "contract_call_identity_destination",
+        );
+        let source =
+            context.access_memory(source, AddressSpace::Heap, "contract_call_identity_source");
+        context.build_memcpy(
+            IntrinsicFunction::MemoryCopy,
+            destination,
+            source,
+            size,
+            "contract_call_memcpy_to_child",
+        );
+        Ok(())
+    })?;
 
     Ok(context.field_const(1).as_basic_value_enum())
 }
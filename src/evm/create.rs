@@ -5,7 +5,9 @@
 use inkwell::values::BasicValue;
 
 use crate::context::address_space::AddressSpace;
+use crate::context::call_definition::CallDefinition;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::header_codec::HeaderCodec;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -21,7 +23,9 @@ pub fn create<'ctx, D>(
 where
     D: Dependency,
 {
-    create2(context, value, input_offset, input_size, None)
+    context.with_evm_op("CREATE", |context| {
+        create2(context, value, input_offset, input_size, None)
+    })
 }
 
 ///
@@ -37,78 +41,83 @@ pub fn create2<'ctx, D>(
 where
     D: Dependency,
 {
-    crate::evm::check_value_zero(context, value);
-
-    let hash_pointer =
-        context.access_memory(input_offset, AddressSpace::Heap, "create_hash_pointer");
-    let hash = context.build_load(hash_pointer, "create_hash_value");
-
-    let constructor_input_offset = context.builder().build_int_add(
-        input_offset,
-        context.field_const(compiler_common::SIZE_FIELD as u64),
-        "create_input_offset",
-    );
-    let constructor_input_size = context.builder().build_int_sub(
-        input_size,
-        context.field_const(compiler_common::SIZE_FIELD as u64),
-        "create_input_size",
-    );
-    let counter_value_key = context.field_const_str(
-        compiler_common::keccak256(
-            compiler_common::ABI_STORAGE_DEPLOYED_CONTRACTS_COUNTER.as_bytes(),
-        )
-        .as_str(),
-    );
-    let counter_value = context
-        .build_call(
-            context.get_intrinsic_function(IntrinsicFunction::StorageLoad),
-            &[
-                counter_value_key.as_basic_value_enum(),
-                context.field_const(0).as_basic_value_enum(),
-            ],
-            "create_counter_load",
-        )
-        .expect("Contract storage always returns a value")
-        .into_int_value();
-    let salt = call_keccak256_salt(
-        context,
-        constructor_input_offset,
-        constructor_input_size,
-        counter_value,
-        salt,
-    )?;
-
-    let address = call_address_precompile(context, hash.into_int_value(), salt.into_int_value())?;
-
-    let is_call_successful = call_constructor(
-        context,
-        address.into_int_value(),
-        constructor_input_offset,
-        constructor_input_size,
-    )?;
-
-    let counter_value_incremented = context.builder().build_int_add(
-        counter_value,
-        context.field_const(1),
-        "create_counter_value_incremented",
-    );
-    context.build_call(
-        context.get_intrinsic_function(IntrinsicFunction::StorageStore),
-        &[
-            counter_value_incremented.as_basic_value_enum(),
-            counter_value_key.as_basic_value_enum(),
-            context.field_const(0).as_basic_value_enum(),
-        ],
-        "create_counter_store",
-    );
-
-    let address = context.builder().build_int_mul(
-        address.into_int_value(),
-        is_call_successful.into_int_value(),
-        "create_address_validated",
-    );
-
-    Ok(Some(address.as_basic_value_enum()))
+    context.with_evm_op("CREATE2", |context| {
+        crate::evm::check_value_zero(context, value);
+
+        let hash_pointer =
+            context.access_memory(input_offset, AddressSpace::Heap, "create_hash_pointer");
+        let hash = context.build_load(hash_pointer, "create_hash_value");
+
+        let constructor_input_offset = context.builder().build_int_add(
+            input_offset,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context.value_name("create_input_offset").as_str(),
+        );
+        let constructor_input_size = context.builder().build_int_sub(
+            input_size,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context.value_name("create_input_size").as_str(),
+        );
+        let counter_value_key = context.field_const_hex(
+            compiler_common::keccak256(
+                compiler_common::ABI_STORAGE_DEPLOYED_CONTRACTS_COUNTER.as_bytes(),
+            )
+            .as_str(),
+        )?;
+        let counter_value = context
+            .build_call(
+                context.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?,
+                &[
+                    counter_value_key.as_basic_value_enum(),
+                    context.field_const(0).as_basic_value_enum(),
+                ],
+                "create_counter_load",
+            )
+            .expect("Contract storage always returns a value")
+            .into_int_value();
+        let salt = call_keccak256_salt(
+            context,
+            constructor_input_offset,
+            constructor_input_size,
+            counter_value,
+            salt,
+        )?;
+
+        let address =
+            call_address_precompile(context, hash.into_int_value(), salt.into_int_value())?;
+        let address = context
+            .build_address_mask(address.into_int_value())
+            .as_basic_value_enum();
+
+        let is_call_successful = call_constructor(
+            context,
+            address.into_int_value(),
+            constructor_input_offset,
+            constructor_input_size,
+        )?;
+
+        let counter_value_incremented = context.builder().build_int_add(
+            counter_value,
+            context.field_const(1),
+            context
+                .value_name("create_counter_value_incremented")
+                .as_str(),
+        );
+        context.build_storage_store(
+            counter_value_incremented,
+            counter_value_key,
+            context.field_const(0),
+            "create_counter_store",
+        )?;
+
+        let address = context.builder().build_int_mul(
+            address.into_int_value(),
+            is_call_successful.into_int_value(),
+            context.value_name("create_address_validated").as_str(),
+        );
+
+        Ok(Some(address.as_basic_value_enum()))
+    })
 }
 
 ///
@@ -124,18 +133,20 @@ pub fn contract_hash<'ctx, D>(
 where
     D: Dependency,
 {
-    let parent = context.module().get_name().to_str().expect("Always valid");
+    context.with_evm_op("DATAOFFSET", |context| {
+        let parent = context.module().get_name().to_str().expect("Always valid");
 
-    if identifier.ends_with("_deployed") || identifier.as_str() == parent {
-        return Ok(Some(context.field_const(0).as_basic_value_enum()));
-    }
+        if identifier.ends_with("_deployed") || identifier.as_str() == parent {
+            return Ok(Some(context.field_const(0).as_basic_value_enum()));
+        }
 
-    let hash_value = context
-        .compile_dependency(identifier.as_str())
-        .map(|hash| context.field_const_str(hash.as_str()))
-        .map(inkwell::values::BasicValueEnum::IntValue)?;
+        let hash_value = context
+            .compile_dependency(identifier.as_str())
+            .and_then(|hash| context.field_const_hex(hash.as_str()))
+            .map(inkwell::values::BasicValueEnum::IntValue)?;
 
-    Ok(Some(hash_value))
+        Ok(Some(hash_value))
+    })
 }
 
 ///
@@ -151,17 +162,19 @@ pub fn contract_hash_size<'ctx, D>(
 where
     D: Dependency,
 {
-    let parent = context.module().get_name().to_str().expect("Always valid");
-
-    if identifier.ends_with("_deployed") || identifier.as_str() == parent {
-        return Ok(Some(context.field_const(0).as_basic_value_enum()));
-    }
-
-    Ok(Some(
-        context
-            .field_const(compiler_common::SIZE_FIELD as u64)
-            .as_basic_value_enum(),
-    ))
+    context.with_evm_op("DATASIZE", |context| {
+        let parent = context.module().get_name().to_str().expect("Always valid");
+
+        if identifier.ends_with("_deployed") || identifier.as_str() == parent {
+            return Ok(Some(context.field_const(0).as_basic_value_enum()));
+        }
+
+        Ok(Some(
+            context
+                .field_const(compiler_common::SIZE_FIELD as u64)
+                .as_basic_value_enum(),
+        ))
+    })
 }
 
 ///
@@ -178,95 +191,83 @@ fn call_keccak256_salt<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::SwitchContext);
-    context.build_call(intrinsic, &[], "salt_keccak256_switch_context");
-
-    let mut input_size = context.builder().build_int_add(
-        constructor_input_size,
-        context.field_const(compiler_common::SIZE_FIELD as u64),
-        "salt_keccak256_input_size_with_counter",
-    );
-    if salt.is_some() {
-        input_size = context.builder().build_int_add(
-            input_size,
+    context.with_child_context("salt_keccak256", |context| {
+        let mut input_size = context.builder().build_int_add(
+            constructor_input_size,
             context.field_const(compiler_common::SIZE_FIELD as u64),
-            "salt_keccak256_input_size_with_salt",
+            context
+                .value_name("salt_keccak256_input_size_with_counter")
+                .as_str(),
         );
-    }
-
-    let child_pointer_header = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "salt_keccak256_child_pointer_header",
-    );
-    context.build_store(child_pointer_header, input_size);
-
-    let child_offset_data = context.field_const(
-        (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-    );
-    let child_pointer_data = context.access_memory(
-        child_offset_data,
-        AddressSpace::Child,
-        "salt_keccak256_child_pointer_data",
-    );
-    let child_offset_constructor_data = child_offset_data;
-    let child_pointer_constructor_data = child_pointer_data;
-    let constructor_input_pointer = context.access_memory(
-        constructor_input_offset,
-        AddressSpace::Heap,
-        "salt_keccak256_heap_pointer_constructor_data",
-    );
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToChild,
-        child_pointer_constructor_data,
-        constructor_input_pointer,
-        constructor_input_size,
-        "salt_keccak256_memcpy_to_child",
-    );
-
-    let child_offset_counter = context.builder().build_int_add(
-        child_offset_constructor_data,
-        constructor_input_size,
-        "salt_keccak256_child_offset_counter",
-    );
-    let child_pointer_counter = context.access_memory(
-        child_offset_counter,
-        AddressSpace::Child,
-        "salt_keccak256_child_pointer_counter",
-    );
-    context.build_store(child_pointer_counter, counter_value);
-
-    if let Some(salt) = salt {
-        let child_offset_salt = context.builder().build_int_add(
-            child_offset_counter,
-            context.field_const(compiler_common::SIZE_FIELD as u64),
-            "salt_keccak256_child_offset_salt",
+        if salt.is_some() {
+            input_size = context.builder().build_int_add(
+                input_size,
+                context.field_const(compiler_common::SIZE_FIELD as u64),
+                context
+                    .value_name("salt_keccak256_input_size_with_salt")
+                    .as_str(),
+            );
+        }
+
+        context.write_header_size(input_size, AddressSpace::Child);
+
+        let child_offset_data = context.field_const(
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
         );
-        let child_pointer_salt = context.access_memory(
-            child_offset_salt,
+        let child_pointer_data = context.access_memory(
+            child_offset_data,
             AddressSpace::Child,
-            "salt_keccak256_child_pointer_salt",
+            "salt_keccak256_child_pointer_data",
         );
-        context.build_store(child_pointer_salt, salt);
-    }
-
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StaticCall);
-    let call_definition = context.builder().build_left_shift(
-        context.field_const_str(compiler_common::ABI_ADDRESS_KECCAK256),
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "",
-    );
-    context.build_call(
-        intrinsic,
-        &[call_definition.as_basic_value_enum()],
-        "salt_keccak256_call_external",
-    );
-
-    let result = context.build_load(child_pointer_data, "salt_keccak256_result");
-
-    Ok(result)
+        let child_offset_constructor_data = child_offset_data;
+        let child_pointer_constructor_data = child_pointer_data;
+        let constructor_input_pointer = context.access_memory(
+            constructor_input_offset,
+            AddressSpace::Heap,
+            "salt_keccak256_heap_pointer_constructor_data",
+        );
+        context.build_memcpy(
+            IntrinsicFunction::MemoryCopyToChild,
+            child_pointer_constructor_data,
+            constructor_input_pointer,
+            constructor_input_size,
+            "salt_keccak256_memcpy_to_child",
+        );
+
+        let child_offset_counter = context.builder().build_int_add(
+            child_offset_constructor_data,
+            constructor_input_size,
+            context
+                .value_name("salt_keccak256_child_offset_counter")
+                .as_str(),
+        );
+        let child_pointer_counter = context.access_memory(
+            child_offset_counter,
+            AddressSpace::Child,
+            "salt_keccak256_child_pointer_counter",
+        );
+        context.build_store(child_pointer_counter, counter_value);
+
+        if let Some(salt) = salt {
+            let child_offset_salt = context.builder().build_int_add(
+                child_offset_counter,
+                context.field_const(compiler_common::SIZE_FIELD as u64),
+                context
+                    .value_name("salt_keccak256_child_offset_salt")
+                    .as_str(),
+            );
+            let child_pointer_salt = context.access_memory(
+                child_offset_salt,
+                AddressSpace::Child,
+                "salt_keccak256_child_pointer_salt",
+            );
+            context.build_store(child_pointer_salt, salt);
+        }
+
+        let result = context.build_keccak256_child(input_size, "salt_keccak256_result")?;
+
+        Ok(result)
+    })
 }
 
 ///
@@ -280,57 +281,49 @@ fn call_address_precompile<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::SwitchContext);
-    context.build_call(intrinsic, &[], "create_precompile_switch_context");
-
-    let child_pointer_header = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "create_precompile_child_pointer_header",
-    );
-    let input_size = context.field_const((compiler_common::SIZE_FIELD * 2) as u64);
-    context.build_store(child_pointer_header, input_size);
-
-    let child_offset_data = context.field_const(
-        (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-    );
-    let child_pointer_data = context.access_memory(
-        child_offset_data,
-        AddressSpace::Child,
-        "create_precompile_child_pointer_hash",
-    );
-    let child_pointer_hash = child_pointer_data;
-    context.build_store(child_pointer_hash, hash);
-
-    let child_offset_salt = context.builder().build_int_add(
-        child_offset_data,
-        context.field_const(compiler_common::SIZE_FIELD as u64),
-        "create_precompile_child_offset_salt",
-    );
-    let child_pointer_salt = context.access_memory(
-        child_offset_salt,
-        AddressSpace::Child,
-        "create_precompile_child_pointer_salt",
-    );
-    context.build_store(child_pointer_salt, salt);
-
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::FarCall);
-    let call_definition = context.builder().build_left_shift(
-        context.field_const_str(compiler_common::ABI_ADDRESS_CREATE),
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "",
-    );
-    context.build_call(
-        intrinsic,
-        &[call_definition.as_basic_value_enum()],
-        "create_precompile_call_external",
-    );
-
-    let result = context.build_load(child_pointer_data, "create_precompile_result");
-
-    Ok(result)
+    context.with_child_context("create_precompile", |context| {
+        let input_size = context.field_const((compiler_common::SIZE_FIELD * 2) as u64);
+        context.write_header_size(input_size, AddressSpace::Child);
+
+        let child_offset_data = context.field_const(
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+        );
+        let child_pointer_data = context.access_memory(
+            child_offset_data,
+            AddressSpace::Child,
+            "create_precompile_child_pointer_hash",
+        );
+        let child_pointer_hash = child_pointer_data;
+        context.build_store(child_pointer_hash, hash);
+
+        let child_offset_salt = context.builder().build_int_add(
+            child_offset_data,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context
+                .value_name("create_precompile_child_offset_salt")
+                .as_str(),
+        );
+        let child_pointer_salt = context.access_memory(
+            child_offset_salt,
+            AddressSpace::Child,
+            "create_precompile_child_pointer_salt",
+        );
+        context.build_store(child_pointer_salt, salt);
+
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::FarCall)?;
+        let call_definition =
+            CallDefinition::new(context.field_const_hex(compiler_common::ABI_ADDRESS_CREATE)?)
+                .encode(context);
+        context.build_call(
+            intrinsic,
+            &[call_definition.as_basic_value_enum()],
+            "create_precompile_call_external",
+        );
+
+        let result = context.build_load(child_pointer_data, "create_precompile_result");
+
+        Ok(result)
+    })
 }
 
 ///
@@ -345,58 +338,44 @@ fn call_constructor<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::SwitchContext);
-    context.build_call(intrinsic, &[], "create_switch_context");
-
-    let child_header_data = context.builder().build_or(
-        constructor_input_size,
-        context.field_const_str("00000000000000010000000000000000"),
-        "child_header_data",
-    );
-
-    let child_pointer_header = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "create_child_pointer_header",
-    );
-    context.build_store(child_pointer_header, child_header_data);
-
-    let destination = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "create_child_input_destination",
-    );
-    let source = context.access_memory(
-        constructor_input_offset,
-        AddressSpace::Heap,
-        "create_child_input_source",
-    );
-
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToChild,
-        destination,
-        source,
-        constructor_input_size,
-        "create_memcpy_to_child",
-    );
-
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::FarCall);
-    let call_definition = context.builder().build_left_shift(
-        address,
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "",
-    );
-    let is_call_successful = context
-        .build_call(
-            intrinsic,
-            &[call_definition.as_basic_value_enum()],
-            "create_call",
-        )
-        .expect("IntrinsicFunction always returns a flag");
+    context.with_child_context("create", |context| {
+        let child_header = HeaderCodec::new(context)
+            .set_size(context, constructor_input_size)
+            .set_constructor_flag(context, true);
+        context.write_header(child_header, AddressSpace::Child);
+
+        let destination = context.access_memory(
+            context.field_const(
+                (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+            ),
+            AddressSpace::Child,
+            "create_child_input_destination",
+        );
+        let source = context.access_memory(
+            constructor_input_offset,
+            AddressSpace::Heap,
+            "create_child_input_source",
+        );
+
+        context.build_memcpy(
+            IntrinsicFunction::MemoryCopyToChild,
+            destination,
+            source,
+            constructor_input_size,
+            "create_memcpy_to_child",
+        );
 
-    Ok(is_call_successful)
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::FarCall)?;
+        let call_definition = CallDefinition::new(address).encode(context);
+        let is_call_successful = context
+            .build_call(
+                intrinsic,
+                &[call_definition.as_basic_value_enum()],
+                "create_call",
+            )
+            .expect("IntrinsicFunction always returns a flag");
+        context.write_return_data_size_from_child();
+
+        Ok(is_call_successful)
+    })
 }
@@ -4,11 +4,15 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::abi_type::AbiType;
 use crate::context::address_space::AddressSpace;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
 use crate::context::Context;
 use crate::Dependency;
 
+/// The maximum number of the indexed event values, excluding the signature topic.
+const EVENT_INDEXED_VALUES_LIMIT: usize = 3;
+
 ///
 /// Translates a log or event call.
 ///
@@ -21,106 +25,177 @@ pub fn log<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::Event);
-
-    let topics_length = context.field_const(topics.len() as u64);
-    let data_length_shifted = context.builder().build_left_shift(
-        length,
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "event_data_length_shifted",
-    );
-    let event_initializer =
-        context
-            .builder()
-            .build_int_add(topics_length, data_length_shifted, "event_initializer");
-
-    let (range_start, length) = if topics.len() % 2 == 0 {
-        let range_start_pointer =
-            context.build_alloca(context.field_type(), "event_odd_range_start_pointer");
-        let length_pointer = context.build_alloca(context.field_type(), "event_odd_length_pointer");
+    let op = format!("LOG{}", topics.len());
+    context.with_evm_op(op.as_str(), |context| {
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::Event)?;
 
-        let data_empty_block = context.append_basic_block("event_odd_data_empty");
-        let data_not_empty_block = context.append_basic_block("event_odd_data_not_empty");
-        let join_block = context.append_basic_block("event_odd_data_join");
-
-        let data_empty_condition = context.builder().build_int_compare(
-            inkwell::IntPredicate::EQ,
+        let topics_length = context.field_const(topics.len() as u64);
+        let data_length_shifted = context.builder().build_left_shift(
             length,
-            context.field_const(0),
-            "event_odd_data_empty_condition",
+            context.field_const((compiler_common::BITLENGTH_X32) as u64),
+            context.value_name("event_data_length_shifted").as_str(),
         );
-        context.build_conditional_branch(
-            data_empty_condition,
-            data_empty_block,
-            data_not_empty_block,
+        let event_initializer = context.builder().build_int_add(
+            topics_length,
+            data_length_shifted,
+            context.value_name("event_initializer").as_str(),
         );
 
-        context.set_basic_block(data_empty_block);
-        if topics.is_empty() {
-            context.build_call(
-                intrinsic,
-                &[
-                    event_initializer.as_basic_value_enum(),
-                    context.field_const(0).as_basic_value_enum(),
-                    context.field_const(1).as_basic_value_enum(),
-                ],
-                "event_odd_data_empty_init_with_no_topics",
+        let (range_start, length) = if topics.len() % 2 == 0 {
+            let range_start_pointer =
+                context.build_alloca(context.field_type(), "event_odd_range_start_pointer");
+            let length_pointer =
+                context.build_alloca(context.field_type(), "event_odd_length_pointer");
+
+            let data_empty_block = context.append_basic_block("event_odd_data_empty");
+            let data_not_empty_block = context.append_basic_block("event_odd_data_not_empty");
+            let join_block = context.append_basic_block("event_odd_data_join");
+
+            let data_empty_condition = context.builder().build_int_compare(
+                inkwell::IntPredicate::EQ,
+                length,
+                context.field_const(0),
+                context
+                    .value_name("event_odd_data_empty_condition")
+                    .as_str(),
             );
-        } else {
-            let mut topic_index = 0;
-            context.build_call(
-                intrinsic,
-                &[
-                    event_initializer.as_basic_value_enum(),
-                    topics[topic_index].as_basic_value_enum(),
-                    context.field_const(1).as_basic_value_enum(),
-                ],
-                "event_odd_data_empty_init_with_first_topic",
+            context.build_conditional_branch(
+                data_empty_condition,
+                data_empty_block,
+                data_not_empty_block,
             );
-            topic_index += 1;
-            while topics.len() - topic_index >= 2 {
+
+            context.set_basic_block(data_empty_block);
+            if topics.is_empty() {
                 context.build_call(
                     intrinsic,
                     &[
+                        event_initializer.as_basic_value_enum(),
+                        context.field_const(0).as_basic_value_enum(),
+                        context.field_const(1).as_basic_value_enum(),
+                    ],
+                    "event_odd_data_empty_init_with_no_topics",
+                );
+            } else {
+                let mut topic_index = 0;
+                context.build_call(
+                    intrinsic,
+                    &[
+                        event_initializer.as_basic_value_enum(),
                         topics[topic_index].as_basic_value_enum(),
-                        topics[topic_index + 1].as_basic_value_enum(),
+                        context.field_const(1).as_basic_value_enum(),
+                    ],
+                    "event_odd_data_empty_init_with_first_topic",
+                );
+                topic_index += 1;
+                while topics.len() - topic_index >= 2 {
+                    context.build_call(
+                        intrinsic,
+                        &[
+                            topics[topic_index].as_basic_value_enum(),
+                            topics[topic_index + 1].as_basic_value_enum(),
+                            context.field_const(0).as_basic_value_enum(),
+                        ],
+                        "event_odd_data_empty_next_two_topics",
+                    );
+                    topic_index += 2;
+                }
+                context.build_call(
+                    intrinsic,
+                    &[
+                        topics[topic_index].as_basic_value_enum(),
+                        context.field_const(0).as_basic_value_enum(),
                         context.field_const(0).as_basic_value_enum(),
                     ],
-                    "event_odd_data_empty_next_two_topics",
+                    "event_odd_data_empty_last_topic",
                 );
-                topic_index += 2;
+                context.build_store(range_start_pointer, range_start);
+                context.build_store(length_pointer, length);
             }
-            context.build_call(
-                intrinsic,
-                &[
-                    topics[topic_index].as_basic_value_enum(),
-                    context.field_const(0).as_basic_value_enum(),
-                    context.field_const(0).as_basic_value_enum(),
-                ],
-                "event_odd_data_empty_last_topic",
+            context.build_unconditional_branch(join_block);
+
+            context.set_basic_block(data_not_empty_block);
+            let pointer = context.access_memory(
+                range_start,
+                AddressSpace::Heap,
+                "event_odd_first_value_pointer",
             );
-            context.build_store(range_start_pointer, range_start);
-            context.build_store(length_pointer, length);
-        }
-        context.build_unconditional_branch(join_block);
+            let value = context.build_load(pointer, "event_odd_first_value");
+            if topics.is_empty() {
+                context.build_call(
+                    intrinsic,
+                    &[
+                        event_initializer.as_basic_value_enum(),
+                        value,
+                        context.field_const(1).as_basic_value_enum(),
+                    ],
+                    "event_odd_data_not_empty_init_with_value",
+                );
+            } else {
+                let mut topic_index = 0;
+                context.build_call(
+                    intrinsic,
+                    &[
+                        event_initializer.as_basic_value_enum(),
+                        topics[topic_index].as_basic_value_enum(),
+                        context.field_const(1).as_basic_value_enum(),
+                    ],
+                    "event_odd_data_not_empty_init_with_topic",
+                );
+                topic_index += 1;
+                while topics.len() - topic_index >= 2 {
+                    context.build_call(
+                        intrinsic,
+                        &[
+                            topics[topic_index].as_basic_value_enum(),
+                            topics[topic_index + 1].as_basic_value_enum(),
+                            context.field_const(0).as_basic_value_enum(),
+                        ],
+                        "event_odd_data_not_empty_next_two_topics",
+                    );
+                    topic_index += 2;
+                }
+                context.build_call(
+                    intrinsic,
+                    &[
+                        topics[topic_index].as_basic_value_enum(),
+                        value,
+                        context.field_const(0).as_basic_value_enum(),
+                    ],
+                    "event_odd_data_not_empty_last_topic",
+                );
+            }
 
-        context.set_basic_block(data_not_empty_block);
-        let pointer = context.access_memory(
-            range_start,
-            AddressSpace::Heap,
-            "event_odd_first_value_pointer",
-        );
-        let value = context.build_load(pointer, "event_odd_first_value");
-        if topics.is_empty() {
-            context.build_call(
-                intrinsic,
-                &[
-                    event_initializer.as_basic_value_enum(),
-                    value,
-                    context.field_const(1).as_basic_value_enum(),
-                ],
-                "event_odd_data_not_empty_init_with_value",
+            context.build_store(
+                range_start_pointer,
+                context.builder().build_int_add(
+                    range_start,
+                    context.field_const(compiler_common::SIZE_FIELD as u64),
+                    context
+                        .value_name("event_odd_range_start_after_first")
+                        .as_str(),
+                ),
             );
+            context.build_store(
+                length_pointer,
+                context.builder().build_int_sub(
+                    length,
+                    context.field_const(compiler_common::SIZE_FIELD as u64),
+                    context
+                        .value_name("event_odd_length_without_first")
+                        .as_str(),
+                ),
+            );
+            context.build_unconditional_branch(join_block);
+
+            context.set_basic_block(join_block);
+            let range_start = context
+                .build_load(range_start_pointer, "event_odd_range_start_joined")
+                .into_int_value();
+            let length = context
+                .build_load(length_pointer, "event_odd_length_joined")
+                .into_int_value();
+            (range_start, length)
         } else {
             let mut topic_index = 0;
             context.build_call(
@@ -130,7 +205,7 @@ where
                     topics[topic_index].as_basic_value_enum(),
                     context.field_const(1).as_basic_value_enum(),
                 ],
-                "event_odd_data_not_empty_init_with_topic",
+                "event_even_init_with_topic",
             );
             topic_index += 1;
             while topics.len() - topic_index >= 2 {
@@ -141,176 +216,171 @@ where
                         topics[topic_index + 1].as_basic_value_enum(),
                         context.field_const(0).as_basic_value_enum(),
                     ],
-                    "event_odd_data_not_empty_next_two_topics",
+                    "event_even_next_two_topics",
                 );
                 topic_index += 2;
             }
-            context.build_call(
-                intrinsic,
-                &[
-                    topics[topic_index].as_basic_value_enum(),
-                    value,
-                    context.field_const(0).as_basic_value_enum(),
-                ],
-                "event_odd_data_not_empty_last_topic",
-            );
-        }
+            (range_start, length)
+        };
 
-        context.build_store(
-            range_start_pointer,
-            context.builder().build_int_add(
-                range_start,
-                context.field_const(compiler_common::SIZE_FIELD as u64),
-                "event_odd_range_start_after_first",
-            ),
+        let condition_block = context.append_basic_block("event_loop_condition");
+        let body_block = context.append_basic_block("event_loop_body");
+        let increment_block = context.append_basic_block("event_loop_increment");
+        let join_block = context.append_basic_block("event_loop_join");
+
+        let index_pointer = context.build_alloca(context.field_type(), "event_loop_index_pointer");
+        let range_end = context.builder().build_int_add(
+            range_start,
+            length,
+            context.value_name("event_loop_range_end").as_str(),
         );
-        context.build_store(
-            length_pointer,
-            context.builder().build_int_sub(
-                length,
-                context.field_const(compiler_common::SIZE_FIELD as u64),
-                "event_odd_length_without_first",
-            ),
+        context.build_store(index_pointer, range_start);
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(condition_block);
+        let index_value = context
+            .build_load(index_pointer, "event_loop_index_value")
+            .into_int_value();
+        let condition = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            index_value,
+            range_end,
+            context.value_name("event_loop_condition").as_str(),
         );
-        context.build_unconditional_branch(join_block);
+        context.build_conditional_branch(condition, body_block, join_block);
 
-        context.set_basic_block(join_block);
-        let range_start = context
-            .build_load(range_start_pointer, "event_odd_range_start_joined")
+        context.set_basic_block(increment_block);
+        let index_value = context
+            .build_load(index_pointer, "event_loop_index_value_increment")
             .into_int_value();
-        let length = context
-            .build_load(length_pointer, "event_odd_length_joined")
+        let incremented = context.builder().build_int_add(
+            index_value,
+            context.field_const((compiler_common::SIZE_FIELD * 2) as u64),
+            context
+                .value_name("event_loop_index_value_incremented")
+                .as_str(),
+        );
+        context.build_store(index_pointer, incremented);
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(body_block);
+        let two_values_block = context.append_basic_block("event_loop_body_two_values");
+        let one_value_block = context.append_basic_block("event_loop_body_one_value");
+        let index_value = context
+            .build_load(index_pointer, "event_loop_body_index_value")
             .into_int_value();
-        (range_start, length)
-    } else {
-        let mut topic_index = 0;
+        let values_remaining = context.builder().build_int_sub(
+            range_end,
+            index_value,
+            context.value_name("event_loop_values_remaining").as_str(),
+        );
+        let has_two_values = context.builder().build_int_compare(
+            inkwell::IntPredicate::UGE,
+            values_remaining,
+            context.field_const((compiler_common::SIZE_FIELD * 2) as u64),
+            context.value_name("event_loop_has_two_values").as_str(),
+        );
+        context.build_conditional_branch(has_two_values, two_values_block, one_value_block);
+
+        context.set_basic_block(two_values_block);
+        let value_1_pointer = context.access_memory(
+            index_value,
+            AddressSpace::Heap,
+            "event_loop_value_1_pointer",
+        );
+        let value_1 = context.build_load(value_1_pointer, "event_loop_value_1");
+        let index_value_next = context.builder().build_int_add(
+            index_value,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context.value_name("event_loop_index_value_next").as_str(),
+        );
+        let value_2_pointer = context.access_memory(
+            index_value_next,
+            AddressSpace::Heap,
+            "event_loop_value_2_pointer",
+        );
+        let value_2 = context.build_load(value_2_pointer, "event_loop_value_2");
         context.build_call(
             intrinsic,
             &[
-                event_initializer.as_basic_value_enum(),
-                topics[topic_index].as_basic_value_enum(),
-                context.field_const(1).as_basic_value_enum(),
+                value_1,
+                value_2,
+                context.field_const(0).as_basic_value_enum(),
             ],
-            "event_even_init_with_topic",
+            "event_loop_call_with_two_values",
         );
-        topic_index += 1;
-        while topics.len() - topic_index >= 2 {
-            context.build_call(
-                intrinsic,
-                &[
-                    topics[topic_index].as_basic_value_enum(),
-                    topics[topic_index + 1].as_basic_value_enum(),
-                    context.field_const(0).as_basic_value_enum(),
-                ],
-                "event_even_next_two_topics",
-            );
-            topic_index += 2;
-        }
-        (range_start, length)
-    };
+        context.build_unconditional_branch(increment_block);
 
-    let condition_block = context.append_basic_block("event_loop_condition");
-    let body_block = context.append_basic_block("event_loop_body");
-    let increment_block = context.append_basic_block("event_loop_increment");
-    let join_block = context.append_basic_block("event_loop_join");
-
-    let index_pointer = context.build_alloca(context.field_type(), "event_loop_index_pointer");
-    let range_end = context
-        .builder()
-        .build_int_add(range_start, length, "event_loop_range_end");
-    context.build_store(index_pointer, range_start);
-    context.build_unconditional_branch(condition_block);
-
-    context.set_basic_block(condition_block);
-    let index_value = context
-        .build_load(index_pointer, "event_loop_index_value")
-        .into_int_value();
-    let condition = context.builder().build_int_compare(
-        inkwell::IntPredicate::ULT,
-        index_value,
-        range_end,
-        "event_loop_condition",
-    );
-    context.build_conditional_branch(condition, body_block, join_block);
+        context.set_basic_block(one_value_block);
+        let value_1_pointer = context.access_memory(
+            index_value,
+            AddressSpace::Heap,
+            "event_loop_value_1_pointer",
+        );
+        let value_1 = context.build_load(value_1_pointer, "event_loop_value_1");
+        context.build_call(
+            intrinsic,
+            &[
+                value_1,
+                context.field_const(0).as_basic_value_enum(),
+                context.field_const(0).as_basic_value_enum(),
+            ],
+            "event_loop_call_with_value_and_zero",
+        );
+        context.build_unconditional_branch(increment_block);
 
-    context.set_basic_block(increment_block);
-    let index_value = context
-        .build_load(index_pointer, "event_loop_index_value_increment")
-        .into_int_value();
-    let incremented = context.builder().build_int_add(
-        index_value,
-        context.field_const((compiler_common::SIZE_FIELD * 2) as u64),
-        "event_loop_index_value_incremented",
-    );
-    context.build_store(index_pointer, incremented);
-    context.build_unconditional_branch(condition_block);
+        context.set_basic_block(join_block);
 
-    context.set_basic_block(body_block);
-    let two_values_block = context.append_basic_block("event_loop_body_two_values");
-    let one_value_block = context.append_basic_block("event_loop_body_one_value");
-    let index_value = context
-        .build_load(index_pointer, "event_loop_body_index_value")
-        .into_int_value();
-    let values_remaining =
-        context
-            .builder()
-            .build_int_sub(range_end, index_value, "event_loop_values_remaining");
-    let has_two_values = context.builder().build_int_compare(
-        inkwell::IntPredicate::UGE,
-        values_remaining,
-        context.field_const((compiler_common::SIZE_FIELD * 2) as u64),
-        "event_loop_has_two_values",
-    );
-    context.build_conditional_branch(has_two_values, two_values_block, one_value_block);
+        Ok(None)
+    })
+}
 
-    context.set_basic_block(two_values_block);
-    let value_1_pointer = context.access_memory(
-        index_value,
-        AddressSpace::Heap,
-        "event_loop_value_1_pointer",
-    );
-    let value_1 = context.build_load(value_1_pointer, "event_loop_value_1");
-    let index_value_next = context.builder().build_int_add(
-        index_value,
-        context.field_const(compiler_common::SIZE_FIELD as u64),
-        "event_loop_index_value_next",
-    );
-    let value_2_pointer = context.access_memory(
-        index_value_next,
-        AddressSpace::Heap,
-        "event_loop_value_2_pointer",
-    );
-    let value_2 = context.build_load(value_2_pointer, "event_loop_value_2");
-    context.build_call(
-        intrinsic,
-        &[
-            value_1,
-            value_2,
-            context.field_const(0).as_basic_value_enum(),
-        ],
-        "event_loop_call_with_two_values",
-    );
-    context.build_unconditional_branch(increment_block);
+///
+/// Translates a typed event emission.
+///
+/// The first topic is the `signature` hash, computed at compile time, followed by the cleaned up
+/// `indexed_values`. The `data_values` are ABI-encoded into a heap region allocated with the free
+/// memory pointer.
+///
+pub fn emit_typed<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    signature: &str,
+    indexed_values: Vec<(inkwell::values::IntValue<'ctx>, AbiType)>,
+    data_values: Vec<(inkwell::values::IntValue<'ctx>, AbiType)>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    let op = format!("LOG{}", indexed_values.len() + 1);
+    context.with_evm_op(op.as_str(), |context| {
+        if indexed_values.len() > EVENT_INDEXED_VALUES_LIMIT {
+            anyhow::bail!(
+                "The event `{}` has {} indexed values, but at most {} are allowed",
+                signature,
+                indexed_values.len(),
+                EVENT_INDEXED_VALUES_LIMIT
+            );
+        }
+        for (_, abi_type) in indexed_values.iter().chain(data_values.iter()) {
+            abi_type.validate()?;
+        }
 
-    context.set_basic_block(one_value_block);
-    let value_1_pointer = context.access_memory(
-        index_value,
-        AddressSpace::Heap,
-        "event_loop_value_1_pointer",
-    );
-    let value_1 = context.build_load(value_1_pointer, "event_loop_value_1");
-    context.build_call(
-        intrinsic,
-        &[
-            value_1,
-            context.field_const(0).as_basic_value_enum(),
-            context.field_const(0).as_basic_value_enum(),
-        ],
-        "event_loop_call_with_value_and_zero",
-    );
-    context.build_unconditional_branch(increment_block);
+        let mut topics = Vec::with_capacity(indexed_values.len() + 1);
+        topics.push(
+            context.field_const_hex(compiler_common::keccak256(signature.as_bytes()).as_str())?,
+        );
+        for (value, abi_type) in indexed_values.into_iter() {
+            topics.push(abi_type.cleanup(context, value));
+        }
 
-    context.set_basic_block(join_block);
+        let values: Vec<inkwell::values::IntValue<'ctx>> = data_values
+            .into_iter()
+            .map(|(value, abi_type)| abi_type.cleanup(context, value))
+            .collect();
+        let length = context.field_const((values.len() * compiler_common::SIZE_FIELD) as u64);
+        let range_start = context.build_heap_alloc(length);
+        context.build_store_multiple(range_start, values.as_slice());
 
-    Ok(None)
+        log(context, range_start, length, topics)
+    })
 }
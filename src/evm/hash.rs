@@ -2,10 +2,9 @@
 //! Translates the hash instruction.
 //!
 
-use inkwell::values::BasicValue;
-
 use crate::context::address_space::AddressSpace;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -14,58 +13,43 @@ use crate::Dependency;
 ///
 pub fn keccak256<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    input_offset: inkwell::values::IntValue<'ctx>,
-    input_size: inkwell::values::IntValue<'ctx>,
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::SwitchContext);
-    context.build_call(intrinsic, &[], "keccak256_switch_context");
-
-    let child_pointer_header = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_HEADER * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "keccak256_child_pointer_header",
-    );
-    context.build_store(child_pointer_header, input_size);
-
-    let child_pointer_data = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Child,
-        "keccak256_child_input_destination",
-    );
-    let heap_pointer = context.access_memory(
-        input_offset,
-        AddressSpace::Heap,
-        "keccak256_child_input_source",
-    );
-
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToChild,
-        child_pointer_data,
-        heap_pointer,
-        input_size,
-        "keccak256_memcpy_to_child",
-    );
-
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StaticCall);
-    let call_definition = context.builder().build_left_shift(
-        context.field_const_str(compiler_common::ABI_ADDRESS_KECCAK256),
-        context.field_const((compiler_common::BITLENGTH_X32) as u64),
-        "",
-    );
-    context.build_call(
-        intrinsic,
-        &[call_definition.as_basic_value_enum()],
-        "keccak256_call_external",
-    );
-
-    let result = context.build_load(child_pointer_data, "keccak256_result");
-
-    Ok(Some(result))
+    context.with_evm_op("KECCAK256", |context| {
+        operands.check_count(2)?;
+        let input_offset = operands.int(0)?;
+        let input_size = operands.int(1)?;
+
+        context.with_child_context("keccak256", |context| {
+            context.write_header_size(input_size, AddressSpace::Child);
+
+            let child_pointer_data = context.access_memory(
+                context.field_const(
+                    (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
+                ),
+                AddressSpace::Child,
+                "keccak256_child_input_destination",
+            );
+            let heap_pointer = context.access_memory(
+                input_offset,
+                AddressSpace::Heap,
+                "keccak256_child_input_source",
+            );
+
+            context.build_memcpy(
+                IntrinsicFunction::MemoryCopyToChild,
+                child_pointer_data,
+                heap_pointer,
+                input_size,
+                "keccak256_memcpy_to_child",
+            );
+
+            let result = context.build_keccak256_child(input_size, "keccak256_result")?;
+
+            Ok(Some(result))
+        })
+    })
 }
@@ -5,12 +5,15 @@
 use inkwell::values::BasicValue;
 
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::restricted_instruction::RestrictedInstruction;
 use crate::context::Context;
 use crate::Dependency;
 
 ///
 /// Translates the contract immutable load.
 ///
+/// Is only allowed in the runtime code.
+///
 pub fn load<'ctx, D>(
     context: &mut Context<'ctx, D>,
     key: String,
@@ -18,26 +21,34 @@ pub fn load<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageLoad);
+    context.with_evm_op("LOADIMMUTABLE", |context| {
+        context.check_code_type(RestrictedInstruction::LoadImmutable)?;
+
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?;
 
-    let position = context.field_const_str(compiler_common::keccak256(key.as_bytes()).as_str());
-    let is_external_storage = context.field_const(0);
-    let value = context
-        .build_call(
-            intrinsic,
-            &[
-                position.as_basic_value_enum(),
-                is_external_storage.as_basic_value_enum(),
-            ],
-            "immutable_load",
-        )
-        .expect("Contract storage always returns a value");
-    Ok(Some(value))
+        let position =
+            context.field_const_hex(compiler_common::keccak256(key.as_bytes()).as_str())?;
+        let is_external_storage = context.field_const(0);
+        let value = context
+            .build_call(
+                intrinsic,
+                &[
+                    position.as_basic_value_enum(),
+                    is_external_storage.as_basic_value_enum(),
+                ],
+                "immutable_load",
+            )
+            .expect("Contract storage always returns a value");
+        Ok(Some(value))
+    })
 }
 
 ///
 /// Translates the contract immutable store.
 ///
+/// Is only allowed in the deploy code. The value is buffered until the construction succeeds,
+/// see `Context::build_immutables_flush`.
+///
 pub fn store<'ctx, D>(
     context: &mut Context<'ctx, D>,
     key: String,
@@ -46,18 +57,11 @@ pub fn store<'ctx, D>(
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageStore);
+    context.with_evm_op("SETIMMUTABLE", |context| {
+        context.check_code_type(RestrictedInstruction::SetImmutable)?;
 
-    let position = context.field_const_str(compiler_common::keccak256(key.as_bytes()).as_str());
-    let is_external_storage = context.field_const(0);
-    context.build_call(
-        intrinsic,
-        &[
-            value.as_basic_value_enum(),
-            position.as_basic_value_enum(),
-            is_external_storage.as_basic_value_enum(),
-        ],
-        "immutable_store",
-    );
-    Ok(None)
+        let pointer = context.immutable_pointer(key.as_str());
+        context.build_store(pointer, value);
+        Ok(None)
+    })
 }
@@ -4,6 +4,7 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -12,16 +13,20 @@ use crate::Dependency;
 ///
 pub fn add_mod<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 3],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(context.build_invoke(
-        context.runtime.addmod,
-        &[arguments[0], arguments[1], arguments[2]],
-        "add_mod_call",
-    ))
+    context.with_evm_op("ADDMOD", |context| {
+        operands.check_count(3)?;
+
+        Ok(context.build_invoke(
+            context.runtime.addmod,
+            &[operands.value(0)?, operands.value(1)?, operands.value(2)?],
+            "add_mod_call",
+        ))
+    })
 }
 
 ///
@@ -29,16 +34,20 @@ where
 ///
 pub fn mul_mod<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 3],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    Ok(context.build_invoke(
-        context.runtime.mulmod,
-        &[arguments[0], arguments[1], arguments[2]],
-        "mul_mod_call",
-    ))
+    context.with_evm_op("MULMOD", |context| {
+        operands.check_count(3)?;
+
+        Ok(context.build_invoke(
+            context.runtime.mulmod,
+            &[operands.value(0)?, operands.value(1)?, operands.value(2)?],
+            "mul_mod_call",
+        ))
+    })
 }
 
 ///
@@ -46,64 +55,73 @@ where
 ///
 pub fn exponent<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let result_pointer = context.build_alloca(context.field_type(), "exponent_result");
-    context.build_store(result_pointer, context.field_const(1));
-
-    let condition_block = context.append_basic_block("exponent_loop_condition");
-    let body_block = context.append_basic_block("exponent_loop_body");
-    let increment_block = context.append_basic_block("exponent_loop_increment");
-    let join_block = context.append_basic_block("exponent_loop_join");
-
-    let index_pointer = context.build_alloca(context.field_type(), "exponent_loop_index_pointer");
-    let index_value = context.field_const(0).as_basic_value_enum();
-    context.build_store(index_pointer, index_value);
-    context.build_unconditional_branch(condition_block);
-
-    context.set_basic_block(condition_block);
-    let index_value = context
-        .build_load(index_pointer, "exponent_loop_index_value_condition")
-        .into_int_value();
-    let condition = context.builder().build_int_compare(
-        inkwell::IntPredicate::ULT,
-        index_value,
-        arguments[1].into_int_value(),
-        "exponent_loop_condition",
-    );
-    context.build_conditional_branch(condition, body_block, join_block);
-
-    context.set_basic_block(increment_block);
-    let index_value = context
-        .build_load(index_pointer, "exponent_loop_index_value_increment")
-        .into_int_value();
-    let incremented = context.builder().build_int_add(
-        index_value,
-        context.field_const(1),
-        "exponent_loop_index_value_incremented",
-    );
-    context.build_store(index_pointer, incremented);
-    context.build_unconditional_branch(condition_block);
-
-    context.set_basic_block(body_block);
-    let intermediate = context
-        .build_load(result_pointer, "exponent_loop_intermediate_result")
-        .into_int_value();
-    let result = context.builder().build_int_mul(
-        intermediate,
-        arguments[0].into_int_value(),
-        "exponent_loop_intermediate_result_multiplied",
-    );
-    context.build_store(result_pointer, result);
-    context.build_unconditional_branch(increment_block);
-
-    context.set_basic_block(join_block);
-    let result = context.build_load(result_pointer, "exponent_result");
-
-    Ok(Some(result))
+    context.with_evm_op("EXP", |context| {
+        operands.check_count(2)?;
+
+        let result_pointer = context.build_alloca(context.field_type(), "exponent_result");
+        context.build_store(result_pointer, context.field_const(1));
+
+        let condition_block = context.append_basic_block("exponent_loop_condition");
+        let body_block = context.append_basic_block("exponent_loop_body");
+        let increment_block = context.append_basic_block("exponent_loop_increment");
+        let join_block = context.append_basic_block("exponent_loop_join");
+
+        let index_pointer =
+            context.build_alloca(context.field_type(), "exponent_loop_index_pointer");
+        let index_value = context.field_const(0).as_basic_value_enum();
+        context.build_store(index_pointer, index_value);
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(condition_block);
+        let index_value = context
+            .build_load(index_pointer, "exponent_loop_index_value_condition")
+            .into_int_value();
+        let condition = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            index_value,
+            operands.int(1)?,
+            context.value_name("exponent_loop_condition").as_str(),
+        );
+        context.build_conditional_branch(condition, body_block, join_block);
+
+        context.set_basic_block(increment_block);
+        let index_value = context
+            .build_load(index_pointer, "exponent_loop_index_value_increment")
+            .into_int_value();
+        let incremented = context.builder().build_int_add(
+            index_value,
+            context.field_const(1),
+            context
+                .value_name("exponent_loop_index_value_incremented")
+                .as_str(),
+        );
+        context.build_store(index_pointer, incremented);
+        context.build_unconditional_branch(condition_block);
+
+        context.set_basic_block(body_block);
+        let intermediate = context
+            .build_load(result_pointer, "exponent_loop_intermediate_result")
+            .into_int_value();
+        let result = context.builder().build_int_mul(
+            intermediate,
+            operands.int(0)?,
+            context
+                .value_name("exponent_loop_intermediate_result_multiplied")
+                .as_str(),
+        );
+        context.build_store(result_pointer, result);
+        context.build_unconditional_branch(increment_block);
+
+        context.set_basic_block(join_block);
+        let result = context.build_load(result_pointer, "exponent_result");
+
+        Ok(Some(result))
+    })
 }
 
 ///
@@ -111,63 +129,72 @@ where
 ///
 pub fn sign_extend<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let bitlength = context.builder().build_int_mul(
-        arguments[0].into_int_value(),
-        context.field_const(compiler_common::BITLENGTH_BYTE as u64),
-        "sign_extend_bitlength_multiplied",
-    );
-    let bitlength = context.builder().build_int_add(
-        bitlength,
-        context.field_const((compiler_common::BITLENGTH_BYTE - 1) as u64),
-        "sign_extend_bitlength",
-    );
-    let sign_mask = context.builder().build_left_shift(
-        context.field_const(1),
-        bitlength,
-        "sign_extend_sign_mask",
-    );
-    let sign_bit = context.builder().build_and(
-        arguments[1].into_int_value(),
-        sign_mask,
-        "sign_extend_sign_bit",
-    );
-    let sign_bit_truncated = context.builder().build_right_shift(
-        sign_bit,
-        bitlength,
-        false,
-        "sign_extend_sign_bit_truncated",
-    );
-
-    let value_mask = context.builder().build_int_sub(
-        sign_mask,
-        context.field_const(1),
-        "sign_extend_value_mask",
-    );
-    let value = context.builder().build_and(
-        arguments[1].into_int_value(),
-        value_mask,
-        "sign_extend_value",
-    );
-
-    let sign_fill_bits = context.builder().build_xor(
-        value_mask,
-        context.field_type().const_all_ones(),
-        "sign_fill_bits",
-    );
-    let sign_fill_bits_checked = context.builder().build_int_mul(
-        sign_fill_bits,
-        sign_bit_truncated,
-        "sign_fill_bits_checked",
-    );
-    let result =
-        context
-            .builder()
-            .build_int_add(value, sign_fill_bits_checked, "sign_extend_result");
-
-    Ok(Some(result.as_basic_value_enum()))
+    context.with_evm_op("SIGNEXTEND", |context| {
+        operands.check_count(2)?;
+
+        let bitlength = context.builder().build_int_mul(
+            operands.int(0)?,
+            context.field_const(compiler_common::BITLENGTH_BYTE as u64),
+            context
+                .value_name("sign_extend_bitlength_multiplied")
+                .as_str(),
+        );
+        let bitlength = context.builder().build_int_add(
+            bitlength,
+            context.field_const((compiler_common::BITLENGTH_BYTE - 1) as u64),
+            context.value_name("sign_extend_bitlength").as_str(),
+        );
+        let sign_mask = context.builder().build_left_shift(
+            context.field_const(1),
+            bitlength,
+            context.value_name("sign_extend_sign_mask").as_str(),
+        );
+        let sign_bit = context.builder().build_and(
+            operands.int(1)?,
+            sign_mask,
+            context.value_name("sign_extend_sign_bit").as_str(),
+        );
+        let sign_bit_truncated = context.builder().build_right_shift(
+            sign_bit,
+            bitlength,
+            false,
+            context
+                .value_name("sign_extend_sign_bit_truncated")
+                .as_str(),
+        );
+
+        let value_mask = context.builder().build_int_sub(
+            sign_mask,
+            context.field_const(1),
+            context.value_name("sign_extend_value_mask").as_str(),
+        );
+        let value = context.builder().build_and(
+            operands.int(1)?,
+            value_mask,
+            context.value_name("sign_extend_value").as_str(),
+        );
+
+        let sign_fill_bits = context.builder().build_xor(
+            value_mask,
+            context.field_type().const_all_ones(),
+            context.value_name("sign_fill_bits").as_str(),
+        );
+        let sign_fill_bits_checked = context.builder().build_int_mul(
+            sign_fill_bits,
+            sign_bit_truncated,
+            context.value_name("sign_fill_bits_checked").as_str(),
+        );
+        let result = context.builder().build_int_add(
+            value,
+            sign_fill_bits_checked,
+            context.value_name("sign_extend_result").as_str(),
+        );
+
+        Ok(Some(result.as_basic_value_enum()))
+    })
 }
@@ -3,6 +3,9 @@
 //!
 
 use crate::context::address_space::AddressSpace;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
+use crate::context::versioned_instruction::VersionedInstruction;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -11,18 +14,19 @@ use crate::Dependency;
 ///
 pub fn load<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 1],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let pointer = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "memory_load_pointer",
-    );
-    let result = context.build_load(pointer, "memory_load_result");
-    Ok(Some(result))
+    context.with_evm_op("MLOAD", |context| {
+        operands.check_count(1)?;
+
+        let pointer =
+            context.access_memory(operands.int(0)?, AddressSpace::Heap, "memory_load_pointer");
+        let result = context.build_load(pointer, "memory_load_result");
+        Ok(Some(result))
+    })
 }
 
 ///
@@ -30,16 +34,20 @@ where
 ///
 pub fn store<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let offset = arguments[0].into_int_value();
-    let pointer = context.access_memory(offset, AddressSpace::Heap, "memory_store_pointer");
-    context.build_store(pointer, arguments[1]);
+    context.with_evm_op("MSTORE", |context| {
+        operands.check_count(2)?;
+
+        let offset = operands.int(0)?;
+        let pointer = context.access_memory(offset, AddressSpace::Heap, "memory_store_pointer");
+        context.build_store(pointer, operands.value(1)?);
 
-    Ok(None)
+        Ok(None)
+    })
 }
 
 ///
@@ -47,46 +55,61 @@ where
 ///
 pub fn store_byte<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let pointer = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "memory_store_byte_original_value_pointer",
-    );
+    context.with_evm_op("MSTORE8", |context| {
+        operands.check_count(2)?;
 
-    let original_value = context
-        .build_load(pointer, "memory_store_byte_original_value")
-        .into_int_value();
-    let original_value_shifted_left = context.builder().build_left_shift(
-        original_value,
-        context.field_const(compiler_common::BITLENGTH_BYTE as u64),
-        "memory_store_byte_original_value_shifted_left",
-    );
-    let original_value_shifted_right = context.builder().build_right_shift(
-        original_value_shifted_left,
-        context.field_const(compiler_common::BITLENGTH_BYTE as u64),
-        false,
-        "memory_store_byte_original_value_shifted_right",
-    );
+        let pointer = context.access_memory(
+            operands.int(0)?,
+            AddressSpace::Heap,
+            "memory_store_byte_pointer",
+        );
+        context.build_store_be_bytes(pointer, operands.int(1)?, 1)?;
+
+        Ok(None)
+    })
+}
+
+///
+/// Translates the heap memory copy.
+///
+/// Is only available since the Cancun EVM version. The regions may overlap, so the memory move
+/// intrinsic is used.
+///
+pub fn copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("MCOPY", |context| {
+        operands.check_count(3)?;
 
-    let value_shifted = context.builder().build_left_shift(
-        arguments[1].into_int_value(),
-        context.field_const(
-            ((compiler_common::SIZE_FIELD - 1) * compiler_common::BITLENGTH_BYTE) as u64,
-        ),
-        "memory_store_byte_value_shifted",
-    );
-    let result = context.builder().build_or(
-        original_value_shifted_right,
-        value_shifted,
-        "memory_store_byte_result",
-    );
+        context.check_evm_version(VersionedInstruction::MemoryCopy)?;
 
-    context.build_store(pointer, result);
+        let destination = context.access_memory(
+            operands.int(0)?,
+            AddressSpace::Heap,
+            "memory_copy_destination_pointer",
+        );
+        let source = context.access_memory(
+            operands.int(1)?,
+            AddressSpace::Heap,
+            "memory_copy_source_pointer",
+        );
+        context.build_memcpy(
+            IntrinsicFunction::MemoryMove,
+            destination,
+            source,
+            operands.int(2)?,
+            "memory_copy_memmove",
+        );
 
-    Ok(None)
+        Ok(None)
+    })
 }
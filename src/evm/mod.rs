@@ -2,9 +2,11 @@
 //! The common code generation utils.
 //!
 
+pub mod account;
 pub mod arithmetic;
 pub mod bitwise;
 pub mod calldata;
+pub mod code;
 pub mod comparison;
 pub mod context;
 pub mod contract;
@@ -14,9 +16,11 @@ pub mod hash;
 pub mod immutable;
 pub mod math;
 pub mod memory;
+pub mod paymaster;
 pub mod r#return;
 pub mod return_data;
 pub mod storage;
+pub mod value;
 
 use crate::context::Context;
 use crate::Dependency;
@@ -32,21 +36,24 @@ pub fn check_value_zero<'ctx, D>(
 ) where
     D: Dependency,
 {
-    let value_zero_block = context.append_basic_block("contract_call_value_zero_block");
-    let value_non_zero_block = context.append_basic_block("contract_call_value_non_zero_block");
-
-    let is_value_zero = context.builder().build_int_compare(
-        inkwell::IntPredicate::EQ,
+    let is_value_non_zero = context.builder().build_int_compare(
+        inkwell::IntPredicate::NE,
         value,
         context.field_const(0),
-        "contract_call_is_value_zero",
+        context
+            .value_name("contract_call_is_value_non_zero")
+            .as_str(),
     );
 
-    context.build_conditional_branch(is_value_zero, value_zero_block, value_non_zero_block);
-
-    context.set_basic_block(value_non_zero_block);
-    context.write_error(compiler_common::ABI_ERROR_FORBIDDEN_SEND_TRANSFER);
-    context.build_unconditional_branch(context.function().throw_block);
-
-    context.set_basic_block(value_zero_block);
+    context
+        .build_if(
+            is_value_non_zero,
+            "contract_call_value_non_zero",
+            |context| {
+                context.write_error(compiler_common::ABI_ERROR_FORBIDDEN_SEND_TRANSFER);
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            },
+        )
+        .expect("Always valid");
 }
@@ -0,0 +1,270 @@
+//!
+//! Translates the paymaster flow helpers.
+//!
+//! The paymaster input starts with the selector of the flow, followed by its ABI-encoded
+//! arguments. The validation result is the magic value followed by the ABI-encoded context bytes.
+//!
+
+use crate::context::address_space::AddressSpace;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::function::paymaster_flow::PaymasterFlow;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// Loads the flow selector from the paymaster input at the heap `input_offset`.
+///
+pub fn flow<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("PAYMASTER_FLOW", |context| {
+        let pointer = context.access_memory(
+            input_offset,
+            AddressSpace::Heap,
+            "paymaster_flow_selector_pointer",
+        );
+        let word = context
+            .build_load(pointer, "paymaster_flow_selector_word")
+            .into_int_value();
+        Ok(context.builder().build_right_shift(
+            word,
+            context.field_const(
+                (compiler_common::BITLENGTH_FIELD - compiler_common::BITLENGTH_X32) as u64,
+            ),
+            false,
+            context.value_name("paymaster_flow_selector").as_str(),
+        ))
+    })
+}
+
+///
+/// Runs the approval-based flow with the paymaster input at the heap `input_offset`.
+///
+/// Throws if the input selects another flow. Otherwise, approves the `spender` to take the
+/// minimal allowance of the token, and returns the token address and the allowance.
+///
+pub fn approval_based<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    spender: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<(
+    inkwell::values::IntValue<'ctx>,
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    context.with_evm_op("PAYMASTER_APPROVAL_BASED", |context| {
+        let selector = flow(context, input_offset)?;
+        let expected = Context::<D>::selector(PaymasterFlow::SIGNATURE_APPROVAL_BASED) as u64;
+        let is_flow_unsupported = context.builder().build_int_compare(
+            inkwell::IntPredicate::NE,
+            selector,
+            context.field_const(expected),
+            context
+                .value_name("paymaster_approval_based_is_flow_unsupported")
+                .as_str(),
+        );
+        context.build_if(
+            is_flow_unsupported,
+            "paymaster_approval_based_flow_unsupported",
+            |context| {
+                context.write_error(PaymasterFlow::ERROR_UNSUPPORTED_FLOW);
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            },
+        )?;
+
+        let token = load_argument(context, input_offset, 0, "paymaster_approval_based_token")?;
+        let token = context.build_address_mask(token);
+        let allowance = load_argument(
+            context,
+            input_offset,
+            1,
+            "paymaster_approval_based_allowance",
+        )?;
+        context.build_paymaster_approve(token, spender, allowance)?;
+
+        Ok((token, allowance))
+    })
+}
+
+///
+/// Encodes the paymaster validation result at the heap `destination`, returning its size.
+///
+/// The result is the magic value, followed by the context bytes copied from the heap
+/// `data_offset`. The word after the data is cleared to zero the padding.
+///
+pub fn encode_context<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    destination: inkwell::values::IntValue<'ctx>,
+    data_offset: inkwell::values::IntValue<'ctx>,
+    data_length: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("PAYMASTER_ENCODE_CONTEXT", |context| {
+        let magic = Context::<D>::selector(PaymasterFlow::SIGNATURE_VALIDATE_AND_PAY) as u64;
+        let magic =
+            context.build_left_align_bytes(context.field_const(magic), compiler_common::SIZE_X32);
+        let head_size = 2 * compiler_common::SIZE_FIELD;
+        let words = [magic, context.field_const(head_size as u64), data_length];
+        for (index, word) in words.into_iter().enumerate() {
+            let offset = context.builder().build_int_add(
+                destination,
+                context.field_const((index * compiler_common::SIZE_FIELD) as u64),
+                context
+                    .value_name("paymaster_encode_context_word_offset")
+                    .as_str(),
+            );
+            let pointer = context.access_memory(
+                offset,
+                AddressSpace::Heap,
+                "paymaster_encode_context_word_pointer",
+            );
+            context.build_store(pointer, word);
+        }
+
+        let data_destination = context.builder().build_int_add(
+            destination,
+            context.field_const((3 * compiler_common::SIZE_FIELD) as u64),
+            context
+                .value_name("paymaster_encode_context_data_destination")
+                .as_str(),
+        );
+        let destination_pointer = context.access_memory(
+            data_destination,
+            AddressSpace::Heap,
+            "paymaster_encode_context_data_destination_pointer",
+        );
+        let source_pointer = context.access_memory(
+            data_offset,
+            AddressSpace::Heap,
+            "paymaster_encode_context_data_source_pointer",
+        );
+        context.build_memcpy_non_empty(
+            IntrinsicFunction::MemoryMove,
+            destination_pointer,
+            source_pointer,
+            data_length,
+            "paymaster_encode_context_data_memmove",
+        );
+
+        let padding_offset = context.builder().build_int_add(
+            data_destination,
+            data_length,
+            context
+                .value_name("paymaster_encode_context_padding_offset")
+                .as_str(),
+        );
+        let padding_pointer = context.access_memory(
+            padding_offset,
+            AddressSpace::Heap,
+            "paymaster_encode_context_padding_pointer",
+        );
+        context.build_store(padding_pointer, context.field_const(0));
+
+        let padded_length = context.builder().build_and(
+            context.builder().build_int_add(
+                data_length,
+                context.field_const((compiler_common::SIZE_FIELD - 1) as u64),
+                context
+                    .value_name("paymaster_encode_context_length_rounded_up")
+                    .as_str(),
+            ),
+            context.field_const(!(compiler_common::SIZE_FIELD as u64 - 1)),
+            context
+                .value_name("paymaster_encode_context_length_padded")
+                .as_str(),
+        );
+        Ok(context.builder().build_int_add(
+            padded_length,
+            context.field_const((3 * compiler_common::SIZE_FIELD) as u64),
+            context.value_name("paymaster_encode_context_size").as_str(),
+        ))
+    })
+}
+
+///
+/// Decodes the context bytes ABI-encoded at the heap `offset`.
+///
+/// Returns the heap offset of the data and its length.
+///
+pub fn decode_context<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    offset: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<(
+    inkwell::values::IntValue<'ctx>,
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    context.with_evm_op("PAYMASTER_DECODE_CONTEXT", |context| {
+        let pointer = context.access_memory(
+            offset,
+            AddressSpace::Heap,
+            "paymaster_decode_context_offset_pointer",
+        );
+        let relative_offset = context
+            .build_load(pointer, "paymaster_decode_context_relative_offset")
+            .into_int_value();
+        let length_offset = context.builder().build_int_add(
+            offset,
+            relative_offset,
+            context
+                .value_name("paymaster_decode_context_length_offset")
+                .as_str(),
+        );
+        let pointer = context.access_memory(
+            length_offset,
+            AddressSpace::Heap,
+            "paymaster_decode_context_length_pointer",
+        );
+        let length = context
+            .build_load(pointer, "paymaster_decode_context_length")
+            .into_int_value();
+        let data_offset = context.builder().build_int_add(
+            length_offset,
+            context.field_const(compiler_common::SIZE_FIELD as u64),
+            context
+                .value_name("paymaster_decode_context_data_offset")
+                .as_str(),
+        );
+
+        Ok((data_offset, length))
+    })
+}
+
+///
+/// Loads the flow argument at `index` from the paymaster input at the heap `input_offset`.
+///
+fn load_argument<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    input_offset: inkwell::values::IntValue<'ctx>,
+    index: usize,
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let offset = context.builder().build_int_add(
+        input_offset,
+        context
+            .field_const((compiler_common::SIZE_X32 + index * compiler_common::SIZE_FIELD) as u64),
+        context
+            .value_name(format!("{}_offset", name).as_str())
+            .as_str(),
+    );
+    let pointer = context.access_memory(
+        offset,
+        AddressSpace::Heap,
+        format!("{}_pointer", name).as_str(),
+    );
+    Ok(context.build_load(pointer, name).into_int_value())
+}
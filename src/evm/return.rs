@@ -2,9 +2,8 @@
 //! Translates the transaction return operations.
 //!
 
-use crate::context::address_space::AddressSpace;
-use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
-use crate::context::function::Function;
+use crate::context::exit_kind::ExitKind;
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -13,40 +12,17 @@ use crate::Dependency;
 ///
 pub fn r#return<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let function = context.function().to_owned();
+    context.with_evm_op("RETURN", |context| {
+        operands.check_count(2)?;
 
-    let source = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "return_source_pointer",
-    );
-
-    let destination = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Parent,
-        "return_destination_pointer",
-    );
-
-    let size = arguments[1].into_int_value();
-
-    context.write_header(size, AddressSpace::Parent);
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToParent,
-        destination,
-        source,
-        size,
-        "return_memcpy_to_parent",
-    );
-    long_return(context, function)?;
-
-    Ok(None)
+        context.build_exit(ExitKind::Return, operands.int(0)?, operands.int(1)?)?;
+        Ok(None)
+    })
 }
 
 ///
@@ -54,40 +30,17 @@ where
 ///
 pub fn revert<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let function = context.function().to_owned();
-
-    let source = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "revert_source_pointer",
-    );
-
-    let destination = context.access_memory(
-        context.field_const(
-            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64,
-        ),
-        AddressSpace::Parent,
-        "revert_destination_pointer",
-    );
+    context.with_evm_op("REVERT", |context| {
+        operands.check_count(2)?;
 
-    let size = arguments[1].into_int_value();
-
-    context.write_header(size, AddressSpace::Parent);
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyToParent,
-        destination,
-        source,
-        size,
-        "revert_memcpy_to_parent",
-    );
-
-    context.build_unconditional_branch(function.throw_block);
-    Ok(None)
+        context.build_exit(ExitKind::Revert, operands.int(0)?, operands.int(1)?)?;
+        Ok(None)
+    })
 }
 
 ///
@@ -99,12 +52,11 @@ pub fn stop<'ctx, D>(
 where
     D: Dependency,
 {
-    let function = context.function().to_owned();
-
-    context.write_header(context.field_const(0), AddressSpace::Parent);
-    long_return(context, function)?;
-
-    Ok(None)
+    context.with_evm_op("STOP", |context| {
+        let zero = context.field_const(0);
+        context.build_exit(ExitKind::Stop, zero, zero)?;
+        Ok(None)
+    })
 }
 
 ///
@@ -116,41 +68,9 @@ pub fn invalid<'ctx, D>(
 where
     D: Dependency,
 {
-    let function = context.function().to_owned();
-
-    context.write_header(context.field_const(0), AddressSpace::Parent);
-
-    context.build_unconditional_branch(function.throw_block);
-    Ok(None)
-}
-
-///
-/// Generates the long return sequence.
-///
-fn long_return<'ctx, D>(
-    context: &mut Context<'ctx, D>,
-    function: Function<'ctx>,
-) -> anyhow::Result<()>
-where
-    D: Dependency,
-{
-    if context.function().name == compiler_common::LLVM_FUNCTION_ENTRY
-        || context.function().name == compiler_common::LLVM_FUNCTION_CONSTRUCTOR
-        || context.function().name == compiler_common::LLVM_FUNCTION_SELECTOR
-    {
-        context.build_unconditional_branch(function.return_block);
-    } else {
-        let long_return_flag_pointer = context.access_memory(
-            context.field_const(
-                (compiler_common::ABI_MEMORY_OFFSET_LONG_RETURN * compiler_common::SIZE_FIELD)
-                    as u64,
-            ),
-            AddressSpace::Heap,
-            "long_return_flag_pointer",
-        );
-        context.build_store(long_return_flag_pointer, context.field_const(1));
-        context.build_unconditional_branch(function.throw_block);
-    }
-
-    Ok(())
+    context.with_evm_op("INVALID", |context| {
+        let zero = context.field_const(0);
+        context.build_exit(ExitKind::Invalid, zero, zero)?;
+        Ok(None)
+    })
 }
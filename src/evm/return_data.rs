@@ -4,28 +4,29 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::abi_type::AbiType;
 use crate::context::address_space::AddressSpace;
+use crate::context::call_result::CallResult;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
 use crate::context::Context;
 use crate::Dependency;
 
 ///
 /// Translates the return data size.
 ///
+/// The size is saved by the external call lowerings, so it is a single load.
+///
 pub fn size<'ctx, D>(
     context: &mut Context<'ctx, D>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let header = context.read_header(AddressSpace::Child);
-    let value = context.builder().build_and(
-        header,
-        context.field_const(0x00000000ffffffff),
-        "calldata_size",
-    );
-
-    Ok(Some(value.as_basic_value_enum()))
+    context.with_evm_op("RETURNDATASIZE", |context| {
+        let value = context.read_return_data_size();
+        Ok(Some(value.as_basic_value_enum()))
+    })
 }
 
 ///
@@ -33,38 +34,139 @@ where
 ///
 pub fn copy<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 3],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let destination = context.access_memory(
-        arguments[0].into_int_value(),
-        AddressSpace::Heap,
-        "return_data_copy_destination_pointer",
-    );
+    context.with_evm_op("RETURNDATACOPY", |context| {
+        operands.check_count(3)?;
 
-    let source_offset_shift = compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
-    let source_offset = context.builder().build_int_add(
-        arguments[1].into_int_value(),
-        context.field_const(source_offset_shift as u64),
-        "return_data_copy_source_offset",
-    );
-    let source = context.access_memory(
-        source_offset,
-        AddressSpace::Child,
-        "return_data_copy_source_pointer",
-    );
+        let destination = context.access_memory(
+            operands.int(0)?,
+            AddressSpace::Heap,
+            "return_data_copy_destination_pointer",
+        );
+
+        let source_offset_shift =
+            compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD;
+        let source_offset = context.builder().build_int_add(
+            operands.int(1)?,
+            context.field_const(source_offset_shift as u64),
+            context
+                .value_name("return_data_copy_source_offset")
+                .as_str(),
+        );
+        let source = context.access_memory(
+            source_offset,
+            AddressSpace::Child,
+            "return_data_copy_source_pointer",
+        );
+
+        let size = operands.int(2)?;
 
-    let size = arguments[2].into_int_value();
+        context.build_memcpy_non_empty(
+            IntrinsicFunction::MemoryCopyFromChild,
+            destination,
+            source,
+            size,
+            "return_data_copy_memcpy_from_child",
+        );
+
+        Ok(None)
+    })
+}
+
+///
+/// Decodes the static head of the return data into field values of `head_types`.
+///
+/// Throws if the return data is shorter than the head. The sub-word values are cleaned up
+/// according to their types.
+///
+pub fn decode<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    head_types: &[AbiType],
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("RETURNDATACOPY", |context| {
+        let return_data_size = context.read_return_data_size();
+        decode_with_size(context, return_data_size, head_types)
+    })
+}
 
-    context.build_memcpy(
-        IntrinsicFunction::MemoryCopyFromChild,
-        destination,
-        source,
-        size,
-        "return_data_copy_memcpy_from_child",
+///
+/// Decodes the static head of the `call_result` return data into field values of `head_types`.
+///
+/// Uses the return data size saved in the call result, so it is not reloaded.
+///
+pub fn decode_call_result<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    call_result: &CallResult<'ctx>,
+    head_types: &[AbiType],
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("RETURNDATACOPY", |context| {
+        decode_with_size(context, call_result.return_data_size, head_types)
+    })
+}
+
+///
+/// Decodes the static head of the return data of `return_data_size` bytes.
+///
+fn decode_with_size<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    return_data_size: inkwell::values::IntValue<'ctx>,
+    head_types: &[AbiType],
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    for abi_type in head_types.iter() {
+        abi_type.validate()?;
+    }
+    if head_types.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let head_size = context.field_const((head_types.len() * compiler_common::SIZE_FIELD) as u64);
+    let is_long_enough = context.builder().build_int_compare(
+        inkwell::IntPredicate::UGE,
+        return_data_size,
+        head_size,
+        context
+            .value_name("return_data_decode_is_long_enough")
+            .as_str(),
     );
+    let decode_block = context.append_basic_block("return_data_decode_block");
+    context.build_conditional_branch(
+        is_long_enough,
+        decode_block,
+        context.try_function()?.throw_block,
+    );
+    context.set_basic_block(decode_block);
+
+    let mut values = Vec::with_capacity(head_types.len());
+    for (index, abi_type) in head_types.iter().enumerate() {
+        let pointer = context.access_memory(
+            context.field_const(
+                ((compiler_common::ABI_MEMORY_OFFSET_DATA + index) * compiler_common::SIZE_FIELD)
+                    as u64,
+            ),
+            AddressSpace::Child,
+            format!("return_data_decode_pointer_{}", index).as_str(),
+        );
+        let value = context
+            .build_load(
+                pointer,
+                format!("return_data_decode_value_{}", index).as_str(),
+            )
+            .into_int_value();
+        values.push(abi_type.cleanup(context, value));
+    }
 
-    Ok(None)
+    Ok(values)
 }
@@ -4,7 +4,12 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::address_space::AddressSpace;
+use crate::context::argument::Argument;
 use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
+use crate::context::privileged_operation::PrivilegedOperation;
+use crate::context::storage_hint::StorageHint;
 use crate::context::Context;
 use crate::Dependency;
 
@@ -13,23 +18,27 @@ use crate::Dependency;
 ///
 pub fn load<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 1],
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageLoad);
+    context.with_evm_op("SLOAD", |context| {
+        operands.check_count(1)?;
 
-    let position = arguments[0];
-    let is_external_storage = context.field_const(0);
-    let value = context
-        .build_call(
-            intrinsic,
-            &[position, is_external_storage.as_basic_value_enum()],
-            "storage_load",
-        )
-        .expect("Contract storage always returns a value");
-    Ok(Some(value))
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?;
+
+        let position = operands.value(0)?;
+        let is_external_storage = context.field_const(0);
+        let value = context
+            .build_call(
+                intrinsic,
+                &[position, is_external_storage.as_basic_value_enum()],
+                "storage_load",
+            )
+            .expect("Contract storage always returns a value");
+        Ok(Some(value))
+    })
 }
 
 ///
@@ -37,20 +46,380 @@ where
 ///
 pub fn store<'ctx, D>(
     context: &mut Context<'ctx, D>,
-    arguments: [inkwell::values::BasicValueEnum<'ctx>; 2],
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SSTORE", |context| {
+        operands.check_count(2)?;
+
+        let position = operands.int(0)?;
+        let value = operands.int(1)?;
+        let is_external_storage = context.field_const(0);
+        context.build_storage_store(value, position, is_external_storage, "storage_store")?;
+        Ok(None)
+    })
+}
+
+///
+/// Translates the contract storage load, annotated with the access `hint`.
+///
+pub fn load_with_hint<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+    hint: StorageHint,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SLOAD", |context| {
+        let value = load(context, operands)?;
+        context.annotate_storage_access(hint);
+        Ok(value)
+    })
+}
+
+///
+/// Translates the contract storage store, annotated with the access `hint`.
+///
+pub fn store_with_hint<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+    hint: StorageHint,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SSTORE", |context| {
+        let value = store(context, operands)?;
+        context.annotate_storage_access(hint);
+        Ok(value)
+    })
+}
+
+///
+/// Loads the packed value of `size_bits` at `offset_bits` from the lowest bit of the `slot`.
+///
+/// The value is returned in the lowest bits, with the higher bits cleared.
+///
+pub fn load_packed<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    slot: inkwell::values::IntValue<'ctx>,
+    offset_bits: usize,
+    size_bits: usize,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SLOAD", |context| {
+        check_packed_layout(offset_bits, size_bits)?;
+
+        let value = load(context, [slot.as_basic_value_enum()].into())?
+            .expect("Always exists")
+            .into_int_value();
+        if size_bits == compiler_common::BITLENGTH_FIELD {
+            return Ok(value);
+        }
+
+        let value_shifted = context.builder().build_right_shift(
+            value,
+            context.field_const(offset_bits as u64),
+            false,
+            context.value_name("storage_load_packed_shifted").as_str(),
+        );
+        let value_masked = context.builder().build_and(
+            value_shifted,
+            packed_mask(context, size_bits),
+            context.value_name("storage_load_packed_masked").as_str(),
+        );
+        Ok(value_masked)
+    })
+}
+
+///
+/// Stores the lowest `size_bits` of `value` at `offset_bits` from the lowest bit of the `slot`,
+/// preserving the rest of the slot.
+///
+/// The read-modify-write sequence is elided if the whole slot is written.
+///
+pub fn store_packed<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    slot: inkwell::values::IntValue<'ctx>,
+    offset_bits: usize,
+    size_bits: usize,
+    value: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SSTORE", |context| {
+        check_packed_layout(offset_bits, size_bits)?;
+
+        if size_bits == compiler_common::BITLENGTH_FIELD {
+            store(
+                context,
+                [slot.as_basic_value_enum(), value.as_basic_value_enum()].into(),
+            )?;
+            return Ok(());
+        }
+
+        let offset = context.field_const(offset_bits as u64);
+        let mask = context.builder().build_left_shift(
+            packed_mask(context, size_bits),
+            offset,
+            context.value_name("storage_store_packed_mask").as_str(),
+        );
+
+        let original = load(context, [slot.as_basic_value_enum()].into())?
+            .expect("Always exists")
+            .into_int_value();
+        let original_cleared = context.builder().build_and(
+            original,
+            context.builder().build_not(
+                mask,
+                context
+                    .value_name("storage_store_packed_mask_inverted")
+                    .as_str(),
+            ),
+            context
+                .value_name("storage_store_packed_original_cleared")
+                .as_str(),
+        );
+        let value_shifted = context.builder().build_left_shift(
+            value,
+            offset,
+            context
+                .value_name("storage_store_packed_value_shifted")
+                .as_str(),
+        );
+        let value_masked = context.builder().build_and(
+            value_shifted,
+            mask,
+            context
+                .value_name("storage_store_packed_value_masked")
+                .as_str(),
+        );
+        let result = context.builder().build_or(
+            original_cleared,
+            value_masked,
+            context.value_name("storage_store_packed_result").as_str(),
+        );
+
+        store(
+            context,
+            [slot.as_basic_value_enum(), result.as_basic_value_enum()].into(),
+        )?;
+        Ok(())
+    })
+}
+
+///
+/// Computes the slot of the mapping value at `key`, following the Solidity layout rules.
+///
+/// The slot is `keccak256(key . base)`, folded at compile time if both inputs are constants.
+///
+pub fn mapping_slot<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    base: inkwell::values::IntValue<'ctx>,
+    key: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("KECCAK256", |context| {
+        keccak256_words(context, &[key, base], "storage_mapping_slot")
+    })
+}
+
+///
+/// Computes the slot of the dynamic array element at `index`, following the Solidity layout
+/// rules for the single-slot elements.
+///
+/// The slot is `keccak256(base) + index`, with the hash folded at compile time if `base` is
+/// a constant.
+///
+pub fn array_element_slot<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    base: inkwell::values::IntValue<'ctx>,
+    index: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("KECCAK256", |context| {
+        let data_slot = keccak256_words(context, &[base], "storage_array_data_slot")?;
+        Ok(context.builder().build_int_add(
+            data_slot,
+            index,
+            context.value_name("storage_array_element_slot").as_str(),
+        ))
+    })
+}
+
+///
+/// Hashes the concatenation of the field `words`.
+///
+/// The hash is computed at compile time if all the words are constants.
+///
+fn keccak256_words<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    words: &[inkwell::values::IntValue<'ctx>],
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let constants: Option<Vec<num::BigUint>> = words
+        .iter()
+        .map(|word| Argument::constant_of(word.as_basic_value_enum()))
+        .collect();
+    if let Some(constants) = constants {
+        let mut preimage = Vec::with_capacity(words.len() * compiler_common::SIZE_FIELD);
+        for constant in constants.into_iter() {
+            let bytes = constant.to_bytes_be();
+            preimage.extend(vec![0u8; compiler_common::SIZE_FIELD - bytes.len()]);
+            preimage.extend(bytes);
+        }
+        return context.field_const_hex(compiler_common::keccak256(preimage.as_slice()).as_str());
+    }
+
+    context.with_child_context(name, |context| {
+        let input_size = context.field_const((words.len() * compiler_common::SIZE_FIELD) as u64);
+        context.write_header_size(input_size, AddressSpace::Child);
+
+        for (index, word) in words.iter().enumerate() {
+            let child_pointer = context.access_memory(
+                context.field_const(
+                    ((compiler_common::ABI_MEMORY_OFFSET_DATA + index)
+                        * compiler_common::SIZE_FIELD) as u64,
+                ),
+                AddressSpace::Child,
+                format!("{}_child_pointer_{}", name, index).as_str(),
+            );
+            context.build_store(child_pointer, *word);
+        }
+
+        let result = context.build_keccak256_child(input_size, name)?;
+        Ok(result.into_int_value())
+    })
+}
+
+///
+/// Checks that the packed value of `size_bits` at `offset_bits` fits into a slot.
+///
+fn check_packed_layout(offset_bits: usize, size_bits: usize) -> anyhow::Result<()> {
+    if size_bits == 0 || offset_bits + size_bits > compiler_common::BITLENGTH_FIELD {
+        anyhow::bail!(
+            "The packed value of {} bits at offset {} does not fit into a storage slot",
+            size_bits,
+            offset_bits
+        );
+    }
+    Ok(())
+}
+
+///
+/// Returns the mask of the lowest `size_bits` bits.
+///
+fn packed_mask<'ctx, D>(
+    context: &Context<'ctx, D>,
+    size_bits: usize,
+) -> inkwell::values::IntValue<'ctx>
+where
+    D: Dependency,
+{
+    context.builder().build_right_shift(
+        context.field_type().const_all_ones(),
+        context.field_const((compiler_common::BITLENGTH_FIELD - size_bits) as u64),
+        false,
+        context.value_name("storage_packed_mask").as_str(),
+    )
+}
+
+///
+/// Translates the switch to the storage of another account.
+///
+/// Is only allowed in the system mode.
+///
+pub fn set_external<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SETSTORAGE", |context| {
+        operands.check_count(1)?;
+
+        context.check_system_mode(PrivilegedOperation::SetStorage)?;
+
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::SetStorage)?;
+
+        let address = operands.value(0)?;
+        context.build_call(intrinsic, &[address], "storage_set_external");
+        Ok(None)
+    })
+}
+
+///
+/// Translates the storage load from another account.
+///
+/// Is only allowed in the system mode.
+///
+pub fn load_external<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
 where
     D: Dependency,
 {
-    let intrinsic = context.get_intrinsic_function(IntrinsicFunction::StorageStore);
+    context.with_evm_op("SLOAD", |context| {
+        operands.check_count(1)?;
+
+        context.check_system_mode(PrivilegedOperation::ExternalStorageLoad)?;
+
+        let intrinsic = context.try_get_intrinsic_function(IntrinsicFunction::StorageLoad)?;
+
+        let position = operands.value(0)?;
+        let is_external_storage = context.field_const(1);
+        let value = context
+            .build_call(
+                intrinsic,
+                &[position, is_external_storage.as_basic_value_enum()],
+                "storage_load_external",
+            )
+            .expect("Contract storage always returns a value");
+        Ok(Some(value))
+    })
+}
+
+///
+/// Translates the storage store to another account.
+///
+/// Is only allowed in the system mode.
+///
+pub fn store_external<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("SSTORE", |context| {
+        operands.check_count(2)?;
+
+        context.check_system_mode(PrivilegedOperation::ExternalStorageStore)?;
 
-    let position = arguments[0];
-    let value = arguments[1];
-    let is_external_storage = context.field_const(0);
-    context.build_call(
-        intrinsic,
-        &[value, position, is_external_storage.as_basic_value_enum()],
-        "storage_store",
-    );
-    Ok(None)
+        let position = operands.int(0)?;
+        let value = operands.int(1)?;
+        let is_external_storage = context.field_const(1);
+        context.build_storage_store(
+            value,
+            position,
+            is_external_storage,
+            "storage_store_external",
+        )?;
+        Ok(None)
+    })
 }
@@ -0,0 +1,220 @@
+//!
+//! Translates the call value instructions.
+//!
+
+use inkwell::values::BasicValue;
+
+use num::Zero;
+
+use crate::context::address_space::AddressSpace;
+use crate::context::argument::Argument;
+use crate::context::call_definition::CallDefinition;
+use crate::context::capabilities::value_simulation::ValueSimulation;
+use crate::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+use crate::context::operands::Operands;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// Translates the `callvalue` instruction.
+///
+/// Without the value simulation, the call value is always zero. With it, the value is requested
+/// from the token contract, which must know the value of the current frame.
+///
+pub fn call_value<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("CALLVALUE", |context| {
+        match context.capabilities().value_simulation.clone() {
+            ValueSimulation::Disabled => Ok(Some(context.field_const(0).as_basic_value_enum())),
+            ValueSimulation::Token(ref token) => {
+                let value = call_token(
+                    context,
+                    token.as_str(),
+                    IntrinsicFunction::StaticCall,
+                    ValueSimulation::SIGNATURE_MSG_VALUE,
+                    &[],
+                    "call_value",
+                )?;
+                Ok(Some(value.as_basic_value_enum()))
+            }
+        }
+    })
+}
+
+///
+/// Translates the `balance` instruction.
+///
+/// Is only supported with the value simulation.
+///
+pub fn balance<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operands: Operands<'ctx>,
+) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+where
+    D: Dependency,
+{
+    context.with_evm_op("BALANCE", |context| {
+        operands.check_count(1)?;
+        let address = operands.int(0)?;
+
+        match context.capabilities().value_simulation.clone() {
+            ValueSimulation::Disabled => {
+                anyhow::bail!(
+                    "The `balance` instruction requires the value simulation on this target"
+                )
+            }
+            ValueSimulation::Token(ref token) => {
+                let address = context.build_address_mask(address);
+                let value = call_token(
+                    context,
+                    token.as_str(),
+                    IntrinsicFunction::StaticCall,
+                    ValueSimulation::SIGNATURE_BALANCE_OF,
+                    &[address],
+                    "balance",
+                )?;
+                Ok(Some(value.as_basic_value_enum()))
+            }
+        }
+    })
+}
+
+///
+/// Transfers `value` to `address` before a value-bearing call.
+///
+/// Without the value simulation, throws on non-zero values. With it, the transfer is skipped for
+/// zero values and throws if the token contract rejects the transfer.
+///
+/// Returns whether a non-zero value may have been transferred. The transfer cannot be undone,
+/// so in that case the call must be made atomic by throwing if it fails.
+///
+pub fn transfer<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    address: inkwell::values::IntValue<'ctx>,
+    value: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<bool>
+where
+    D: Dependency,
+{
+    let token = match context.capabilities().value_simulation.clone() {
+        ValueSimulation::Disabled => {
+            crate::evm::check_value_zero(context, value);
+            return Ok(false);
+        }
+        ValueSimulation::Token(token) => token,
+    };
+
+    if Argument::constant_of(value.as_basic_value_enum()).map_or(false, |value| value.is_zero()) {
+        return Ok(false);
+    }
+
+    let is_value_non_zero = context.builder().build_int_compare(
+        inkwell::IntPredicate::NE,
+        value,
+        context.field_const(0),
+        context
+            .value_name("value_transfer_is_value_non_zero")
+            .as_str(),
+    );
+    context.build_if(is_value_non_zero, "value_transfer", |context| {
+        let address = context.build_address_mask(address);
+        call_token(
+            context,
+            token.as_str(),
+            IntrinsicFunction::FarCall,
+            ValueSimulation::SIGNATURE_TRANSFER,
+            &[address, value],
+            "value_transfer",
+        )?;
+        Ok(())
+    })?;
+    Ok(true)
+}
+
+///
+/// Calls the token contract `signature` method with `arguments`, returning the first word of
+/// the return data.
+///
+/// Throws if the call fails.
+///
+fn call_token<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    token: &str,
+    call_type: IntrinsicFunction,
+    signature: &str,
+    arguments: &[inkwell::values::IntValue<'ctx>],
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let selector = Context::<D>::selector(signature) as u64;
+
+    context.with_child_context(name, |context| {
+        let input_size = compiler_common::SIZE_X32 + arguments.len() * compiler_common::SIZE_FIELD;
+        context.write_header_size(context.field_const(input_size as u64), AddressSpace::Child);
+
+        let data_offset =
+            (compiler_common::ABI_MEMORY_OFFSET_DATA * compiler_common::SIZE_FIELD) as u64;
+        let selector_pointer = context.access_memory(
+            context.field_const(data_offset),
+            AddressSpace::Child,
+            format!("{}_selector_pointer", name).as_str(),
+        );
+        let selector = context
+            .build_left_align_bytes(context.field_const(selector), compiler_common::SIZE_X32);
+        context.build_store(selector_pointer, selector);
+        for (index, argument) in arguments.iter().enumerate() {
+            let argument_pointer = context.access_memory(
+                context.field_const(
+                    data_offset
+                        + (compiler_common::SIZE_X32 + index * compiler_common::SIZE_FIELD) as u64,
+                ),
+                AddressSpace::Child,
+                format!("{}_argument_{}_pointer", name, index).as_str(),
+            );
+            context.build_store(argument_pointer, *argument);
+        }
+
+        let intrinsic = context.try_get_intrinsic_function(call_type)?;
+        let call_definition = CallDefinition::new(context.field_const_hex(token)?).encode(context);
+        let is_call_successful = context
+            .build_call(
+                intrinsic,
+                &[call_definition.as_basic_value_enum()],
+                format!("{}_call_external", name).as_str(),
+            )
+            .ok_or_else(|| anyhow::anyhow!("The token contract call returns no success flag"))?
+            .into_int_value();
+
+        let is_call_failed = context.builder().build_int_compare(
+            inkwell::IntPredicate::EQ,
+            is_call_successful,
+            context.field_const(0),
+            context
+                .value_name(format!("{}_is_call_failed", name).as_str())
+                .as_str(),
+        );
+        context.build_if(
+            is_call_failed,
+            format!("{}_failed", name).as_str(),
+            |context| {
+                context.build_unconditional_branch(context.try_function()?.throw_block);
+                Ok(())
+            },
+        )?;
+
+        let result_pointer = context.access_memory(
+            context.field_const(data_offset),
+            AddressSpace::Child,
+            format!("{}_result_pointer", name).as_str(),
+        );
+        Ok(context
+            .build_load(result_pointer, format!("{}_result", name).as_str())
+            .into_int_value())
+    })
+}
@@ -0,0 +1,23 @@
+//!
+//! The fuzzing harness dependency manager.
+//!
+
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use crate::Dependency as IDependency;
+
+///
+/// The fuzzing harness dependency manager.
+///
+/// The translators driven by the harnesses do not reference other contracts, so every request
+/// is rejected.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dependency {}
+
+impl IDependency for Dependency {
+    fn resolve_library(_project: Arc<RwLock<Self>>, path: &str) -> anyhow::Result<String> {
+        anyhow::bail!("The library `{}` cannot be resolved while fuzzing", path)
+    }
+}
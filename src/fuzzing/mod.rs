@@ -0,0 +1,159 @@
+//!
+//! The fuzzing harnesses of the EVM translators.
+//!
+//! The harnesses are deterministic, so the `cargo-fuzz` targets only have to forward the input,
+//! e.g. `fuzz_target!(|data: &[u8]| compiler_llvm_context::fuzzing::harness(data));`.
+//!
+
+pub mod dependency;
+pub mod operand_shape;
+pub mod translator;
+
+use inkwell::types::BasicType;
+use inkwell::values::BasicValue;
+
+use crate::context::error::Error as ContextError;
+use crate::context::long_return_policy::LongReturnPolicy;
+use crate::context::Context;
+
+use self::dependency::Dependency;
+use self::operand_shape::OperandShape;
+use self::translator::Translator;
+
+/// The target name of the harness modules.
+pub const TARGET_NAME: &str = "syncvm";
+
+/// The harness module name.
+pub const MODULE_NAME: &str = "fuzzing";
+
+/// The harness function name.
+pub const FUNCTION_NAME: &str = "fuzzing_harness";
+
+/// The number of the harness function parameters, which provide the unknown operands.
+pub const PARAMETERS: usize = 4;
+
+///
+/// Decodes the translator and the operand shapes from the fuzzer-provided `data` and checks the
+/// translation.
+///
+/// The first byte selects the translator, and the following bytes encode the operand shapes.
+/// The missing bytes are treated as zeros, so every input is valid.
+///
+/// # Panics
+/// If the translated function does not pass the verifier.
+///
+pub fn harness(data: &[u8]) {
+    let (translator, data) = match data.split_first() {
+        Some((byte, data)) => (Translator::from_byte(*byte), data),
+        None => (Translator::from_byte(0), data),
+    };
+    let shapes: Vec<OperandShape> = (0..translator.input_size())
+        .map(|index| {
+            let offset = (index * OperandShape::ENCODED_SIZE).min(data.len());
+            OperandShape::decode(&data[offset..], PARAMETERS)
+        })
+        .collect();
+
+    if let Err(error) = check(translator, shapes.as_slice()) {
+        panic!(
+            "The {:?} translation with operands {:?} is invalid: {}",
+            translator, shapes, error
+        );
+    }
+}
+
+///
+/// Translates `translator` with the `shapes` operands in a fresh module and verifies it.
+///
+/// The translations rejected with the context errors, e.g. the instructions restricted to
+/// another code type, are not reported, since the frontends report them to the user. The throw
+/// and catch blocks are always built, so the exception paths are verified as well.
+///
+/// # Errors
+/// If the translation fails with another error, or the translated function does not pass the
+/// verifier.
+///
+pub fn check(translator: Translator, shapes: &[OperandShape]) -> anyhow::Result<()> {
+    let llvm = inkwell::context::Context::create();
+    let machine = target_machine()?;
+    let mut context = Context::<Dependency>::new(
+        &llvm,
+        &machine,
+        inkwell::OptimizationLevel::None,
+        inkwell::OptimizationLevel::None,
+        MODULE_NAME,
+        None,
+        vec![],
+    );
+
+    let function_type = context.function_type(
+        1,
+        vec![context.field_type().as_basic_type_enum(); PARAMETERS],
+    );
+    context.add_function(
+        FUNCTION_NAME,
+        function_type,
+        Some(inkwell::module::Linkage::External),
+    )?;
+    let function = context
+        .get_function(FUNCTION_NAME)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Harness function `{}` not found", FUNCTION_NAME))?;
+    context.set_function(function);
+    context.set_basic_block(context.function().entry_block);
+
+    let parameters: Vec<inkwell::values::IntValue> = context
+        .function()
+        .value
+        .get_param_iter()
+        .map(|parameter| parameter.into_int_value())
+        .collect();
+    let operands: Vec<inkwell::values::BasicValueEnum> = shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .build(&context, parameters.as_slice())
+                .as_basic_value_enum()
+        })
+        .collect();
+
+    let result_pointer = context.build_alloca(context.field_type(), "fuzzing_result_pointer");
+    context.build_store(result_pointer, context.field_const(0));
+    match translator.translate(&mut context, operands.into()) {
+        Ok(Some(result)) => context.build_store(result_pointer, result),
+        Ok(None) => {}
+        Err(error) if error.downcast_ref::<ContextError>().is_some() => return Ok(()),
+        Err(error) => return Err(error),
+    }
+    if context.basic_block().get_terminator().is_none() {
+        context.build_unconditional_branch(context.function().return_block);
+    }
+
+    context.build_throw_block(LongReturnPolicy::Propagate);
+    context.build_catch_block(LongReturnPolicy::Propagate);
+
+    context.set_basic_block(context.function().return_block);
+    let result = context.build_load(result_pointer, "fuzzing_result");
+    context.build_return(Some(&result));
+
+    context.verify()
+}
+
+///
+/// Creates the target machine of the harness modules.
+///
+fn target_machine() -> anyhow::Result<inkwell::targets::TargetMachine> {
+    inkwell::targets::Target::initialize_syncvm(&inkwell::targets::InitializationConfig::default());
+    let target = inkwell::targets::Target::from_name(TARGET_NAME)
+        .ok_or_else(|| anyhow::anyhow!("Target `{}` not found", TARGET_NAME))?;
+    target
+        .create_target_machine(
+            &inkwell::targets::TargetTriple::create(TARGET_NAME),
+            "",
+            "",
+            inkwell::OptimizationLevel::None,
+            inkwell::targets::RelocMode::Default,
+            inkwell::targets::CodeModel::Default,
+        )
+        .ok_or_else(|| anyhow::anyhow!("Target machine `{}` creation error", TARGET_NAME))
+}
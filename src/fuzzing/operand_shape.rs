@@ -0,0 +1,78 @@
+//!
+//! The fuzzing harness operand shape.
+//!
+
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The fuzzing harness operand shape.
+///
+/// Describes how an operand passed to a translator is produced, since many translators have
+/// dedicated paths for the compile-time constants and the extended booleans.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperandShape {
+    /// The harness function parameter with the index, unknown at compile time.
+    Parameter(usize),
+    /// The compile-time constant.
+    Constant(u64),
+    /// The compile-time constant with all bits set.
+    AllOnes,
+    /// The zero-extended boolean, computed from the parameter with the index.
+    Boolean(usize),
+}
+
+impl OperandShape {
+    /// The size of an encoded shape in bytes: the tag and the little-endian payload.
+    pub const ENCODED_SIZE: usize = 1 + std::mem::size_of::<u64>();
+
+    ///
+    /// Decodes the shape from `data`, which is zero-padded to `ENCODED_SIZE` if shorter.
+    ///
+    /// The parameter indexes are taken modulo `parameters`, which must be non-zero.
+    ///
+    pub fn decode(data: &[u8], parameters: usize) -> Self {
+        let mut encoded = [0u8; Self::ENCODED_SIZE];
+        let length = data.len().min(Self::ENCODED_SIZE);
+        encoded[..length].copy_from_slice(&data[..length]);
+
+        let mut payload = [0u8; std::mem::size_of::<u64>()];
+        payload.copy_from_slice(&encoded[1..]);
+        let payload = u64::from_le_bytes(payload);
+
+        match encoded[0] % 4 {
+            0 => Self::Parameter(payload as usize % parameters),
+            1 => Self::Constant(payload),
+            2 => Self::AllOnes,
+            _ => Self::Boolean(payload as usize % parameters),
+        }
+    }
+
+    ///
+    /// Builds the operand value, taking the unknown values from the harness `parameters`.
+    ///
+    pub fn build<'ctx, D>(
+        &self,
+        context: &Context<'ctx, D>,
+        parameters: &[inkwell::values::IntValue<'ctx>],
+    ) -> inkwell::values::IntValue<'ctx>
+    where
+        D: Dependency,
+    {
+        match self {
+            Self::Parameter(index) => parameters[*index],
+            Self::Constant(value) => context.field_const(*value),
+            Self::AllOnes => context.field_type().const_all_ones(),
+            Self::Boolean(index) => {
+                let boolean = context.builder().build_int_compare(
+                    inkwell::IntPredicate::NE,
+                    parameters[*index],
+                    context.field_const(0),
+                    "fuzzing_operand_boolean",
+                );
+                context.as_field_bool(boolean)
+            }
+        }
+    }
+}
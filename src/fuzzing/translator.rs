@@ -0,0 +1,126 @@
+//!
+//! The fuzzing harness translator.
+//!
+
+use crate::context::operands::Operands;
+use crate::context::Context;
+use crate::Dependency;
+
+///
+/// The fuzzing harness translator.
+///
+/// Selects the EVM translator driven by the harness.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Translator {
+    /// The `add` instruction.
+    Addition,
+    /// The `sub` instruction.
+    Subtraction,
+    /// The `mul` instruction.
+    Multiplication,
+    /// The `div` instruction.
+    Division,
+    /// The `mod` instruction.
+    Remainder,
+    /// The `sdiv` instruction.
+    DivisionSigned,
+    /// The `smod` instruction.
+    RemainderSigned,
+    /// The `or` instruction.
+    Or,
+    /// The `xor` instruction.
+    Xor,
+    /// The `and` instruction.
+    And,
+    /// The `shl` instruction.
+    ShiftLeft,
+    /// The `shr` instruction.
+    ShiftRight,
+    /// The `sar` instruction.
+    ShiftRightArithmetic,
+    /// The `byte` instruction.
+    Byte,
+    /// The `calldataload` instruction.
+    CallDataLoad,
+    /// The `calldatasize` instruction.
+    CallDataSize,
+    /// The `calldatacopy` instruction.
+    CallDataCopy,
+}
+
+impl Translator {
+    /// All translators in the selection order.
+    pub const ALL: [Self; 17] = [
+        Self::Addition,
+        Self::Subtraction,
+        Self::Multiplication,
+        Self::Division,
+        Self::Remainder,
+        Self::DivisionSigned,
+        Self::RemainderSigned,
+        Self::Or,
+        Self::Xor,
+        Self::And,
+        Self::ShiftLeft,
+        Self::ShiftRight,
+        Self::ShiftRightArithmetic,
+        Self::Byte,
+        Self::CallDataLoad,
+        Self::CallDataSize,
+        Self::CallDataCopy,
+    ];
+
+    ///
+    /// Selects the translator by the fuzzer-provided `byte`.
+    ///
+    pub fn from_byte(byte: u8) -> Self {
+        Self::ALL[byte as usize % Self::ALL.len()]
+    }
+
+    ///
+    /// Returns the number of the translator operands.
+    ///
+    pub fn input_size(&self) -> usize {
+        match self {
+            Self::CallDataSize => 0,
+            Self::CallDataLoad => 1,
+            Self::CallDataCopy => 3,
+            _ => 2,
+        }
+    }
+
+    ///
+    /// Translates the instruction with `operands` at the current insertion point.
+    ///
+    pub fn translate<'ctx, D>(
+        &self,
+        context: &mut Context<'ctx, D>,
+        operands: Operands<'ctx>,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+    where
+        D: Dependency,
+    {
+        match self {
+            Self::Addition => crate::evm::arithmetic::addition(context, operands),
+            Self::Subtraction => crate::evm::arithmetic::subtraction(context, operands),
+            Self::Multiplication => crate::evm::arithmetic::multiplication(context, operands),
+            Self::Division => crate::evm::arithmetic::division(context, operands),
+            Self::Remainder => crate::evm::arithmetic::remainder(context, operands),
+            Self::DivisionSigned => crate::evm::arithmetic::division_signed(context, operands),
+            Self::RemainderSigned => crate::evm::arithmetic::remainder_signed(context, operands),
+            Self::Or => crate::evm::bitwise::or(context, operands),
+            Self::Xor => crate::evm::bitwise::xor(context, operands),
+            Self::And => crate::evm::bitwise::and(context, operands),
+            Self::ShiftLeft => crate::evm::bitwise::shift_left(context, operands),
+            Self::ShiftRight => crate::evm::bitwise::shift_right(context, operands),
+            Self::ShiftRightArithmetic => {
+                crate::evm::bitwise::shift_right_arithmetic(context, operands)
+            }
+            Self::Byte => crate::evm::bitwise::byte(context, operands),
+            Self::CallDataLoad => crate::evm::calldata::load(context, operands),
+            Self::CallDataSize => crate::evm::calldata::size(context),
+            Self::CallDataCopy => crate::evm::calldata::copy(context, operands),
+        }
+    }
+}
@@ -3,31 +3,100 @@
 //!
 
 pub(crate) mod context;
+pub(crate) mod dependency;
 pub(crate) mod dump_flag;
 pub(crate) mod evm;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 
+pub use self::context::abi_type::AbiType;
+pub use self::context::account_method::AccountMethod;
 pub use self::context::address_space::AddressSpace;
+pub use self::context::alignment_verifier::violation::Violation as AlignmentViolation;
+pub use self::context::alignment_verifier::AlignmentVerifier;
 pub use self::context::argument::Argument;
+pub use self::context::artifact::Artifact;
+pub use self::context::assembly_hook::AssemblyHook;
+pub use self::context::cache::Cache;
+pub use self::context::call_definition::CallDefinition;
+pub use self::context::call_result::CallResult;
+pub use self::context::calldata_mode::CalldataMode;
+pub use self::context::capabilities::blobs::Blobs as BlobsBackend;
+pub use self::context::capabilities::keccak256::Keccak256 as Keccak256Backend;
+pub use self::context::capabilities::program_counter::ProgramCounter as ProgramCounterBackend;
+pub use self::context::capabilities::system_intrinsics::SystemIntrinsics;
+pub use self::context::capabilities::value_simulation::ValueSimulation;
+pub use self::context::capabilities::Capabilities as TargetCapabilities;
+pub use self::context::catch_payload::CatchPayload;
 pub use self::context::code_type::CodeType;
+pub use self::context::debug_tracer::DebugTracer;
+pub use self::context::dump::Dump;
+pub use self::context::eliminated_write::EliminatedWrite;
+pub use self::context::error::Error as ContextError;
 pub use self::context::evm_data::EVMData as ContextEVMData;
+pub use self::context::evm_version::EvmVersion;
+pub use self::context::exit_kind::ExitKind;
 pub use self::context::function::block::evm_data::EVMData as FunctionBlockEVMData;
 pub use self::context::function::block::key::Key as FunctionBlockKey;
 pub use self::context::function::block::Block as FunctionBlock;
+pub use self::context::function::bytes_copy::BytesCopy;
 pub use self::context::function::constructor::Constructor as ConstructorFunction;
 pub use self::context::function::entry::Entry as EntryFunction;
+pub use self::context::function::error_encoder::ErrorEncoder;
 pub use self::context::function::evm_data::EVMData as FunctionEVMData;
+pub use self::context::function::external_interface::ExternalInterface;
+pub use self::context::function::guard::Guard;
 pub use self::context::function::intrinsic::Intrinsic as IntrinsicFunction;
+pub use self::context::function::memory_compare::MemoryCompare;
+pub use self::context::function::nonreentrant_guard::NonreentrantGuard;
+pub use self::context::function::paymaster_flow::PaymasterFlow;
 pub use self::context::function::r#return::Return as FunctionReturn;
+pub use self::context::function::revert_on_failure::RevertOnFailure;
 pub use self::context::function::runtime::Runtime;
+pub use self::context::function::selector::policy::Policy as SelectorPolicy;
 pub use self::context::function::selector::Selector as SelectorFunction;
+pub use self::context::function::statistics::Statistics as FunctionStatistics;
+pub use self::context::function::stdlib::StdlibFunction;
 pub use self::context::function::Function;
+pub use self::context::gas_report::block::Block as GasReportBlock;
+pub use self::context::gas_report::cost_table::CostTable;
+pub use self::context::gas_report::function::Function as GasReportFunction;
+pub use self::context::gas_report::storage_access::StorageAccess as GasReportStorageAccess;
+pub use self::context::gas_report::GasReport;
+pub use self::context::header_codec::HeaderCodec;
+pub use self::context::long_return_policy::LongReturnPolicy;
+pub use self::context::naming_policy::NamingPolicy;
+pub use self::context::operands::Operands;
+pub use self::context::optimizer::settings::Settings as OptimizerSettings;
 pub use self::context::optimizer::Optimizer;
+pub use self::context::panic_code::PanicCode;
+pub use self::context::privileged_operation::PrivilegedOperation;
+pub use self::context::r#loop::unroll_hint::UnrollHint as LoopUnrollHint;
 pub use self::context::r#loop::Loop;
+pub use self::context::restricted_instruction::RestrictedInstruction;
+pub use self::context::size_report::duplicate::Duplicate as SizeReportDuplicate;
+pub use self::context::size_report::function::Function as SizeReportFunction;
+pub use self::context::size_report::SizeReport;
+pub use self::context::snapshot::Snapshot;
+pub use self::context::statistics::phase::Phase as StatisticsPhase;
+pub use self::context::statistics::Statistics;
+pub use self::context::storage_hint::StorageHint;
+pub use self::context::submodule::Submodule;
+pub use self::context::symbol::kind::Kind as SymbolKind;
+pub use self::context::symbol::Symbol;
+pub use self::context::versioned_instruction::VersionedInstruction;
+pub use self::context::warning::span::Span as WarningSpan;
+pub use self::context::warning::Warning;
 pub use self::context::Context;
+pub use self::dependency::output::Output as DependencyOutput;
+pub use self::dependency::request::Request as DependencyRequest;
+pub use self::dump_flag::set::Set as DumpFlagSet;
 pub use self::dump_flag::DumpFlag;
+pub use self::evm::account;
 pub use self::evm::arithmetic;
 pub use self::evm::bitwise;
 pub use self::evm::calldata;
+pub use self::evm::code;
 pub use self::evm::comparison;
 pub use self::evm::context as contract_context;
 pub use self::evm::contract;
@@ -37,9 +106,11 @@ pub use self::evm::hash;
 pub use self::evm::immutable;
 pub use self::evm::math;
 pub use self::evm::memory;
+pub use self::evm::paymaster;
 pub use self::evm::r#return;
 pub use self::evm::return_data;
 pub use self::evm::storage;
+pub use self::evm::value;
 
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -81,24 +152,108 @@ where
     }
 }
 
+///
+/// The object-safe counterpart of `WriteLLVM`, allowing heterogeneous lists of boxed items.
+///
+/// Implemented for every `WriteLLVM` item, so `Box<dyn WriteLLVMBoxed<D>>` is the type to use
+/// with `Context::declare_all` and `Context::define_all`.
+///
+#[allow(clippy::upper_case_acronyms)]
+pub trait WriteLLVMBoxed<D>
+where
+    D: Dependency,
+{
+    ///
+    /// Declares the boxed entity in the LLVM IR.
+    ///
+    fn declare_boxed(&mut self, context: &mut Context<D>) -> anyhow::Result<()>;
+
+    ///
+    /// Translates the boxed entity into LLVM IR.
+    ///
+    fn into_llvm_boxed(self: Box<Self>, context: &mut Context<D>) -> anyhow::Result<()>;
+}
+
+impl<D, T> WriteLLVMBoxed<D> for T
+where
+    D: Dependency,
+    T: WriteLLVM<D>,
+{
+    fn declare_boxed(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        self.declare(context)
+    }
+
+    fn into_llvm_boxed(self: Box<Self>, context: &mut Context<D>) -> anyhow::Result<()> {
+        (*self).into_llvm(context)
+    }
+}
+
+impl<D> WriteLLVM<D> for Box<dyn WriteLLVMBoxed<D>>
+where
+    D: Dependency,
+{
+    fn declare(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        self.as_mut().declare_boxed(context)
+    }
+
+    fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
+        self.into_llvm_boxed(context)
+    }
+}
+
 ///
 /// Implemented by items managing project dependencies.
 ///
 pub trait Dependency {
     ///
-    /// Compiles a project dependency.
+    /// Compiles a project dependency, returning its bytecode hash.
+    ///
+    /// The legacy interface kept for compatibility. New implementors should implement
+    /// `compile_request` instead.
     ///
     fn compile(
-        project: Arc<RwLock<Self>>,
+        _project: Arc<RwLock<Self>>,
         name: &str,
-        parent_name: &str,
-        optimization_level_middle: inkwell::OptimizationLevel,
-        optimization_level_back: inkwell::OptimizationLevel,
-        dump_flags: Vec<DumpFlag>,
-    ) -> anyhow::Result<String>;
+        _parent_name: &str,
+        _optimization_level_middle: inkwell::OptimizationLevel,
+        _optimization_level_back: inkwell::OptimizationLevel,
+        _dump_flags: Vec<DumpFlag>,
+    ) -> anyhow::Result<String> {
+        anyhow::bail!(
+            "The dependency `{}` cannot be compiled: the dependency manager implements neither `compile` nor `compile_request`",
+            name
+        )
+    }
+
+    ///
+    /// Compiles a project dependency described by the `request`.
+    ///
+    /// By default, forwards to the legacy `compile`, so only the output hash is set.
+    ///
+    fn compile_request(
+        project: Arc<RwLock<Self>>,
+        request: DependencyRequest,
+    ) -> anyhow::Result<DependencyOutput> {
+        let hash = Self::compile(
+            project,
+            request.name.as_str(),
+            request.parent_name.as_str(),
+            request.optimization_level_middle,
+            request.optimization_level_back,
+            request.dump_flags,
+        )?;
+        Ok(DependencyOutput::new(hash))
+    }
 
     ///
     /// Resolves a library address.
     ///
     fn resolve_library(project: Arc<RwLock<Self>>, path: &str) -> anyhow::Result<String>;
+
+    ///
+    /// Resolves a linker symbol constant, if it is known before linking.
+    ///
+    fn resolve_symbol(_project: Arc<RwLock<Self>>, _name: &str) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
 }